@@ -0,0 +1,5 @@
+drop function abac_authorize(abac_attribute[], abac_attribute[], abac_attribute[], uuid[]);
+alter function abac_authorize_allow_only(abac_attribute[], abac_attribute[], abac_attribute[], uuid[])
+  rename to abac_authorize;
+
+alter table abac_policy drop column effect;
@@ -0,0 +1,41 @@
+alter table abac_policy
+  add column effect text not null default 'allow' check (effect in ('allow', 'deny'));
+
+-- Deny-overrides combining: access is granted when at least one allowing policy matches
+-- and no denying policy matches within the requested namespaces.
+-- The upstream function of abac-rs is kept aside to be restored as is on rollback.
+alter function abac_authorize(abac_attribute[], abac_attribute[], abac_attribute[], uuid[])
+  rename to abac_authorize_allow_only;
+create function abac_authorize(
+  _subject abac_attribute[],
+  _object abac_attribute[],
+  _action abac_attribute[],
+  _namespace_ids uuid[]
+) returns bool as $$
+  with recursive
+  s(attr) as (
+    select unnest(_subject)
+    union
+    select e.outbound from abac_subject e inner join s on e.inbound = s.attr
+  ),
+  o(attr) as (
+    select unnest(_object)
+    union
+    select e.outbound from abac_object e inner join o on e.inbound = o.attr
+  ),
+  a(attr) as (
+    select unnest(_action)
+    union
+    select e.outbound from abac_action e inner join a on e.inbound = a.attr
+  ),
+  p as (
+    select effect
+    from abac_policy
+    where namespace_id = any(_namespace_ids)
+      and subject <@ array(select attr from s)
+      and object <@ array(select attr from o)
+      and action <@ array(select attr from a)
+  )
+  select exists(select 1 from p where effect = 'allow')
+    and not exists(select 1 from p where effect = 'deny')
+$$ language sql stable;
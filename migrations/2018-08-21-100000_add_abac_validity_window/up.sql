@@ -0,0 +1,69 @@
+alter table abac_subject
+  add column not_before timestamptz,
+  add column expires_at timestamptz,
+  add check (not_before < expires_at);
+alter table abac_object
+  add column not_before timestamptz,
+  add column expires_at timestamptz,
+  add check (not_before < expires_at);
+alter table abac_action
+  add column not_before timestamptz,
+  add column expires_at timestamptz,
+  add check (not_before < expires_at);
+alter table abac_policy
+  add column not_before timestamptz,
+  add column expires_at timestamptz,
+  add check (not_before < expires_at);
+
+create index abac_subject_expires_at_idx on abac_subject (expires_at) where expires_at is not null;
+create index abac_object_expires_at_idx on abac_object (expires_at) where expires_at is not null;
+create index abac_action_expires_at_idx on abac_action (expires_at) where expires_at is not null;
+create index abac_policy_expires_at_idx on abac_policy (expires_at) where expires_at is not null;
+
+-- A row takes part in authorization only within its validity window, unbounded sides are open.
+create function abac_is_active(
+  _not_before timestamptz,
+  _expires_at timestamptz
+) returns bool as $$
+  select (_not_before is null or _not_before <= now())
+    and (_expires_at is null or _expires_at > now())
+$$ language sql stable;
+
+drop function if exists abac_authorize(abac_attribute[], abac_attribute[], abac_attribute[], uuid[]);
+create function abac_authorize(
+  _subject abac_attribute[],
+  _object abac_attribute[],
+  _action abac_attribute[],
+  _namespace_ids uuid[]
+) returns bool as $$
+  with recursive
+  s(attr) as (
+    select unnest(_subject)
+    union
+    select e.outbound from abac_subject e inner join s on e.inbound = s.attr
+    where abac_is_active(e.not_before, e.expires_at)
+  ),
+  o(attr) as (
+    select unnest(_object)
+    union
+    select e.outbound from abac_object e inner join o on e.inbound = o.attr
+    where abac_is_active(e.not_before, e.expires_at)
+  ),
+  a(attr) as (
+    select unnest(_action)
+    union
+    select e.outbound from abac_action e inner join a on e.inbound = a.attr
+    where abac_is_active(e.not_before, e.expires_at)
+  ),
+  p as (
+    select effect
+    from abac_policy
+    where namespace_id = any(_namespace_ids)
+      and abac_is_active(not_before, expires_at)
+      and subject <@ array(select attr from s)
+      and object <@ array(select attr from o)
+      and action <@ array(select attr from a)
+  )
+  select exists(select 1 from p where effect = 'allow')
+    and not exists(select 1 from p where effect = 'deny')
+$$ language sql stable;
@@ -0,0 +1,2 @@
+-- Identifier of the only refresh token of the account that may still be used.
+alter table refresh_token add column jti uuid not null default gen_random_uuid();
@@ -0,0 +1,16 @@
+drop index refresh_token_account_id_idx;
+
+-- Only the most recent session of each account is kept.
+delete from refresh_token a
+  using refresh_token b
+  where a.account_id = b.account_id
+    and (a.created_at, a.id) < (b.created_at, b.id);
+
+alter table refresh_token drop constraint refresh_token_pkey;
+
+alter table refresh_token
+  drop column last_used_at,
+  drop column aud,
+  drop column id;
+
+alter table refresh_token add primary key (account_id);
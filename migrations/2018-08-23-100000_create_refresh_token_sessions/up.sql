@@ -0,0 +1,25 @@
+alter table refresh_token drop constraint refresh_token_pkey;
+
+-- The only refresh token an account had becomes its first session, keeping its keys and jti,
+-- so that tokens issued before keep working.
+alter table refresh_token
+  alter column account_id set not null,
+  add column id uuid not null default gen_random_uuid(),
+  add column aud text,
+  add column last_used_at timestamptz;
+
+-- Tokens weren't bound to an audience before, the one of the earliest identity is assumed.
+update refresh_token t
+  set aud = coalesce((
+    select n.label
+    from identity i
+    inner join namespace n on n.id = i.provider
+    where i.account_id = t.account_id
+    order by i.created_at
+    limit 1
+  ), '');
+
+alter table refresh_token alter column aud set not null;
+
+alter table refresh_token add primary key (id);
+create index refresh_token_account_id_idx on refresh_token (account_id);
@@ -0,0 +1,3 @@
+alter table account drop column tokens_revoked_before;
+
+drop table revoked_access_token;
@@ -0,0 +1,14 @@
+-- The token is identified along with the account, since anyone allowed to update
+-- an account may revoke its tokens, but not the tokens of other accounts.
+create table revoked_access_token (
+  jti uuid not null,
+  account_id uuid not null references account (id) on delete cascade,
+  expires_at timestamptz not null,
+  created_at timestamptz not null default now(),
+  primary key (jti, account_id)
+);
+
+create index revoked_access_token_expires_at_idx on revoked_access_token (expires_at);
+
+-- Tokens of the account issued before are revoked all at once.
+alter table account add column tokens_revoked_before timestamptz;
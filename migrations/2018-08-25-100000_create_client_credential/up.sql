@@ -0,0 +1,8 @@
+create table client_credential (
+  id uuid primary key default gen_random_uuid(),
+  account_id uuid not null references account (id) on delete cascade,
+  secret_hash bytea not null,
+  created_at timestamptz not null default now()
+);
+
+create index client_credential_account_id_idx on client_credential (account_id);
@@ -0,0 +1,11 @@
+create table api_key (
+  id uuid primary key default gen_random_uuid(),
+  account_id uuid not null references account (id) on delete cascade,
+  label text not null,
+  secret_hash bytea not null,
+  namespace_ids uuid[],
+  expires_at timestamptz,
+  created_at timestamptz not null default now()
+);
+
+create index api_key_account_id_idx on api_key (account_id);
@@ -0,0 +1 @@
+alter table refresh_token drop column scope;
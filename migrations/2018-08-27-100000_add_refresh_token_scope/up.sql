@@ -0,0 +1,2 @@
+-- Scope granted to the session, refreshed access tokens may only narrow it.
+alter table refresh_token add column scope text;
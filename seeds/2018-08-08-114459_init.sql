@@ -6,8 +6,8 @@ declare
 begin
   insert into account default values returning id into _a_iam_id;
 
-  insert into refresh_token (account_id, algorithm, keys)
-  values (_a_iam_id, 'HS256', array[gen_random_bytes(64)]);
+  insert into refresh_token (account_id, aud, algorithm, keys)
+  values (_a_iam_id, 'iam.netology-group.services', 'HS256', array[gen_random_bytes(64)]);
 
   insert into namespace (id, label, account_id)
   values (_n_iam_id, 'iam.netology-group.services', _a_iam_id);
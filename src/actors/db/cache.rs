@@ -0,0 +1,268 @@
+use abac::AbacAttribute;
+use uuid::Uuid;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use actors::db::authz::Authz;
+
+/// Authorization decisions shared by all `DbExecutor` threads.
+///
+/// Every change to ABAC links or policies drops all of the entries. Since links and
+/// policies also become active or expire on their own, entries are only kept for `ttl`.
+#[derive(Debug)]
+pub struct DecisionCache {
+    capacity: usize,
+    ttl: Duration,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    order: VecDeque<(String, Instant)>,
+    generation: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    granted: bool,
+    inserted_at: Instant,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+}
+
+impl DecisionCache {
+    /// Creates a cache holding up to `capacity` decisions, a zero capacity disables it.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        DecisionCache {
+            capacity,
+            ttl,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub fn from_settings() -> Self {
+        let settings = get_settings!();
+        Self::new(
+            settings.decision_cache.size,
+            Duration::from_secs(settings.decision_cache.ttl),
+        )
+    }
+
+    pub fn get(&self, key: &str) -> Option<bool> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let mut inner = self.lock();
+        let granted = match inner.entries.get(key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => Some(entry.granted),
+            _ => None,
+        };
+
+        if granted.is_some() {
+            inner.hits += 1;
+        } else {
+            inner.misses += 1;
+        }
+
+        granted
+    }
+
+    /// Current generation, to be taken before querying the decision to insert.
+    pub fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    /// Stores a decision unless the cache was invalidated since `generation` was taken,
+    /// in which case the decision may already be stale.
+    pub fn insert(&self, key: String, granted: bool, generation: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.lock();
+        if inner.generation != generation {
+            return;
+        }
+
+        let now = Instant::now();
+        while inner.entries.len() >= self.capacity || self.is_front_expired(&inner, now) {
+            let (queued_key, queued_at) = match inner.order.pop_front() {
+                Some(item) => item,
+                None => break,
+            };
+
+            // The key may have been inserted again after this item was queued.
+            if inner.entries.get(&queued_key).map(|e| e.inserted_at) == Some(queued_at) {
+                inner.entries.remove(&queued_key);
+            }
+        }
+
+        inner.order.push_back((key.clone(), now));
+        inner.entries.insert(
+            key,
+            Entry {
+                granted,
+                inserted_at: now,
+            },
+        );
+    }
+
+    pub fn invalidate(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.order.clear();
+        inner.generation += 1;
+    }
+
+    pub fn stats(&self) -> Stats {
+        let inner = self.lock();
+        Stats {
+            hits: inner.hits,
+            misses: inner.misses,
+            size: inner.entries.len(),
+        }
+    }
+
+    fn is_front_expired(&self, inner: &Inner, now: Instant) -> bool {
+        match inner.order.front() {
+            Some((_, inserted_at)) => now.duration_since(*inserted_at) >= self.ttl,
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Inner> {
+        self.inner.lock().expect("Decision cache mutex is poisoned")
+    }
+}
+
+/// Builds a key which is the same for requests differing only in the order or
+/// repetition of namespaces and attributes, as these don't affect the decision.
+pub fn key(msg: &Authz) -> String {
+    let mut namespace_ids = msg.namespace_ids.clone();
+    namespace_ids.sort();
+    namespace_ids.dedup();
+
+    json!([
+        namespace_ids,
+        normalize(&msg.subject),
+        normalize(&msg.object),
+        normalize(&msg.action),
+    ]).to_string()
+}
+
+fn normalize(attributes: &[AbacAttribute]) -> Vec<(Uuid, &str, &str)> {
+    let mut attributes = attributes
+        .iter()
+        .map(|a| (a.namespace_id, a.key.as_str(), a.value.as_str()))
+        .collect::<Vec<_>>();
+    attributes.sort();
+    attributes.dedup();
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_authz(subject: Vec<&str>) -> Authz {
+        let ns_id = Uuid::nil();
+        let attribute = |value: &str| AbacAttribute {
+            namespace_id: ns_id,
+            key: "uri".to_owned(),
+            value: value.to_owned(),
+        };
+
+        Authz {
+            namespace_ids: vec![ns_id, ns_id],
+            subject: subject.into_iter().map(|value| attribute(value)).collect(),
+            object: vec![attribute("room/1")],
+            action: vec![attribute("operation/read")],
+        }
+    }
+
+    #[test]
+    fn key_ignores_order_and_duplicates() {
+        let a = build_authz(vec!["account/1", "account/2"]);
+        let b = build_authz(vec!["account/2", "account/1", "account/2"]);
+        assert_eq!(key(&a), key(&b));
+
+        let c = build_authz(vec!["account/1"]);
+        assert_ne!(key(&a), key(&c));
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let cache = DecisionCache::new(10, Duration::from_secs(60));
+
+        assert_eq!(cache.get("a"), None);
+        let generation = cache.generation();
+        cache.insert("a".to_owned(), true, generation);
+        assert_eq!(cache.get("a"), Some(true));
+
+        let stats = cache.stats();
+        assert_eq!(
+            stats,
+            Stats {
+                hits: 1,
+                misses: 1,
+                size: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn evicts_oldest_when_full() {
+        let cache = DecisionCache::new(2, Duration::from_secs(60));
+        let generation = cache.generation();
+        cache.insert("a".to_owned(), true, generation);
+        cache.insert("b".to_owned(), true, generation);
+        cache.insert("c".to_owned(), false, generation);
+
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(true));
+        assert_eq!(cache.get("c"), Some(false));
+    }
+
+    #[test]
+    fn expires_entries() {
+        let cache = DecisionCache::new(10, Duration::from_secs(0));
+        let generation = cache.generation();
+        cache.insert("a".to_owned(), true, generation);
+
+        assert_eq!(cache.get("a"), None);
+    }
+
+    #[test]
+    fn discards_decisions_taken_before_invalidation() {
+        let cache = DecisionCache::new(10, Duration::from_secs(60));
+        let generation = cache.generation();
+        cache.insert("a".to_owned(), true, generation);
+
+        cache.invalidate();
+        assert_eq!(cache.get("a"), None);
+
+        cache.insert("b".to_owned(), true, generation);
+        assert_eq!(cache.get("b"), None);
+    }
+
+    #[test]
+    fn disabled_with_zero_capacity() {
+        let cache = DecisionCache::new(0, Duration::from_secs(60));
+        let generation = cache.generation();
+        cache.insert("a".to_owned(), true, generation);
+
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.stats().misses, 0);
+    }
+}
@@ -0,0 +1,17 @@
+use abac::AbacAttribute;
+use diesel;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "{}", _0)]
+    Db(#[cause] diesel::result::Error),
+
+    #[fail(display = "Link would create a cycle")]
+    Cycle(Vec<AbacAttribute>),
+}
+
+impl From<diesel::result::Error> for Error {
+    fn from(e: diesel::result::Error) -> Self {
+        Error::Db(e)
+    }
+}
@@ -1,8 +1,9 @@
-use abac::{models::AbacAction, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::{self, prelude::*};
 
 use actors::DbExecutor;
+use models::AbacAction;
 use rpc::abac_action_attr::delete;
 
 #[derive(Debug)]
@@ -20,7 +21,7 @@ impl Handler<Delete> for DbExecutor {
 
     fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        call(conn, msg)
+        self.invalidate_on_success(call(conn, msg))
     }
 }
 
@@ -33,8 +34,8 @@ impl From<delete::Request> for Delete {
     }
 }
 
-fn call(conn: &PgConnection, msg: Delete) -> QueryResult<AbacAction> {
-    use abac::schema::abac_action::dsl::*;
+pub fn call(conn: &PgConnection, msg: Delete) -> QueryResult<AbacAction> {
+    use schema::abac_action::dsl::*;
 
     let pk = (msg.inbound, msg.outbound);
     let target = abac_action.find(pk);
@@ -1,8 +1,9 @@
-use abac::{models::AbacAction, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::prelude::*;
 
 use actors::DbExecutor;
+use models::AbacAction;
 use rpc::abac_action_attr::read;
 
 #[derive(Debug)]
@@ -34,7 +35,7 @@ impl From<read::Request> for Find {
 }
 
 fn call(conn: &PgConnection, msg: Find) -> QueryResult<AbacAction> {
-    use abac::schema::abac_action::dsl::*;
+    use schema::abac_action::dsl::*;
 
     let pk = (msg.inbound, msg.outbound);
     let action = abac_action.find(pk).get_result(conn)?;
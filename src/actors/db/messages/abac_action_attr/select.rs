@@ -1,9 +1,9 @@
-use abac::models::AbacAction;
 use actix::prelude::*;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::AbacAction;
 
 #[derive(Debug)]
 pub struct Select {
@@ -28,29 +28,35 @@ impl Handler<Select> for DbExecutor {
 
 fn call(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<AbacAction>> {
     use abac::dsl::*;
-    use abac::schema::abac_action;
     use diesel::dsl::any;
+    use functions::abac_is_active;
+    use schema::abac_action;
 
     let mut query = abac_action::table
         .filter(
             abac_action::inbound
                 .namespace_id()
-                .eq(any(&msg.namespace_ids)),
-        )
-        .or_filter(
-            abac_action::outbound
-                .namespace_id()
-                .eq(any(&msg.namespace_ids)),
+                .eq(any(&msg.namespace_ids))
+                .or(abac_action::outbound
+                    .namespace_id()
+                    .eq(any(&msg.namespace_ids))),
         )
+        .filter(abac_is_active(
+            abac_action::not_before,
+            abac_action::expires_at,
+        ))
         .order(abac_action::created_at.asc())
         .limit(i64::from(msg.limit))
         .offset(i64::from(msg.offset))
         .into_boxed();
 
     if let Some(ref key) = msg.key {
-        query = query
-            .filter(abac_action::inbound.key().eq(key))
-            .or_filter(abac_action::outbound.key().eq(key));
+        query = query.filter(
+            abac_action::inbound
+                .key()
+                .eq(key)
+                .or(abac_action::outbound.key().eq(key)),
+        );
     }
 
     query.load(conn)
@@ -1,8 +1,9 @@
-use abac::{models::AbacObject, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::{self, prelude::*};
 
 use actors::DbExecutor;
+use models::AbacObject;
 use rpc::abac_object_attr::delete;
 
 #[derive(Debug)]
@@ -20,7 +21,7 @@ impl Handler<Delete> for DbExecutor {
 
     fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        call(conn, msg)
+        self.invalidate_on_success(call(conn, msg))
     }
 }
 
@@ -33,8 +34,8 @@ impl From<delete::Request> for Delete {
     }
 }
 
-fn call(conn: &PgConnection, msg: Delete) -> QueryResult<AbacObject> {
-    use abac::schema::abac_object::dsl::*;
+pub fn call(conn: &PgConnection, msg: Delete) -> QueryResult<AbacObject> {
+    use schema::abac_object::dsl::*;
 
     let pk = (msg.inbound, msg.outbound);
     let target = abac_object.find(pk);
@@ -1,8 +1,9 @@
-use abac::{models::AbacObject, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::prelude::*;
 
 use actors::DbExecutor;
+use models::AbacObject;
 use rpc::abac_object_attr::read;
 
 #[derive(Debug)]
@@ -34,7 +35,7 @@ impl From<read::Request> for Find {
 }
 
 fn call(conn: &PgConnection, msg: Find) -> QueryResult<AbacObject> {
-    use abac::schema::abac_object::dsl::*;
+    use schema::abac_object::dsl::*;
 
     let pk = (msg.inbound, msg.outbound);
     let object = abac_object.find(pk).get_result(conn)?;
@@ -1,29 +1,34 @@
-use abac::{
-    models::{AbacObject, NewAbacObject},
-    AbacAttribute,
-};
+use abac::AbacAttribute;
 use actix::prelude::*;
+use chrono::{DateTime, Utc};
 use diesel::{self, prelude::*};
 
+use actors::db::{
+    tree::{self, CollectionKind},
+    Error,
+};
 use actors::DbExecutor;
+use models::{AbacObject, NewAbacObject};
 use rpc::abac_object_attr::create;
 
 #[derive(Debug)]
 pub struct Insert {
     pub inbound: AbacAttribute,
     pub outbound: AbacAttribute,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl Message for Insert {
-    type Result = QueryResult<AbacObject>;
+    type Result = Result<AbacObject, Error>;
 }
 
 impl Handler<Insert> for DbExecutor {
-    type Result = QueryResult<AbacObject>;
+    type Result = Result<AbacObject, Error>;
 
     fn handle(&mut self, msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        call(conn, msg)
+        self.invalidate_on_success(call(conn, msg))
     }
 }
 
@@ -32,19 +37,37 @@ impl From<create::Request> for Insert {
         Insert {
             inbound: req.inbound,
             outbound: req.outbound,
+            not_before: req.validity.not_before,
+            expires_at: req.validity.expires_at,
         }
     }
 }
 
-fn call(conn: &PgConnection, msg: Insert) -> QueryResult<AbacObject> {
-    use abac::schema::abac_object;
+pub fn call(conn: &PgConnection, msg: Insert) -> Result<AbacObject, Error> {
+    use schema::abac_object;
+
+    conn.transaction(|| {
+        // Concurrent inserts are serialized, otherwise two of them could close a cycle together.
+        diesel::sql_query("select pg_advisory_xact_lock(hashtext('abac_object'))").execute(conn)?;
+
+        let kind = CollectionKind::AbacObject;
+        if let Some(path) = tree::find_path(conn, kind, &msg.outbound, &msg.inbound)? {
+            let mut cycle = vec![msg.inbound.clone()];
+            cycle.extend(path);
+            return Err(Error::Cycle(cycle));
+        }
+
+        let changeset = NewAbacObject {
+            inbound: msg.inbound,
+            outbound: msg.outbound,
+            not_before: msg.not_before,
+            expires_at: msg.expires_at,
+        };
 
-    let changeset = NewAbacObject {
-        inbound: msg.inbound,
-        outbound: msg.outbound,
-    };
+        let link = diesel::insert_into(abac_object::table)
+            .values(changeset)
+            .get_result(conn)?;
 
-    diesel::insert_into(abac_object::table)
-        .values(changeset)
-        .get_result(conn)
+        Ok(link)
+    })
 }
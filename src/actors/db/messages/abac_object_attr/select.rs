@@ -1,9 +1,9 @@
-use abac::models::AbacObject;
 use actix::prelude::*;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::AbacObject;
 
 #[derive(Debug)]
 pub struct Select {
@@ -28,29 +28,35 @@ impl Handler<Select> for DbExecutor {
 
 fn select(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<AbacObject>> {
     use abac::dsl::*;
-    use abac::schema::abac_object;
     use diesel::dsl::any;
+    use functions::abac_is_active;
+    use schema::abac_object;
 
     let mut query = abac_object::table
         .filter(
             abac_object::inbound
                 .namespace_id()
-                .eq(any(&msg.namespace_ids)),
-        )
-        .or_filter(
-            abac_object::outbound
-                .namespace_id()
-                .eq(any(&msg.namespace_ids)),
+                .eq(any(&msg.namespace_ids))
+                .or(abac_object::outbound
+                    .namespace_id()
+                    .eq(any(&msg.namespace_ids))),
         )
+        .filter(abac_is_active(
+            abac_object::not_before,
+            abac_object::expires_at,
+        ))
         .order(abac_object::created_at.asc())
         .limit(i64::from(msg.limit))
         .offset(i64::from(msg.offset))
         .into_boxed();
 
     if let Some(ref key) = msg.key {
-        query = query
-            .filter(abac_object::inbound.key().eq(key))
-            .or_filter(abac_object::outbound.key().eq(key));
+        query = query.filter(
+            abac_object::inbound
+                .key()
+                .eq(key)
+                .or(abac_object::outbound.key().eq(key)),
+        );
     }
 
     query.load(conn)
@@ -1,9 +1,10 @@
-use abac::{models::AbacPolicy, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::{self, prelude::*};
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::AbacPolicy;
 use rpc::abac_policy::delete;
 
 #[derive(Debug)]
@@ -23,7 +24,7 @@ impl Handler<Delete> for DbExecutor {
 
     fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        call(conn, msg)
+        self.invalidate_on_success(call(conn, msg))
     }
 }
 
@@ -38,8 +39,8 @@ impl From<delete::Request> for Delete {
     }
 }
 
-fn call(conn: &PgConnection, msg: Delete) -> QueryResult<AbacPolicy> {
-    use abac::schema::abac_policy::dsl::*;
+pub fn call(conn: &PgConnection, msg: Delete) -> QueryResult<AbacPolicy> {
+    use schema::abac_policy::dsl::*;
 
     let pk = (msg.subject, msg.object, msg.action, msg.namespace_id);
     let target = abac_policy.find(pk);
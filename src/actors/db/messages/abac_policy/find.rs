@@ -1,9 +1,10 @@
-use abac::{models::AbacPolicy, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::AbacPolicy;
 use rpc::abac_policy::read;
 
 #[derive(Debug)]
@@ -39,7 +40,7 @@ impl From<read::Request> for Find {
 }
 
 fn call(conn: &PgConnection, msg: Find) -> QueryResult<AbacPolicy> {
-    use abac::schema::abac_policy::dsl::*;
+    use schema::abac_policy::dsl::*;
 
     let pk = (msg.subject, msg.object, msg.action, msg.namespace_id);
     let policy = abac_policy.find(pk).get_result(conn)?;
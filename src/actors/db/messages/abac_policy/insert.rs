@@ -1,12 +1,11 @@
-use abac::{
-    models::{AbacPolicy, NewAbacPolicy},
-    AbacAttribute,
-};
+use abac::AbacAttribute;
 use actix::prelude::*;
+use chrono::{DateTime, Utc};
 use diesel::{self, prelude::*};
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::{abac_policy::Effect, AbacPolicy, NewAbacPolicy};
 use rpc::abac_policy::create;
 
 #[derive(Debug)]
@@ -15,6 +14,9 @@ pub struct Insert {
     pub subject: Vec<AbacAttribute>,
     pub object: Vec<AbacAttribute>,
     pub action: Vec<AbacAttribute>,
+    pub effect: Effect,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl Message for Insert {
@@ -26,7 +28,7 @@ impl Handler<Insert> for DbExecutor {
 
     fn handle(&mut self, msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        call(conn, msg)
+        self.invalidate_on_success(call(conn, msg))
     }
 }
 
@@ -37,18 +39,24 @@ impl From<create::Request> for Insert {
             subject: req.subject,
             object: req.object,
             action: req.action,
+            effect: req.effect,
+            not_before: req.validity.not_before,
+            expires_at: req.validity.expires_at,
         }
     }
 }
 
-fn call(conn: &PgConnection, msg: Insert) -> QueryResult<AbacPolicy> {
-    use abac::schema::abac_policy;
+pub fn call(conn: &PgConnection, msg: Insert) -> QueryResult<AbacPolicy> {
+    use schema::abac_policy;
 
     let changeset = NewAbacPolicy {
         namespace_id: msg.namespace_id,
         subject: msg.subject,
         object: msg.object,
         action: msg.action,
+        effect: msg.effect,
+        not_before: msg.not_before,
+        expires_at: msg.expires_at,
     };
 
     diesel::insert_into(abac_policy::table)
@@ -1,9 +1,9 @@
-use abac::models::AbacPolicy;
 use actix::prelude::*;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::AbacPolicy;
 
 #[derive(Debug)]
 pub struct Select {
@@ -26,11 +26,16 @@ impl Handler<Select> for DbExecutor {
 }
 
 fn select(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<AbacPolicy>> {
-    use abac::schema::abac_policy;
     use diesel::dsl::any;
+    use functions::abac_is_active;
+    use schema::abac_policy;
 
     let query = abac_policy::table
         .filter(abac_policy::namespace_id.eq(any(&msg.namespace_ids)))
+        .filter(abac_is_active(
+            abac_policy::not_before,
+            abac_policy::expires_at,
+        ))
         .order(abac_policy::created_at.asc())
         .limit(i64::from(msg.limit))
         .offset(i64::from(msg.offset));
@@ -1,8 +1,9 @@
-use abac::{models::AbacSubject, AbacAttribute};
+use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::prelude::*;
 
 use actors::DbExecutor;
+use models::AbacSubject;
 use rpc::abac_subject_attr::read;
 
 #[derive(Debug)]
@@ -34,7 +35,7 @@ impl From<read::Request> for Find {
 }
 
 fn call(conn: &PgConnection, msg: Find) -> QueryResult<AbacSubject> {
-    use abac::schema::abac_subject::dsl::*;
+    use schema::abac_subject::dsl::*;
 
     let pk = (msg.inbound, msg.outbound);
     let subject = abac_subject.find(pk).get_result(conn)?;
@@ -1,9 +1,9 @@
-use abac::models::AbacSubject;
 use actix::prelude::*;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use actors::DbExecutor;
+use models::AbacSubject;
 
 #[derive(Debug)]
 pub struct Select {
@@ -28,29 +28,35 @@ impl Handler<Select> for DbExecutor {
 
 fn call(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<AbacSubject>> {
     use abac::dsl::*;
-    use abac::schema::abac_subject;
     use diesel::dsl::any;
+    use functions::abac_is_active;
+    use schema::abac_subject;
 
     let mut query = abac_subject::table
         .filter(
             abac_subject::inbound
                 .namespace_id()
-                .eq(any(&msg.namespace_ids)),
-        )
-        .or_filter(
-            abac_subject::outbound
-                .namespace_id()
-                .eq(any(&msg.namespace_ids)),
+                .eq(any(&msg.namespace_ids))
+                .or(abac_subject::outbound
+                    .namespace_id()
+                    .eq(any(&msg.namespace_ids))),
         )
+        .filter(abac_is_active(
+            abac_subject::not_before,
+            abac_subject::expires_at,
+        ))
         .order(abac_subject::created_at.asc())
         .limit(i64::from(msg.limit))
         .offset(i64::from(msg.offset))
         .into_boxed();
 
     if let Some(ref key) = msg.key {
-        query = query
-            .filter(abac_subject::inbound.key().eq(key))
-            .or_filter(abac_subject::outbound.key().eq(key));
+        query = query.filter(
+            abac_subject::inbound
+                .key()
+                .eq(key)
+                .or(abac_subject::outbound.key().eq(key)),
+        );
     }
 
     query.load(conn)
@@ -25,7 +25,7 @@ impl Handler<Delete> for DbExecutor {
 
     fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        delete(conn, msg.id)
+        self.invalidate_on_success(delete(conn, msg.id))
     }
 }
 
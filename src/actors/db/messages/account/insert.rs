@@ -24,7 +24,7 @@ impl Handler<Insert> for DbExecutor {
 
     fn handle(&mut self, _msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        insert_account(conn)
+        self.invalidate_on_success(insert_account(conn))
     }
 }
 
@@ -1,4 +1,6 @@
 pub mod delete;
 pub mod find;
 pub mod insert;
+pub mod related;
+pub mod select;
 pub mod update;
@@ -0,0 +1,76 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+
+use actors::DbExecutor;
+use models::{Account, Identity, Namespace};
+
+/// Loads identities and namespaces of the accounts, each kind only when it's requested.
+#[derive(Debug)]
+pub struct SelectRelated {
+    pub accounts: Vec<Account>,
+    pub identities: bool,
+    pub namespaces: bool,
+}
+
+#[derive(Debug)]
+pub struct Related {
+    pub account: Account,
+    pub identities: Option<Vec<Identity>>,
+    pub namespaces: Option<Vec<Namespace>>,
+}
+
+impl Message for SelectRelated {
+    type Result = QueryResult<Vec<Related>>;
+}
+
+impl Handler<SelectRelated> for DbExecutor {
+    type Result = QueryResult<Vec<Related>>;
+
+    fn handle(&mut self, msg: SelectRelated, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        select_related(conn, msg)
+    }
+}
+
+fn select_related(conn: &PgConnection, msg: SelectRelated) -> QueryResult<Vec<Related>> {
+    use schema::{identity, namespace};
+
+    let accounts = msg.accounts;
+
+    let identities: Vec<Option<Vec<Identity>>> = if msg.identities {
+        Identity::belonging_to(&accounts)
+            .order(identity::created_at.asc())
+            .load::<Identity>(conn)?
+            .grouped_by(&accounts)
+            .into_iter()
+            .map(Some)
+            .collect()
+    } else {
+        accounts.iter().map(|_| None).collect()
+    };
+
+    let namespaces: Vec<Option<Vec<Namespace>>> = if msg.namespaces {
+        Namespace::belonging_to(&accounts)
+            .filter(namespace::deleted_at.is_null())
+            .order(namespace::created_at.asc())
+            .load::<Namespace>(conn)?
+            .grouped_by(&accounts)
+            .into_iter()
+            .map(Some)
+            .collect()
+    } else {
+        accounts.iter().map(|_| None).collect()
+    };
+
+    let related = accounts
+        .into_iter()
+        .zip(identities.into_iter().zip(namespaces))
+        .map(|(account, (identities, namespaces))| Related {
+            account,
+            identities,
+            namespaces,
+        })
+        .collect();
+
+    Ok(related)
+}
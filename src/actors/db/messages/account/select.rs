@@ -0,0 +1,50 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+
+use actors::DbExecutor;
+use models::Account;
+use schema::account;
+
+/// Selects either deleted or not deleted accounts, optionally filtered by their disabled state.
+#[derive(Debug)]
+pub struct Select {
+    pub disabled: Option<bool>,
+    pub deleted: bool,
+    pub limit: u16,
+    pub offset: u16,
+}
+
+impl Message for Select {
+    type Result = QueryResult<Vec<Account>>;
+}
+
+impl Handler<Select> for DbExecutor {
+    type Result = QueryResult<Vec<Account>>;
+
+    fn handle(&mut self, msg: Select, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        select_accounts(conn, &msg)
+    }
+}
+
+fn select_accounts(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<Account>> {
+    let mut query = account::table
+        .order(account::id.asc())
+        .limit(i64::from(msg.limit))
+        .offset(i64::from(msg.offset))
+        .into_boxed();
+
+    if msg.deleted {
+        query = query.filter(account::deleted_at.is_not_null());
+    } else {
+        query = query.filter(account::deleted_at.is_null());
+    }
+
+    match msg.disabled {
+        Some(true) => query = query.filter(account::disabled_at.is_not_null()),
+        Some(false) => query = query.filter(account::disabled_at.is_null()),
+        None => (),
+    }
+
+    query.load(conn)
+}
@@ -8,6 +8,7 @@ use schema::account;
 
 pub use self::disable::Disable;
 pub use self::enable::Enable;
+pub use self::revoke_tokens::RevokeTokens;
 
 mod disable {
     use super::*;
@@ -62,3 +63,49 @@ mod enable {
             .get_result(conn)
     }
 }
+
+mod revoke_tokens {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::*;
+
+    /// Revokes all of the tokens of the account issued before `issued_before`,
+    /// unless they have already been revoked up to a later moment.
+    #[derive(Debug)]
+    pub struct RevokeTokens {
+        pub id: Uuid,
+        pub issued_before: DateTime<Utc>,
+    }
+
+    impl Message for RevokeTokens {
+        type Result = QueryResult<Account>;
+    }
+
+    impl Handler<RevokeTokens> for DbExecutor {
+        type Result = QueryResult<Account>;
+
+        fn handle(&mut self, msg: RevokeTokens, _ctx: &mut Self::Context) -> Self::Result {
+            let conn = &self.0.get().unwrap();
+            revoke_tokens(conn, msg.id, msg.issued_before)
+        }
+    }
+
+    fn revoke_tokens(
+        conn: &PgConnection,
+        id: Uuid,
+        issued_before: DateTime<Utc>,
+    ) -> QueryResult<Account> {
+        use functions::{coalesce, greatest};
+
+        // Tokens carry the moment they were issued at in whole seconds.
+        let issued_before = Utc.timestamp(issued_before.timestamp(), 0);
+        let revoked_before = greatest(
+            coalesce(account::tokens_revoked_before, issued_before),
+            issued_before,
+        );
+
+        diesel::update(account::table.find(id))
+            .set(account::tokens_revoked_before.eq(revoked_before.nullable()))
+            .get_result(conn)
+    }
+}
@@ -0,0 +1,35 @@
+use actix::prelude::*;
+use diesel::{self, prelude::*};
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use models::ApiKey;
+use schema::api_key;
+
+/// Deletes the key `id`, provided it belongs to the account.
+#[derive(Debug)]
+pub struct Delete {
+    pub id: Uuid,
+    pub account_id: Uuid,
+}
+
+impl Message for Delete {
+    type Result = QueryResult<ApiKey>;
+}
+
+impl Handler<Delete> for DbExecutor {
+    type Result = QueryResult<ApiKey>;
+
+    fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        delete_key(conn, &msg)
+    }
+}
+
+fn delete_key(conn: &PgConnection, msg: &Delete) -> QueryResult<ApiKey> {
+    let target = api_key::table
+        .filter(api_key::id.eq(msg.id))
+        .filter(api_key::account_id.eq(msg.account_id));
+
+    diesel::delete(target).get_result(conn)
+}
@@ -0,0 +1,33 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use models::{Account, ApiKey};
+use schema::api_key;
+
+/// Finds the key along with its account to authenticate a request.
+#[derive(Debug)]
+pub struct FindWithAccount(pub Uuid);
+
+impl Message for FindWithAccount {
+    type Result = QueryResult<(ApiKey, Account)>;
+}
+
+impl Handler<FindWithAccount> for DbExecutor {
+    type Result = QueryResult<(ApiKey, Account)>;
+
+    fn handle(&mut self, msg: FindWithAccount, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        find_key_with_account(conn, msg.0)
+    }
+}
+
+fn find_key_with_account(conn: &PgConnection, id: Uuid) -> QueryResult<(ApiKey, Account)> {
+    use schema::account;
+
+    api_key::table
+        .find(id)
+        .inner_join(account::table)
+        .get_result(conn)
+}
@@ -0,0 +1,28 @@
+use actix::prelude::*;
+use diesel::{self, prelude::*};
+
+use actors::DbExecutor;
+use models::{ApiKey, NewApiKey};
+use schema::api_key;
+
+#[derive(Debug)]
+pub struct Insert(pub NewApiKey);
+
+impl Message for Insert {
+    type Result = QueryResult<ApiKey>;
+}
+
+impl Handler<Insert> for DbExecutor {
+    type Result = QueryResult<ApiKey>;
+
+    fn handle(&mut self, msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        insert_key(conn, &msg.0)
+    }
+}
+
+pub fn insert_key(conn: &PgConnection, key: &NewApiKey) -> QueryResult<ApiKey> {
+    diesel::insert_into(api_key::table)
+        .values(key)
+        .get_result(conn)
+}
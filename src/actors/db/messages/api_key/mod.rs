@@ -0,0 +1,4 @@
+pub mod delete;
+pub mod find;
+pub mod insert;
+pub mod select;
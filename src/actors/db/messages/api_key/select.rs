@@ -0,0 +1,37 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use models::ApiKey;
+use schema::api_key;
+
+#[derive(Debug)]
+pub struct Select {
+    pub account_id: Uuid,
+    pub limit: u16,
+    pub offset: u16,
+}
+
+impl Message for Select {
+    type Result = QueryResult<Vec<ApiKey>>;
+}
+
+impl Handler<Select> for DbExecutor {
+    type Result = QueryResult<Vec<ApiKey>>;
+
+    fn handle(&mut self, msg: Select, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        select_keys(conn, &msg)
+    }
+}
+
+fn select_keys(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<ApiKey>> {
+    let query = api_key::table
+        .filter(api_key::account_id.eq(msg.account_id))
+        .order((api_key::created_at.asc(), api_key::id.asc()))
+        .limit(i64::from(msg.limit))
+        .offset(i64::from(msg.offset));
+
+    query.load(conn)
+}
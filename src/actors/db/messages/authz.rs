@@ -1,8 +1,10 @@
 use abac::AbacAttribute;
 use actix::prelude::*;
 use diesel::{self, prelude::*};
+use serde_json;
 use uuid::Uuid;
 
+use actors::db::cache;
 use actors::DbExecutor;
 use rpc::authz::Request;
 
@@ -22,8 +24,18 @@ impl Handler<Authz> for DbExecutor {
     type Result = QueryResult<bool>;
 
     fn handle(&mut self, msg: Authz, _ctx: &mut Self::Context) -> Self::Result {
+        let key = cache::key(&msg);
+        if let Some(granted) = self.1.get(&key) {
+            debug!("authz cache hit, {:?}", self.1.stats());
+            return Ok(granted);
+        }
+
+        let generation = self.1.generation();
         let conn = &self.0.get().expect("Failed to get a connection from pool");
-        call(conn, &msg)
+        let granted = call(conn, &msg)?;
+        self.1.insert(key, granted, generation);
+
+        Ok(granted)
     }
 }
 
@@ -38,7 +50,23 @@ impl From<Request> for Authz {
     }
 }
 
-fn call(conn: &PgConnection, msg: &Authz) -> QueryResult<bool> {
+#[derive(Debug)]
+pub struct AuthzBatch(pub Vec<(String, Authz)>);
+
+impl Message for AuthzBatch {
+    type Result = QueryResult<Vec<(String, bool)>>;
+}
+
+impl Handler<AuthzBatch> for DbExecutor {
+    type Result = QueryResult<Vec<(String, bool)>>;
+
+    fn handle(&mut self, msg: AuthzBatch, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().expect("Failed to get a connection from pool");
+        call_batch(conn, &msg)
+    }
+}
+
+pub fn call(conn: &PgConnection, msg: &Authz) -> QueryResult<bool> {
     use abac::functions::abac_authorize;
 
     let granted = diesel::select(abac_authorize(
@@ -50,3 +78,71 @@ fn call(conn: &PgConnection, msg: &Authz) -> QueryResult<bool> {
 
     Ok(granted)
 }
+
+#[derive(Debug, QueryableByName)]
+struct Decision {
+    #[sql_type = "diesel::sql_types::Text"]
+    id: String,
+    #[sql_type = "diesel::sql_types::Bool"]
+    granted: bool,
+}
+
+pub fn call_batch(conn: &PgConnection, msg: &AuthzBatch) -> QueryResult<Vec<(String, bool)>> {
+    use diesel::sql_types::Jsonb;
+
+    if msg.0.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // All checks are passed as a single JSON document and unpacked into `abac_authorize`
+    // arguments on the database side, so the whole batch costs one round-trip.
+    // Decisions are returned in the order of the checks.
+    let checks = msg
+        .0
+        .iter()
+        .map(|(id, authz)| {
+            json!({
+                "id": id,
+                "namespace_ids": authz.namespace_ids,
+                "subject": authz.subject,
+                "object": authz.object,
+                "action": authz.action,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let query = r#"
+        select
+            c.id,
+            abac_authorize(
+                array(
+                    select jsonb_populate_record(null::abac_attribute, a)
+                    from jsonb_array_elements(c.subject) a
+                ),
+                array(
+                    select jsonb_populate_record(null::abac_attribute, a)
+                    from jsonb_array_elements(c.object) a
+                ),
+                array(
+                    select jsonb_populate_record(null::abac_attribute, a)
+                    from jsonb_array_elements(c.action) a
+                ),
+                array(select jsonb_array_elements_text(c.namespace_ids)::uuid)
+            ) as granted
+        from rows from (
+            jsonb_to_recordset($1) as (
+                id text,
+                namespace_ids jsonb,
+                subject jsonb,
+                object jsonb,
+                action jsonb
+            )
+        ) with ordinality as c(id, namespace_ids, subject, object, action, n)
+        order by c.n
+    "#;
+
+    diesel::dsl::sql_query(query)
+        .bind::<Jsonb, _>(serde_json::Value::Array(checks))
+        .load::<Decision>(conn)
+        .map(|rows| rows.into_iter().map(|d| (d.id, d.granted)).collect())
+}
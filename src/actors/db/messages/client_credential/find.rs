@@ -0,0 +1,36 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use models::{Account, ClientCredential};
+use schema::client_credential;
+
+/// Finds the credentials by the client identifier along with their account.
+#[derive(Debug)]
+pub struct FindWithAccount(pub Uuid);
+
+impl Message for FindWithAccount {
+    type Result = QueryResult<(ClientCredential, Account)>;
+}
+
+impl Handler<FindWithAccount> for DbExecutor {
+    type Result = QueryResult<(ClientCredential, Account)>;
+
+    fn handle(&mut self, msg: FindWithAccount, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        find_credential_with_account(conn, msg.0)
+    }
+}
+
+fn find_credential_with_account(
+    conn: &PgConnection,
+    id: Uuid,
+) -> QueryResult<(ClientCredential, Account)> {
+    use schema::account;
+
+    client_credential::table
+        .find(id)
+        .inner_join(account::table)
+        .get_result(conn)
+}
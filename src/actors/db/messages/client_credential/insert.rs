@@ -0,0 +1,46 @@
+use actix::prelude::*;
+use diesel::{self, prelude::*};
+
+use actors::DbExecutor;
+use models::{Account, ClientCredential, NewClientCredential};
+use schema::client_credential;
+
+/// Creates a service account, which isn't backed by any identity, along with its credentials.
+#[derive(Debug)]
+pub struct InsertWithAccount {
+    pub secret_hash: Vec<u8>,
+}
+
+impl Message for InsertWithAccount {
+    type Result = QueryResult<(ClientCredential, Account)>;
+}
+
+impl Handler<InsertWithAccount> for DbExecutor {
+    type Result = QueryResult<(ClientCredential, Account)>;
+
+    fn handle(&mut self, msg: InsertWithAccount, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        self.invalidate_on_success(insert_credential_with_account(conn, msg))
+    }
+}
+
+pub fn insert_credential_with_account(
+    conn: &PgConnection,
+    msg: InsertWithAccount,
+) -> QueryResult<(ClientCredential, Account)> {
+    use actors::db;
+
+    conn.transaction::<_, _, _>(|| {
+        let account = db::account::insert::insert_account(conn)?;
+
+        let changeset = NewClientCredential {
+            account_id: account.id,
+            secret_hash: msg.secret_hash,
+        };
+        let credential = diesel::insert_into(client_credential::table)
+            .values(changeset)
+            .get_result(conn)?;
+
+        Ok((credential, account))
+    })
+}
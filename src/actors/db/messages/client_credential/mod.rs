@@ -0,0 +1,2 @@
+pub mod find;
+pub mod insert;
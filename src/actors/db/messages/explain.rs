@@ -0,0 +1,146 @@
+use abac::AbacAttribute;
+use actix::prelude::*;
+use diesel::prelude::*;
+
+use actors::db::{
+    authz::{self, Authz},
+    tree::{self, CollectionKind, Link},
+};
+use actors::DbExecutor;
+use models::{abac_policy::Effect, AbacPolicy};
+use rpc::DirectionKind;
+
+const CANDIDATES_LIMIT: usize = 5;
+
+#[derive(Debug)]
+pub struct Explain {
+    pub authz: Authz,
+}
+
+#[derive(Debug)]
+pub struct Explanation {
+    pub granted: bool,
+    pub subject: Expansion,
+    pub object: Expansion,
+    pub action: Expansion,
+    pub policies: Vec<AbacPolicy>,
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Debug)]
+pub struct Expansion {
+    pub attributes: Vec<AbacAttribute>,
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug)]
+pub struct Candidate {
+    pub policy: AbacPolicy,
+    pub missing_subject: Vec<AbacAttribute>,
+    pub missing_object: Vec<AbacAttribute>,
+    pub missing_action: Vec<AbacAttribute>,
+}
+
+impl Candidate {
+    fn missing_count(&self) -> usize {
+        self.missing_subject.len() + self.missing_object.len() + self.missing_action.len()
+    }
+}
+
+impl Message for Explain {
+    type Result = QueryResult<Explanation>;
+}
+
+impl Handler<Explain> for DbExecutor {
+    type Result = QueryResult<Explanation>;
+
+    fn handle(&mut self, msg: Explain, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().expect("Failed to get a connection from pool");
+        call(conn, &msg)
+    }
+}
+
+fn call(conn: &PgConnection, msg: &Explain) -> QueryResult<Explanation> {
+    use diesel::dsl::any;
+    use functions::abac_is_active;
+    use schema::abac_policy;
+
+    let granted = authz::call(conn, &msg.authz)?;
+
+    let subject = expand(conn, CollectionKind::AbacSubject, &msg.authz.subject)?;
+    let object = expand(conn, CollectionKind::AbacObject, &msg.authz.object)?;
+    let action = expand(conn, CollectionKind::AbacAction, &msg.authz.action)?;
+
+    let all_policies = abac_policy::table
+        .filter(abac_policy::namespace_id.eq(any(&msg.authz.namespace_ids)))
+        .filter(abac_is_active(
+            abac_policy::not_before,
+            abac_policy::expires_at,
+        ))
+        .order(abac_policy::created_at.asc())
+        .load::<AbacPolicy>(conn)?;
+
+    let mut policies = Vec::new();
+    let mut candidates = Vec::new();
+
+    for policy in all_policies {
+        let missing_subject = missing(&policy.subject, &subject.attributes);
+        let missing_object = missing(&policy.object, &object.attributes);
+        let missing_action = missing(&policy.action, &action.attributes);
+
+        let candidate = Candidate {
+            policy,
+            missing_subject,
+            missing_object,
+            missing_action,
+        };
+
+        // Only allowing policies are worth suggesting as the closest ones for a denial.
+        if candidate.missing_count() == 0 {
+            policies.push(candidate.policy);
+        } else if candidate.policy.effect == Effect::Allow {
+            candidates.push(candidate);
+        }
+    }
+
+    if granted {
+        candidates.clear();
+    } else {
+        candidates.sort_by_key(|c| c.missing_count());
+        candidates.truncate(CANDIDATES_LIMIT);
+    }
+
+    Ok(Explanation {
+        granted,
+        subject,
+        object,
+        action,
+        policies,
+        candidates,
+    })
+}
+
+fn expand(
+    conn: &PgConnection,
+    kind: CollectionKind,
+    attrs: &[AbacAttribute],
+) -> QueryResult<Expansion> {
+    let links = tree::closure(conn, kind, DirectionKind::Outbound, attrs)?;
+
+    let mut attributes = attrs.to_vec();
+    for link in &links {
+        if !attributes.contains(&link.outbound) {
+            attributes.push(link.outbound.clone());
+        }
+    }
+
+    Ok(Expansion { attributes, links })
+}
+
+fn missing(required: &[AbacAttribute], available: &[AbacAttribute]) -> Vec<AbacAttribute> {
+    required
+        .iter()
+        .filter(|attr| !available.contains(attr))
+        .cloned()
+        .collect()
+}
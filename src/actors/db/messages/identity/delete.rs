@@ -19,10 +19,11 @@ impl Handler<Delete> for DbExecutor {
 
     fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        match msg {
+        let res = match msg {
             Delete::Identity(ref pk) => delete_identity(conn, pk),
             Delete::IdentityWithAccount(ref pk) => delete_identity_with_account(conn, pk),
-        }
+        };
+        self.invalidate_on_success(res)
     }
 }
 
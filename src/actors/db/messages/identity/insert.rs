@@ -3,7 +3,7 @@ use actix::prelude::*;
 use diesel::{self, prelude::*};
 
 use actors::DbExecutor;
-use models::{identity::PrimaryKey, Account, Identity, NewIdentity, NewRefreshToken, RefreshToken};
+use models::{identity::PrimaryKey, Account, Identity, NewIdentity};
 
 #[derive(Debug)]
 pub struct Insert(pub NewIdentity);
@@ -17,7 +17,7 @@ impl Handler<Insert> for DbExecutor {
 
     fn handle(&mut self, msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        insert_identity(conn, &msg.0)
+        self.invalidate_on_success(insert_identity(conn, &msg.0))
     }
 }
 
@@ -25,15 +25,15 @@ impl Handler<Insert> for DbExecutor {
 pub struct InsertWithAccount(pub PrimaryKey);
 
 impl Message for InsertWithAccount {
-    type Result = QueryResult<(Identity, Account, RefreshToken)>;
+    type Result = QueryResult<(Identity, Account)>;
 }
 
 impl Handler<InsertWithAccount> for DbExecutor {
-    type Result = QueryResult<(Identity, Account, RefreshToken)>;
+    type Result = QueryResult<(Identity, Account)>;
 
     fn handle(&mut self, msg: InsertWithAccount, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        insert_identity_with_account(conn, msg.0)
+        self.invalidate_on_success(insert_identity_with_account(conn, msg.0))
     }
 }
 
@@ -54,16 +54,12 @@ fn insert_identity(conn: &PgConnection, changeset: &NewIdentity) -> QueryResult<
 pub fn insert_identity_with_account(
     conn: &PgConnection,
     pk: PrimaryKey,
-) -> QueryResult<(Identity, Account, RefreshToken)> {
+) -> QueryResult<(Identity, Account)> {
     use actors::db;
 
     conn.transaction::<_, _, _>(|| {
         let account = db::account::insert::insert_account(conn)?;
 
-        // TODO: do not unwrap
-        let changeset = NewRefreshToken::try_new(account.id).unwrap();
-        let token = db::refresh_token::insert::insert_token(conn, changeset)?;
-
         let changeset = NewIdentity {
             provider: pk.provider,
             label: pk.label,
@@ -72,7 +68,7 @@ pub fn insert_identity_with_account(
         };
         let identity = insert_identity(conn, &changeset)?;
 
-        Ok((identity, account, token))
+        Ok((identity, account))
     })
 }
 
@@ -4,9 +4,17 @@ pub mod abac_object_target;
 pub mod abac_policy;
 pub mod abac_subject_attr;
 pub mod account;
+pub mod api_key;
 pub mod authz;
+pub mod client_credential;
+pub mod explain;
 pub mod identity;
 pub mod namespace;
 pub mod object_list;
+pub mod permissions;
 pub mod refresh_token;
+pub mod revoked_access_token;
+pub mod simulate;
+pub mod subjects;
+pub mod sweep;
 pub mod tree;
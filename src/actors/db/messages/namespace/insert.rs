@@ -17,7 +17,7 @@ impl Handler<Insert> for DbExecutor {
 
     fn handle(&mut self, msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        insert_namespace(conn, msg.0)
+        self.invalidate_on_success(insert_namespace(conn, msg.0))
     }
 }
 
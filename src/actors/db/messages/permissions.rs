@@ -0,0 +1,84 @@
+use abac::{sql_types, AbacAttribute};
+use actix::prelude::*;
+use diesel::{
+    self,
+    prelude::*,
+    sql_types::{Array, BigInt, Uuid as SqlUuid},
+};
+use uuid::Uuid;
+
+use actors::DbExecutor;
+
+#[derive(Debug)]
+pub struct Permissions {
+    pub subject: Vec<AbacAttribute>,
+    pub namespace_ids: Vec<Uuid>,
+    pub limit: u16,
+    pub offset: u16,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct Permission {
+    #[sql_type = "SqlUuid"]
+    pub namespace_id: Uuid,
+    #[sql_type = "Array<sql_types::AbacAttribute>"]
+    pub object: Vec<AbacAttribute>,
+    #[sql_type = "Array<sql_types::AbacAttribute>"]
+    pub action: Vec<AbacAttribute>,
+}
+
+impl Message for Permissions {
+    type Result = QueryResult<Vec<Permission>>;
+}
+
+impl Handler<Permissions> for DbExecutor {
+    type Result = QueryResult<Vec<Permission>>;
+
+    fn handle(&mut self, msg: Permissions, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        call(conn, &msg)
+    }
+}
+
+fn call(conn: &PgConnection, msg: &Permissions) -> QueryResult<Vec<Permission>> {
+    // Allowing policies are skipped when a denying one matches every object and action
+    // they grant, since `abac_authorize` would never let them through.
+    let query = r#"
+        with recursive
+        s(attr) as (
+            select unnest($1)
+            union
+            select e.outbound
+            from abac_subject e
+            inner join s on e.inbound = s.attr
+            where abac_is_active(e.not_before, e.expires_at)
+        ),
+        p as (
+            select *
+            from abac_policy
+            where namespace_id = any($2)
+              and abac_is_active(not_before, expires_at)
+              and subject <@ array(select attr from s)
+        )
+        select a.namespace_id, a.object, a.action
+        from p a
+        where a.effect = 'allow'
+          and not exists(
+              select 1
+              from p d
+              where d.effect = 'deny'
+                and d.object <@ a.object
+                and d.action <@ a.action
+          )
+        order by a.created_at, a.namespace_id
+        limit $3
+        offset $4
+    "#;
+
+    diesel::dsl::sql_query(query)
+        .bind::<Array<sql_types::AbacAttribute>, _>(&msg.subject)
+        .bind::<Array<SqlUuid>, _>(&msg.namespace_ids)
+        .bind::<BigInt, _>(i64::from(msg.limit))
+        .bind::<BigInt, _>(i64::from(msg.offset))
+        .load(conn)
+}
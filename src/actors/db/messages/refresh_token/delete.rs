@@ -0,0 +1,28 @@
+use actix::prelude::*;
+use diesel::{self, prelude::*};
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use models::RefreshToken;
+use schema::refresh_token;
+
+#[derive(Debug)]
+pub struct Delete(pub Uuid);
+
+impl Message for Delete {
+    type Result = QueryResult<RefreshToken>;
+}
+
+impl Handler<Delete> for DbExecutor {
+    type Result = QueryResult<RefreshToken>;
+
+    fn handle(&mut self, msg: Delete, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        delete_token(conn, msg.0)
+    }
+}
+
+fn delete_token(conn: &PgConnection, id: Uuid) -> QueryResult<RefreshToken> {
+    let target = refresh_token::table.find(id);
+    diesel::delete(target).get_result(conn)
+}
@@ -2,12 +2,36 @@ use actix::prelude::*;
 use diesel::prelude::*;
 use uuid::Uuid;
 
+use actors::db::refresh_token::expired_before;
 use actors::DbExecutor;
 use models::{Account, RefreshToken};
 use schema::refresh_token;
 
 #[derive(Debug)]
-pub struct FindWithAccount(pub Uuid);
+pub struct Find(pub Uuid);
+
+impl Message for Find {
+    type Result = QueryResult<RefreshToken>;
+}
+
+impl Handler<Find> for DbExecutor {
+    type Result = QueryResult<RefreshToken>;
+
+    fn handle(&mut self, msg: Find, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        find_token(conn, msg.0)
+    }
+}
+
+/// Finds the session `id`, provided it belongs to the account and isn't expired.
+///
+/// Without `id` the earliest session of the account is found, the one refresh tokens
+/// issued before sessions were introduced belong to.
+#[derive(Debug)]
+pub struct FindWithAccount {
+    pub id: Option<Uuid>,
+    pub account_id: Uuid,
+}
 
 impl Message for FindWithAccount {
     type Result = QueryResult<(RefreshToken, Account)>;
@@ -18,18 +42,34 @@ impl Handler<FindWithAccount> for DbExecutor {
 
     fn handle(&mut self, msg: FindWithAccount, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
-        find_token_with_account(conn, msg.0)
+        find_token_with_account(conn, msg.id, msg.account_id)
     }
 }
 
+fn find_token(conn: &PgConnection, id: Uuid) -> QueryResult<RefreshToken> {
+    refresh_token::table.find(id).get_result(conn)
+}
+
 fn find_token_with_account(
     conn: &PgConnection,
+    id: Option<Uuid>,
     account_id: Uuid,
 ) -> QueryResult<(RefreshToken, Account)> {
+    use functions::coalesce;
     use schema::account;
 
-    refresh_token::table
-        .find(account_id)
+    let last_used_at = coalesce(refresh_token::last_used_at, refresh_token::created_at);
+
+    let mut query = refresh_token::table
+        .filter(refresh_token::account_id.eq(account_id))
+        .filter(last_used_at.gt(expired_before()))
         .inner_join(account::table)
-        .get_result(conn)
+        .into_boxed();
+
+    query = match id {
+        Some(id) => query.filter(refresh_token::id.eq(id)),
+        None => query.order((refresh_token::created_at.asc(), refresh_token::id.asc())),
+    };
+
+    query.first(conn)
 }
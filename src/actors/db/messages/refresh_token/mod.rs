@@ -1,3 +1,14 @@
+use chrono::{DateTime, Duration, Utc};
+
+pub mod delete;
 pub mod find;
 pub mod insert;
+pub mod rotate;
+pub mod select;
 pub mod update;
+
+/// Sessions not used since then are expired.
+pub fn expired_before() -> DateTime<Utc> {
+    let settings = get_settings!();
+    Utc::now() - Duration::seconds(i64::from(settings.tokens.session_expires_in))
+}
@@ -0,0 +1,46 @@
+use actix::prelude::*;
+use diesel::{self, pg::Pg, prelude::*, sql_types::Bool};
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use models::RefreshToken;
+use schema::refresh_token;
+
+/// Replaces `jti` of the session with a new one, provided it is still current.
+#[derive(Debug)]
+pub struct Rotate {
+    pub id: Uuid,
+    pub jti: Option<Uuid>,
+}
+
+impl Message for Rotate {
+    type Result = QueryResult<Option<RefreshToken>>;
+}
+
+impl Handler<Rotate> for DbExecutor {
+    type Result = QueryResult<Option<RefreshToken>>;
+
+    fn handle(&mut self, msg: Rotate, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        rotate_token(conn, &msg)
+    }
+}
+
+fn rotate_token(conn: &PgConnection, msg: &Rotate) -> QueryResult<Option<RefreshToken>> {
+    // Matching on the current `jti` makes concurrent rotations of the same token
+    // succeed only once, so that all the others are treated as a reuse.
+    // The first generation of tokens doesn't carry it and is current until the first rotation.
+    let current: Box<BoxableExpression<refresh_token::table, Pg, SqlType = Bool>> = match msg.jti {
+        Some(jti) => Box::new(refresh_token::jti.eq(jti)),
+        None => Box::new(refresh_token::last_used_at.is_null()),
+    };
+    let target = refresh_token::table.find(msg.id).filter(current);
+
+    diesel::update(target)
+        .set((
+            refresh_token::jti.eq(Uuid::new_v4()),
+            refresh_token::last_used_at.eq(diesel::dsl::now),
+        ))
+        .get_result(conn)
+        .optional()
+}
@@ -0,0 +1,43 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use actors::db::refresh_token::expired_before;
+use actors::DbExecutor;
+use models::RefreshToken;
+use schema::refresh_token;
+
+#[derive(Debug)]
+pub struct Select {
+    pub account_id: Uuid,
+    pub limit: u16,
+    pub offset: u16,
+}
+
+impl Message for Select {
+    type Result = QueryResult<Vec<RefreshToken>>;
+}
+
+impl Handler<Select> for DbExecutor {
+    type Result = QueryResult<Vec<RefreshToken>>;
+
+    fn handle(&mut self, msg: Select, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        select_tokens(conn, &msg)
+    }
+}
+
+fn select_tokens(conn: &PgConnection, msg: &Select) -> QueryResult<Vec<RefreshToken>> {
+    use functions::coalesce;
+
+    let last_used_at = coalesce(refresh_token::last_used_at, refresh_token::created_at);
+
+    let query = refresh_token::table
+        .filter(refresh_token::account_id.eq(msg.account_id))
+        .filter(last_used_at.gt(expired_before()))
+        .order((refresh_token::created_at.asc(), refresh_token::id.asc()))
+        .limit(i64::from(msg.limit))
+        .offset(i64::from(msg.offset));
+
+    query.load(conn)
+}
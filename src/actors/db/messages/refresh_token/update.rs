@@ -1,11 +1,12 @@
 use actix::prelude::*;
-use diesel::prelude::*;
+use diesel::{self, prelude::*};
+use uuid::Uuid;
 
 use actors::DbExecutor;
-use models::{NewRefreshToken, RefreshToken};
+use models::{RefreshToken, RenewedRefreshToken};
 
 #[derive(Debug)]
-pub struct Update(pub NewRefreshToken);
+pub struct Update(pub RenewedRefreshToken);
 
 impl Message for Update {
     type Result = QueryResult<RefreshToken>;
@@ -20,7 +21,44 @@ impl Handler<Update> for DbExecutor {
     }
 }
 
-fn update_token(conn: &PgConnection, changeset: NewRefreshToken) -> QueryResult<RefreshToken> {
+fn update_token(conn: &PgConnection, changeset: RenewedRefreshToken) -> QueryResult<RefreshToken> {
     let token = changeset.save_changes(conn)?;
     Ok(token)
 }
+
+/// Prepends a new key to every session of the account, keeping `keep` previous keys.
+#[derive(Debug)]
+pub struct RotateKeys {
+    pub account_id: Uuid,
+    pub keep: u16,
+}
+
+impl Message for RotateKeys {
+    type Result = QueryResult<Vec<RefreshToken>>;
+}
+
+impl Handler<RotateKeys> for DbExecutor {
+    type Result = QueryResult<Vec<RefreshToken>>;
+
+    fn handle(&mut self, msg: RotateKeys, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        rotate_keys(conn, &msg)
+    }
+}
+
+fn rotate_keys(conn: &PgConnection, msg: &RotateKeys) -> QueryResult<Vec<RefreshToken>> {
+    use diesel::sql_types::{Integer, Uuid as SqlUuid};
+
+    // Keys are generated by pgcrypto so that each session gets its own key in a single query.
+    let query = r#"
+        update refresh_token
+        set keys = array_prepend(gen_random_bytes(64), keys[1:$2])
+        where account_id = $1
+        returning *
+    "#;
+
+    diesel::sql_query(query)
+        .bind::<SqlUuid, _>(msg.account_id)
+        .bind::<Integer, _>(i32::from(msg.keep))
+        .load(conn)
+}
@@ -0,0 +1,48 @@
+use actix::prelude::*;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{self, dsl::exists, prelude::*};
+use uuid::Uuid;
+
+use actors::DbExecutor;
+use schema::{account, revoked_access_token};
+
+/// Checks whether the token has been revoked, either by itself or along with
+/// all of the tokens of the account issued before some moment.
+#[derive(Debug)]
+pub struct IsRevoked {
+    pub jti: Option<Uuid>,
+    pub account_id: Uuid,
+    pub issued_at: NaiveDateTime,
+}
+
+impl Message for IsRevoked {
+    type Result = QueryResult<bool>;
+}
+
+impl Handler<IsRevoked> for DbExecutor {
+    type Result = QueryResult<bool>;
+
+    fn handle(&mut self, msg: IsRevoked, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        is_revoked(conn, &msg)
+    }
+}
+
+fn is_revoked(conn: &PgConnection, msg: &IsRevoked) -> QueryResult<bool> {
+    let issued_at = DateTime::<Utc>::from_utc(msg.issued_at, Utc);
+    let query = account::table
+        .find(msg.account_id)
+        .filter(account::tokens_revoked_before.gt(issued_at));
+
+    if diesel::select(exists(query)).get_result(conn)? {
+        return Ok(true);
+    }
+
+    match msg.jti {
+        Some(jti) => {
+            let query = revoked_access_token::table.find((jti, msg.account_id));
+            diesel::select(exists(query)).get_result(conn)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,37 @@
+use actix::prelude::*;
+use diesel::{self, prelude::*};
+
+use actors::DbExecutor;
+use models::{NewRevokedAccessToken, RevokedAccessToken};
+use schema::revoked_access_token;
+
+/// Revokes the token, revoking it again only extends its expiration.
+#[derive(Debug)]
+pub struct Insert(pub NewRevokedAccessToken);
+
+impl Message for Insert {
+    type Result = QueryResult<RevokedAccessToken>;
+}
+
+impl Handler<Insert> for DbExecutor {
+    type Result = QueryResult<RevokedAccessToken>;
+
+    fn handle(&mut self, msg: Insert, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        insert_token(conn, &msg.0)
+    }
+}
+
+fn insert_token(
+    conn: &PgConnection,
+    token: &NewRevokedAccessToken,
+) -> QueryResult<RevokedAccessToken> {
+    use diesel::pg::upsert::excluded;
+
+    diesel::insert_into(revoked_access_token::table)
+        .values(token)
+        .on_conflict((revoked_access_token::jti, revoked_access_token::account_id))
+        .do_update()
+        .set(revoked_access_token::expires_at.eq(excluded(revoked_access_token::expires_at)))
+        .get_result(conn)
+}
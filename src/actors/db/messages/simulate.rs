@@ -0,0 +1,117 @@
+use actix::prelude::*;
+use diesel::prelude::*;
+
+use actors::db::{
+    abac_action_attr, abac_object_attr, abac_policy, abac_subject_attr,
+    authz::{self, AuthzBatch},
+    Error,
+};
+use actors::DbExecutor;
+
+#[derive(Debug)]
+pub enum Change {
+    InsertPolicy(abac_policy::insert::Insert),
+    DeletePolicy(abac_policy::delete::Delete),
+    InsertSubject(abac_subject_attr::insert::Insert),
+    DeleteSubject(abac_subject_attr::delete::Delete),
+    InsertObject(abac_object_attr::insert::Insert),
+    DeleteObject(abac_object_attr::delete::Delete),
+    InsertAction(abac_action_attr::insert::Insert),
+    DeleteAction(abac_action_attr::delete::Delete),
+}
+
+#[derive(Debug)]
+pub struct Simulate {
+    pub changes: Vec<Change>,
+    pub checks: AuthzBatch,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub id: String,
+    pub before: bool,
+    pub after: bool,
+}
+
+impl Message for Simulate {
+    type Result = Result<Vec<Outcome>, Error>;
+}
+
+impl Handler<Simulate> for DbExecutor {
+    type Result = Result<Vec<Outcome>, Error>;
+
+    fn handle(&mut self, msg: Simulate, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        call(conn, msg)
+    }
+}
+
+fn call(conn: &PgConnection, msg: Simulate) -> Result<Vec<Outcome>, Error> {
+    use diesel::result::Error::RollbackTransaction;
+
+    let mut outcomes = Vec::new();
+
+    // The transaction is failed on purpose once the outcomes are known,
+    // so nothing the simulation has changed is persisted.
+    conn.transaction::<(), Error, _>(|| {
+        outcomes = simulate(conn, msg)?;
+        Err(Error::from(RollbackTransaction))
+    }).or_else(|e| match e {
+        Error::Db(RollbackTransaction) => Ok(()),
+        e => Err(e),
+    })?;
+
+    Ok(outcomes)
+}
+
+fn simulate(conn: &PgConnection, msg: Simulate) -> Result<Vec<Outcome>, Error> {
+    let before = authz::call_batch(conn, &msg.checks)?;
+
+    for change in msg.changes {
+        apply(conn, change)?;
+    }
+
+    let after = authz::call_batch(conn, &msg.checks)?;
+
+    // Both batches are ordered as the checks are, so decisions are paired by position.
+    let outcomes = before
+        .into_iter()
+        .zip(after)
+        .map(|((id, before), (_, after))| Outcome { id, before, after })
+        .collect();
+
+    Ok(outcomes)
+}
+
+fn apply(conn: &PgConnection, change: Change) -> Result<(), Error> {
+    use self::Change::*;
+
+    match change {
+        InsertPolicy(msg) => {
+            abac_policy::insert::call(conn, msg)?;
+        }
+        DeletePolicy(msg) => {
+            abac_policy::delete::call(conn, msg)?;
+        }
+        InsertSubject(msg) => {
+            abac_subject_attr::insert::call(conn, msg)?;
+        }
+        DeleteSubject(msg) => {
+            abac_subject_attr::delete::call(conn, msg)?;
+        }
+        InsertObject(msg) => {
+            abac_object_attr::insert::call(conn, msg)?;
+        }
+        DeleteObject(msg) => {
+            abac_object_attr::delete::call(conn, msg)?;
+        }
+        InsertAction(msg) => {
+            abac_action_attr::insert::call(conn, msg)?;
+        }
+        DeleteAction(msg) => {
+            abac_action_attr::delete::call(conn, msg)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use abac::{sql_types, AbacAttribute};
+use actix::prelude::*;
+use diesel::{
+    self,
+    prelude::*,
+    sql_types::{BigInt, Uuid as SqlUuid},
+};
+use uuid::Uuid;
+
+use actors::DbExecutor;
+
+#[derive(Debug)]
+pub struct Subjects {
+    pub namespace_id: Uuid,
+    pub object: AbacAttribute,
+    pub action: AbacAttribute,
+    pub limit: u16,
+    pub offset: u16,
+}
+
+#[derive(Debug, QueryableByName)]
+struct Subject {
+    #[sql_type = "sql_types::AbacAttribute"]
+    attribute: AbacAttribute,
+}
+
+impl Message for Subjects {
+    type Result = QueryResult<Vec<AbacAttribute>>;
+}
+
+impl Handler<Subjects> for DbExecutor {
+    type Result = QueryResult<Vec<AbacAttribute>>;
+
+    fn handle(&mut self, msg: Subjects, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().unwrap();
+        call(conn, &msg)
+    }
+}
+
+fn call(conn: &PgConnection, msg: &Subjects) -> QueryResult<Vec<AbacAttribute>> {
+    use settings;
+
+    // Account attributes are found walking subject links backwards from the policies
+    // matching the object and action. Each of them is then expanded forwards to check
+    // it has every subject attribute of some allowing policy and of no denying one.
+    let query = r#"
+        with recursive
+        o(attr) as (
+            select $1
+            union
+            select e.outbound
+            from abac_object e
+            inner join o on e.inbound = o.attr
+            where abac_is_active(e.not_before, e.expires_at)
+        ),
+        a(attr) as (
+            select $2
+            union
+            select e.outbound
+            from abac_action e
+            inner join a on e.inbound = a.attr
+            where abac_is_active(e.not_before, e.expires_at)
+        ),
+        p as (
+            select *
+            from abac_policy
+            where namespace_id = $3
+              and abac_is_active(not_before, expires_at)
+              and object <@ array(select attr from o)
+              and action <@ array(select attr from a)
+        ),
+        r(attr) as (
+            select unnest(subject) from p where effect = 'allow'
+            union
+            select e.inbound
+            from abac_subject e
+            inner join r on e.outbound = r.attr
+            where abac_is_active(e.not_before, e.expires_at)
+        ),
+        s(root, attr) as (
+            select attr, attr
+            from r
+            where (attr).namespace_id = $4
+              and (attr).key = 'uri'
+              and (attr).value like 'account/%'
+            union
+            select s.root, e.outbound
+            from abac_subject e
+            inner join s on e.inbound = s.attr
+            where abac_is_active(e.not_before, e.expires_at)
+        ),
+        g as (
+            select root, array_agg(attr) as attrs
+            from s
+            group by root
+        )
+        select root as attribute
+        from g
+        where exists(select 1 from p where effect = 'allow' and subject <@ g.attrs)
+          and not exists(select 1 from p where effect = 'deny' and subject <@ g.attrs)
+        order by (root).value
+        limit $5
+        offset $6
+    "#;
+
+    let subjects = diesel::dsl::sql_query(query)
+        .bind::<sql_types::AbacAttribute, _>(&msg.object)
+        .bind::<sql_types::AbacAttribute, _>(&msg.action)
+        .bind::<SqlUuid, _>(msg.namespace_id)
+        .bind::<SqlUuid, _>(settings::iam_namespace_id())
+        .bind::<BigInt, _>(i64::from(msg.limit))
+        .bind::<BigInt, _>(i64::from(msg.offset))
+        .load::<Subject>(conn)?;
+
+    Ok(subjects.into_iter().map(|s| s.attribute).collect())
+}
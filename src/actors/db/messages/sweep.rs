@@ -0,0 +1,51 @@
+use actix::prelude::*;
+use diesel::{self, dsl::now, prelude::*};
+
+use actors::db::refresh_token::expired_before;
+use actors::DbExecutor;
+
+/// Purges ABAC links and policies whose validity window is over, along with
+/// revoked access tokens which have expired anyway and sessions not used for too long.
+#[derive(Debug)]
+pub struct Sweep;
+
+impl Message for Sweep {
+    type Result = QueryResult<usize>;
+}
+
+impl Handler<Sweep> for DbExecutor {
+    type Result = QueryResult<usize>;
+
+    fn handle(&mut self, _msg: Sweep, _ctx: &mut Self::Context) -> Self::Result {
+        let conn = &self.0.get().expect("Failed to get a connection from pool");
+        call(conn)
+    }
+}
+
+fn call(conn: &PgConnection) -> QueryResult<usize> {
+    use functions::coalesce;
+    use schema::{
+        abac_action, abac_object, abac_policy, abac_subject, refresh_token, revoked_access_token,
+    };
+
+    conn.transaction::<_, _, _>(|| {
+        let subjects =
+            diesel::delete(abac_subject::table.filter(abac_subject::expires_at.le(now)))
+                .execute(conn)?;
+        let objects = diesel::delete(abac_object::table.filter(abac_object::expires_at.le(now)))
+            .execute(conn)?;
+        let actions = diesel::delete(abac_action::table.filter(abac_action::expires_at.le(now)))
+            .execute(conn)?;
+        let policies = diesel::delete(abac_policy::table.filter(abac_policy::expires_at.le(now)))
+            .execute(conn)?;
+        let tokens = diesel::delete(
+            revoked_access_token::table.filter(revoked_access_token::expires_at.le(now)),
+        ).execute(conn)?;
+        let last_used_at = coalesce(refresh_token::last_used_at, refresh_token::created_at);
+        let sessions =
+            diesel::delete(refresh_token::table.filter(last_used_at.le(expired_before())))
+                .execute(conn)?;
+
+        Ok(subjects + objects + actions + policies + tokens + sessions)
+    })
+}
@@ -1,34 +1,60 @@
-use abac::AbacAttribute;
+use abac::{sql_types, AbacAttribute};
 use actix::prelude::*;
-use diesel::prelude::*;
+use diesel::{
+    self,
+    prelude::*,
+    sql_types::{Array, Integer, Nullable},
+};
 use rpc::DirectionKind;
 
 use actors::DbExecutor;
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum CollectionKind {
     AbacSubject,
     AbacObject,
     AbacAction,
 }
 
+impl CollectionKind {
+    fn table_name(self) -> &'static str {
+        use self::CollectionKind::*;
+
+        match self {
+            AbacSubject => "abac_subject",
+            AbacObject => "abac_object",
+            AbacAction => "abac_action",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Select {
     pub direction: DirectionKind,
     pub attribute: AbacAttribute,
+    pub recursive: bool,
     pub limit: u16,
     pub offset: u16,
 }
 
+#[derive(Debug, QueryableByName)]
+pub struct Node {
+    #[sql_type = "sql_types::AbacAttribute"]
+    pub attribute: AbacAttribute,
+    /// Number of links between the start attribute and this one, known for recursive queries.
+    #[sql_type = "Nullable<Integer>"]
+    pub distance: Option<i32>,
+}
+
 #[derive(Debug)]
 pub struct Tree(pub CollectionKind, pub Select);
 
 impl Message for Tree {
-    type Result = QueryResult<Vec<AbacAttribute>>;
+    type Result = QueryResult<Vec<Node>>;
 }
 
 impl Handler<Tree> for DbExecutor {
-    type Result = QueryResult<Vec<AbacAttribute>>;
+    type Result = QueryResult<Vec<Node>>;
 
     fn handle(&mut self, msg: Tree, _ctx: &mut Self::Context) -> Self::Result {
         let conn = &self.0.get().unwrap();
@@ -38,15 +64,17 @@ impl Handler<Tree> for DbExecutor {
 
 macro_rules! tree_query {
     ($table:ident, $msg:expr) => {{
-        use abac::schema::$table;
+        use functions::abac_is_active;
+        use schema::$table;
 
         let msg = $msg;
 
         // Explicitly specifying non-default select clause is required to set desired
-        // select type: (AbacAttribute) instead of (AbacAttribute, AbacAttribute, Timestamptz).
+        // select type: (AbacAttribute) instead of the whole row.
         // A correct column is then specified based on direction kind.
         let mut query = $table::table
             .select($table::inbound)
+            .filter(abac_is_active($table::not_before, $table::expires_at))
             .order($table::created_at.asc())
             .limit(i64::from(msg.limit))
             .offset(i64::from(msg.offset))
@@ -69,21 +97,184 @@ macro_rules! tree_query {
     }};
 }
 
-fn call(conn: &PgConnection, msg: &Tree) -> QueryResult<Vec<AbacAttribute>> {
+fn call(conn: &PgConnection, msg: &Tree) -> QueryResult<Vec<Node>> {
     use self::CollectionKind::*;
 
-    match msg.0 {
+    if msg.1.recursive {
+        return transitive(conn, msg.0, &msg.1);
+    }
+
+    let attrs: Vec<AbacAttribute> = match msg.0 {
         AbacSubject => {
             let query = tree_query!(abac_subject, &msg.1);
-            query.load(conn)
+            query.load(conn)?
         }
         AbacObject => {
             let query = tree_query!(abac_object, &msg.1);
-            query.load(conn)
+            query.load(conn)?
         }
         AbacAction => {
             let query = tree_query!(abac_action, &msg.1);
-            query.load(conn)
+            query.load(conn)?
         }
+    };
+
+    let nodes = attrs
+        .into_iter()
+        .map(|attribute| Node {
+            attribute,
+            distance: None,
+        })
+        .collect();
+
+    Ok(nodes)
+}
+
+fn transitive(conn: &PgConnection, kind: CollectionKind, msg: &Select) -> QueryResult<Vec<Node>> {
+    use diesel::sql_types::BigInt;
+
+    // The same attribute may be reachable by several paths, the shortest one is its distance.
+    let query = format!(
+        r#"
+        {walk}
+        select attr as attribute, min(depth) as distance
+        from (
+            select {to} as attr, depth
+            from t
+        ) n
+        group by attr
+        order by distance, attr
+        limit $2
+        offset $3
+        "#,
+        walk = walk(kind, msg.direction),
+        to = direction_columns(msg.direction).1,
+    );
+
+    diesel::dsl::sql_query(query)
+        .bind::<Array<sql_types::AbacAttribute>, _>(vec![msg.attribute.clone()])
+        .bind::<BigInt, _>(i64::from(msg.limit))
+        .bind::<BigInt, _>(i64::from(msg.offset))
+        .load(conn)
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct Link {
+    #[sql_type = "sql_types::AbacAttribute"]
+    pub inbound: AbacAttribute,
+    #[sql_type = "sql_types::AbacAttribute"]
+    pub outbound: AbacAttribute,
+    #[sql_type = "Integer"]
+    pub depth: i32,
+}
+
+/// Walks the links of the collection starting from `attrs` and returns every link reachable
+/// in the given direction along with its distance (starting links have depth 1).
+pub fn closure(
+    conn: &PgConnection,
+    kind: CollectionKind,
+    direction: DirectionKind,
+    attrs: &[AbacAttribute],
+) -> QueryResult<Vec<Link>> {
+    let query = format!(
+        r#"
+        {walk}
+        select inbound, outbound, depth
+        from (
+            select distinct on (inbound, outbound) inbound, outbound, depth
+            from t
+            order by inbound, outbound, depth
+        ) l
+        order by depth
+        "#,
+        walk = walk(kind, direction),
+    );
+
+    diesel::dsl::sql_query(query)
+        .bind::<Array<sql_types::AbacAttribute>, _>(attrs)
+        .load(conn)
+}
+
+#[derive(Debug, QueryableByName)]
+struct Path {
+    #[sql_type = "Array<sql_types::AbacAttribute>"]
+    path: Vec<AbacAttribute>,
+}
+
+/// Finds the shortest chain of links leading from `from` to `to`, whatever their validity
+/// windows are. The chain includes both of the attributes.
+pub fn find_path(
+    conn: &PgConnection,
+    kind: CollectionKind,
+    from: &AbacAttribute,
+    to: &AbacAttribute,
+) -> QueryResult<Option<Vec<AbacAttribute>>> {
+    if from == to {
+        return Ok(Some(vec![from.clone()]));
     }
+
+    // Links are walked breadth first regardless of their validity windows, so the first path
+    // found is the shortest one and the walk stops there instead of exploring the whole graph.
+    // The path is tracked to stop walking in case of cyclic links stored before they were
+    // rejected.
+    let query = format!(
+        r#"
+        with recursive t(outbound, path) as (
+            select e.outbound, array[e.inbound, e.outbound]
+            from {table} e
+            where e.inbound = $1
+            union all
+            select e.outbound, t.path || e.outbound
+            from {table} e
+            inner join t on e.inbound = t.outbound
+            where not e.outbound = any(t.path)
+              and not t.outbound = $2
+        )
+        select path
+        from t
+        where outbound = $2
+        limit 1
+        "#,
+        table = kind.table_name(),
+    );
+
+    diesel::dsl::sql_query(query)
+        .bind::<sql_types::AbacAttribute, _>(from)
+        .bind::<sql_types::AbacAttribute, _>(to)
+        .get_result::<Path>(conn)
+        .optional()
+        .map(|res| res.map(|p| p.path))
+}
+
+/// Columns of a link to walk from and to in the given direction.
+fn direction_columns(direction: DirectionKind) -> (&'static str, &'static str) {
+    match direction {
+        DirectionKind::Inbound => ("outbound", "inbound"),
+        DirectionKind::Outbound => ("inbound", "outbound"),
+    }
+}
+
+/// Recursive CTE `t(inbound, outbound, depth)` of active links reachable from the attributes
+/// bound as `$1`. Links can't form a cycle, so the walk ends once no new link is reachable.
+fn walk(kind: CollectionKind, direction: DirectionKind) -> String {
+    let (from, to) = direction_columns(direction);
+
+    format!(
+        r#"
+        with recursive t(inbound, outbound, depth) as (
+            select e.inbound, e.outbound, 1
+            from {table} e
+            where e.{from} = any($1)
+              and abac_is_active(e.not_before, e.expires_at)
+            union
+            select e.inbound, e.outbound, t.depth + 1
+            from {table} e
+            inner join t on e.{from} = t.{to}
+            where abac_is_active(e.not_before, e.expires_at)
+        )
+        "#,
+        table = kind.table_name(),
+        from = from,
+        to = to,
+    )
 }
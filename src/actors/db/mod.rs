@@ -1,12 +1,28 @@
 use actix::prelude::*;
 
+use std::sync::Arc;
+
+pub use actors::db::cache::DecisionCache;
+pub use actors::db::error::Error;
 pub use actors::db::messages::*;
 use DbPool;
 
+pub mod cache;
+pub mod error;
 mod messages;
 
 #[allow(missing_debug_implementations)]
-pub struct DbExecutor(pub DbPool);
+pub struct DbExecutor(pub DbPool, pub Arc<DecisionCache>);
+
+impl DbExecutor {
+    /// Drops cached authorization decisions once a change of ABAC links or policies succeeded.
+    fn invalidate_on_success<T, E>(&self, res: Result<T, E>) -> Result<T, E> {
+        if res.is_ok() {
+            self.1.invalidate();
+        }
+        res
+    }
+}
 
 impl Actor for DbExecutor {
     type Context = SyncContext<Self>;
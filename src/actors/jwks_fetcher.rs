@@ -0,0 +1,42 @@
+use actix::prelude::*;
+
+use std::time::Duration;
+
+use authn::jwks;
+
+/// Periodically fetches keys of the providers configured with `jwks_url`.
+#[allow(missing_debug_implementations)]
+pub struct JwksFetcher {
+    interval: Duration,
+}
+
+impl JwksFetcher {
+    pub fn new(interval: Duration) -> Self {
+        JwksFetcher { interval }
+    }
+
+    fn fetch(&mut self, ctx: &mut Context<Self>) {
+        let providers = {
+            let settings = get_settings!();
+            settings
+                .providers
+                .iter()
+                .filter_map(|(auth_key, p)| p.jwks_url.clone().map(|url| (auth_key.clone(), url)))
+                .collect::<Vec<_>>()
+        };
+
+        for (auth_key, url) in providers {
+            let fut = jwks::refresh(auth_key, &url);
+            ctx.spawn(fut.into_actor(self));
+        }
+    }
+}
+
+impl Actor for JwksFetcher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.fetch(ctx);
+        ctx.run_interval(self.interval, |act, ctx| act.fetch(ctx));
+    }
+}
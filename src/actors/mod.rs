@@ -1,3 +1,7 @@
 pub mod db;
+pub mod jwks_fetcher;
+pub mod sweeper;
 
 pub use actors::db::DbExecutor;
+pub use actors::jwks_fetcher::JwksFetcher;
+pub use actors::sweeper::Sweeper;
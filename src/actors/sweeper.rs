@@ -0,0 +1,42 @@
+use actix::prelude::*;
+use futures::Future;
+
+use std::time::Duration;
+
+use actors::{db::sweep::Sweep, DbExecutor};
+
+/// Periodically purges expired ABAC links, policies and revoked access tokens
+/// through `DbExecutor`.
+#[allow(missing_debug_implementations)]
+pub struct Sweeper {
+    db: Addr<DbExecutor>,
+    interval: Duration,
+}
+
+impl Sweeper {
+    pub fn new(db: Addr<DbExecutor>, interval: Duration) -> Self {
+        Sweeper { db, interval }
+    }
+
+    fn sweep(&mut self, ctx: &mut Context<Self>) {
+        let fut = self.db.send(Sweep).then(|res| -> Result<(), ()> {
+            match res {
+                Ok(Ok(0)) => {}
+                Ok(Ok(count)) => info!("Swept {} expired rows", count),
+                Ok(Err(e)) => error!("Failed to sweep expired rows: {}", e),
+                Err(e) => error!("Failed to sweep expired rows: {}", e),
+            }
+            Ok(())
+        });
+
+        ctx.spawn(fut.into_actor(self));
+    }
+}
+
+impl Actor for Sweeper {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(self.interval, |act, ctx| act.sweep(ctx));
+    }
+}
@@ -18,6 +18,12 @@ pub enum Error {
     #[fail(display = "Invalid request")]
     InvalidRequest,
 
+    #[fail(display = "Invalid target")]
+    InvalidTarget,
+
+    #[fail(display = "Invalid scope")]
+    InvalidScope,
+
     #[fail(display = "Internal error")]
     InternalError,
 
@@ -32,6 +38,18 @@ pub enum Error {
 
     #[fail(display = "Not found")]
     NotFound,
+
+    #[fail(display = "Expired client token")]
+    ExpiredToken,
+
+    #[fail(display = "Client token isn't valid yet")]
+    PrematureToken,
+
+    #[fail(display = "Invalid issuer of client token")]
+    InvalidIssuer,
+
+    #[fail(display = "Invalid audience of client token")]
+    InvalidAudience,
 }
 
 impl From<actix::MailboxError> for Error {
@@ -52,6 +70,19 @@ impl From<authn::jwt::DecodeError> for Error {
     }
 }
 
+impl From<authn::jwt::ValidationError> for Error {
+    fn from(e: authn::jwt::ValidationError) -> Self {
+        use authn::jwt::ValidationError::*;
+
+        match e {
+            Expired => Error::ExpiredToken,
+            Premature => Error::PrematureToken,
+            InvalidIssuer => Error::InvalidIssuer,
+            InvalidAudience => Error::InvalidAudience,
+        }
+    }
+}
+
 impl From<authn::jwt::EncodeError> for Error {
     fn from(_e: authn::jwt::EncodeError) -> Self {
         Error::InternalError
@@ -62,16 +93,22 @@ impl From<Error> for actix_web::Error {
     fn from(e: Error) -> Self {
         use self::Error::*;
 
+        let description = e.to_string();
         match e {
             ActorMailbox(_) | Db(_) | InternalError => {
                 actix_web::error::ErrorInternalServerError("")
             }
             InvalidClient => bad_request("invalid_client"),
             InvalidRequest => bad_request("invalid_request"),
+            InvalidTarget => bad_request("invalid_target"),
+            InvalidScope => bad_request("invalid_scope"),
             BadRequest => actix_web::error::ErrorBadRequest(""),
             Unauthorized => actix_web::error::ErrorUnauthorized(""),
             Forbidden => actix_web::error::ErrorForbidden(""),
             NotFound => actix_web::error::ErrorNotFound(""),
+            ExpiredToken | PrematureToken | InvalidIssuer | InvalidAudience => {
+                invalid_client(&description)
+            }
         }
     }
 }
@@ -82,3 +119,14 @@ fn bad_request(cause: &str) -> actix_web::Error {
     let resp = HttpResponse::BadRequest().json(json!({ "error": cause }));
     actix_web::error::InternalError::from_response("", resp).into()
 }
+
+/// Tells why the client's token is rejected.
+fn invalid_client(description: &str) -> actix_web::Error {
+    use actix_web::HttpResponse;
+
+    let resp = HttpResponse::BadRequest().json(json!({
+        "error": "invalid_client",
+        "error_description": description,
+    }));
+    actix_web::error::InternalError::from_response("", resp).into()
+}
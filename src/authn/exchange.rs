@@ -0,0 +1,130 @@
+use actix::Addr;
+use actix_web::HttpResponse;
+use chrono::{Duration, Utc};
+use diesel;
+use futures::future::{self, Future};
+
+use actors::{db, DbExecutor};
+use authn::{self, jwt};
+use rpc;
+
+pub const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+pub const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// Both tokens are IAM access tokens: `subject_token` of the account the new token
+/// is issued for and `actor_token` of the service acting on its behalf.
+#[derive(Debug)]
+pub struct Request {
+    pub subject_token: String,
+    pub actor_token: String,
+    pub audience: String,
+    pub expires_in: u16,
+    /// Narrows the scope of `subject_token`, which is inherited if omitted.
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response<'a> {
+    pub access_token: &'a str,
+    pub issued_token_type: &'a str,
+    pub token_type: &'a str,
+    pub expires_in: u16,
+}
+
+impl<'a> Response<'a> {
+    pub fn new(access_token: &'a str, expires_in: u16) -> Self {
+        use TOKEN_TYPE;
+        Response {
+            access_token,
+            issued_token_type: ACCESS_TOKEN_TYPE,
+            token_type: TOKEN_TYPE,
+            expires_in,
+        }
+    }
+}
+
+pub fn call(
+    meta: rpc::Meta,
+    req: Request,
+) -> impl Future<Item = HttpResponse, Error = authn::Error> {
+    let db = meta.db.unwrap();
+    let tokens = decode_access_token(&req.subject_token)
+        .and_then(|subject| Ok((subject, decode_access_token(&req.actor_token)?)));
+
+    future::result(tokens)
+        .and_then({
+            let db = db.clone();
+            move |(subject, actor)| {
+                authenticate(&db, &subject)
+                    .join(authenticate(&db, &actor))
+                    .map(|_| (subject, actor))
+            }
+        })
+        .and_then(move |(subject, actor)| {
+            let msg = db::namespace::find::Find::ByLabel(req.audience);
+            db.send(msg).from_err().and_then(move |res| {
+                let namespace = res.map_err(|_| authn::Error::InvalidTarget)?;
+                Ok((subject, actor, namespace.label, req.expires_in, req.scope))
+            })
+        })
+        .and_then(|(subject, actor, aud, expires_in, scope)| {
+            let mut token = jwt::AccessToken::new(aud, u32::from(expires_in), subject.sub);
+
+            // The new token never outlives the one it was exchanged for.
+            if token.exp > subject.exp {
+                token.exp = subject.exp;
+            }
+            // Neither is it less restricted.
+            token.scope = jwt::narrow_scope(subject.scope.as_ref().map(String::as_str), scope)
+                .map_err(|_| authn::Error::InvalidScope)?;
+            token.act = Some(jwt::Actor {
+                sub: actor.sub,
+                act: subject.act.map(Box::new),
+            });
+
+            let expires_in = (token.exp - Utc::now().naive_utc()).max(Duration::zero());
+            let expires_in = expires_in.num_seconds() as u16;
+            let access_token = jwt::AccessToken::encode(token)?;
+
+            Ok(HttpResponse::Ok().json(Response::new(&access_token, expires_in)))
+        })
+}
+
+fn decode_access_token(value: &str) -> Result<jwt::AccessToken, authn::Error> {
+    let raw_token = jwt::RawToken {
+        kind: jwt::RawTokenKind::Iam,
+        value,
+    };
+    let token = jwt::AccessToken::decode(&raw_token).map_err(|_| authn::Error::InvalidRequest)?;
+
+    let validator = jwt::Validator::default();
+    validator.call(&token).map_err(|_| authn::Error::InvalidRequest)?;
+
+    Ok(token)
+}
+
+/// Neither a revoked token nor a token of a disabled or deleted account is accepted.
+fn authenticate(
+    db: &Addr<DbExecutor>,
+    token: &jwt::AccessToken,
+) -> impl Future<Item = (), Error = authn::Error> {
+    let revoked = db::revoked_access_token::find::IsRevoked {
+        jti: token.jti(),
+        account_id: token.sub,
+        issued_at: token.iat,
+    };
+    let enabled = db::account::find::Find::Enabled(token.sub);
+
+    let revoked = db.send(revoked).from_err().and_then(|res| match res {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(authn::Error::InvalidRequest),
+        Err(e) => Err(e.into()),
+    });
+    let enabled = db.send(enabled).from_err().and_then(|res| match res {
+        Ok(_) => Ok(()),
+        Err(diesel::result::Error::NotFound) => Err(authn::Error::Forbidden),
+        Err(e) => Err(e.into()),
+    });
+
+    revoked.join(enabled).map(|_| ())
+}
@@ -0,0 +1,217 @@
+use actix::Addr;
+use actix_web::{self, HttpRequest, HttpResponse};
+use chrono::NaiveDateTime;
+use diesel;
+use futures::future::{self, Either, Future};
+use jsonwebtoken;
+use uuid::Uuid;
+
+use actors::{db, DbExecutor};
+use authn::{self, jwt};
+use models::Account;
+use AppState;
+
+/// `token_type_hint` is ignored, both kinds of tokens are tried anyway.
+#[derive(Debug, Deserialize)]
+struct Payload {
+    pub token: String,
+}
+
+/// Only `active` is returned for invalid tokens. The tokens of disabled or deleted
+/// accounts are inactive, though their claims are returned to tell the reason.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Response {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub act: Option<jwt::Actor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl Response {
+    fn inactive() -> Self {
+        Default::default()
+    }
+
+    fn new(account: &Account, aud: String, exp: Option<NaiveDateTime>, iat: NaiveDateTime) -> Self {
+        let disabled = account.disabled_at.is_some();
+        let deleted = account.deleted_at.is_some();
+
+        Response {
+            active: !disabled && !deleted,
+            sub: Some(account.id),
+            aud: Some(aud),
+            exp: exp.map(|v| v.timestamp()),
+            iat: Some(iat.timestamp()),
+            disabled: Some(disabled),
+            deleted: Some(deleted),
+            act: None,
+            scope: None,
+        }
+    }
+}
+
+pub fn call(
+    req: HttpRequest<AppState>,
+) -> impl Future<Item = HttpResponse, Error = authn::Error> {
+    use actix_web::FromRequest;
+
+    let meta = req.state().rpc_meta.clone();
+    let content_type = req
+        .headers()
+        .get("Content-Type")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_owned())
+        .ok_or(authn::Error::BadRequest);
+
+    // Only services having an access token are allowed to introspect tokens.
+    authenticate(&req, meta.db.clone().unwrap())
+        .and_then(|()| content_type)
+        .and_then(move |content_type| {
+            let f = match content_type.as_ref() {
+                "application/x-www-form-urlencoded" => {
+                    let f = actix_web::Form::<Payload>::extract(&req).map(|v| v.into_inner());
+                    Either::A(f)
+                }
+                "application/json" => {
+                    let f = actix_web::Json::<Payload>::extract(&req).map(|v| v.into_inner());
+                    Either::B(f)
+                }
+                _ => return Either::B(future::err(authn::Error::BadRequest)),
+            };
+
+            Either::A(f.map_err(|_| authn::Error::BadRequest))
+        })
+        .and_then(move |payload| {
+            let db = meta.db.unwrap();
+            match decode_access_token(&payload.token) {
+                Some(token) => Either::A(introspect_access_token(&db, token)),
+                None => Either::B(introspect_refresh_token(&db, payload.token)),
+            }
+        })
+        .map(|resp| HttpResponse::Ok().json(resp))
+}
+
+fn authenticate(
+    req: &HttpRequest<AppState>,
+    db: Addr<DbExecutor>,
+) -> impl Future<Item = (), Error = authn::Error> {
+    use {extract_authorization_header, Authorization};
+
+    let token = match extract_authorization_header(req.headers()) {
+        Ok(Some(Authorization::Bearer(value))) => decode_access_token(value),
+        _ => None,
+    };
+
+    match token {
+        Some(token) => {
+            let f = is_revoked(&db, &token).and_then(|revoked| {
+                if revoked {
+                    Err(authn::Error::Unauthorized)
+                } else {
+                    Ok(())
+                }
+            });
+            Either::A(f)
+        }
+        None => Either::B(future::err(authn::Error::Unauthorized)),
+    }
+}
+
+fn decode_access_token(value: &str) -> Option<jwt::AccessToken> {
+    let raw_token = jwt::RawToken {
+        kind: jwt::RawTokenKind::Iam,
+        value,
+    };
+    let token = jwt::AccessToken::decode(&raw_token).ok()?;
+
+    let validator = jwt::Validator::default();
+    validator.call(&token).ok()?;
+
+    Some(token)
+}
+
+fn is_revoked(
+    db: &Addr<DbExecutor>,
+    token: &jwt::AccessToken,
+) -> impl Future<Item = bool, Error = authn::Error> {
+    let msg = db::revoked_access_token::find::IsRevoked {
+        jti: token.jti(),
+        account_id: token.sub,
+        issued_at: token.iat,
+    };
+    db.send(msg).from_err().and_then(|res| Ok(res?))
+}
+
+/// Revoked tokens are reported just as invalid ones.
+fn introspect_access_token(
+    db: &Addr<DbExecutor>,
+    token: jwt::AccessToken,
+) -> impl Future<Item = Response, Error = authn::Error> {
+    let db = db.clone();
+    is_revoked(&db, &token).and_then(move |revoked| {
+        if revoked {
+            return Either::A(future::ok(Response::inactive()));
+        }
+
+        let msg = db::account::find::Find::Any(token.sub);
+        let f = db.send(msg).from_err().and_then(move |res| match res {
+            Ok(account) => {
+                let mut resp = Response::new(&account, token.aud, Some(token.exp), token.iat);
+                resp.act = token.act;
+                resp.scope = token.scope;
+                Ok(resp)
+            }
+            Err(diesel::result::Error::NotFound) => Ok(Response::inactive()),
+            Err(e) => Err(e.into()),
+        });
+        Either::B(f)
+    })
+}
+
+/// Refresh tokens are only active while they belong to the current generation of
+/// their session, the ones already exchanged for new tokens are not. Neither are
+/// the ones issued before all of the tokens of the account were revoked.
+fn introspect_refresh_token(
+    db: &Addr<DbExecutor>,
+    value: String,
+) -> impl Future<Item = Response, Error = authn::Error> {
+    let claims = jsonwebtoken::dangerous_unsafe_decode::<jwt::RefreshToken>(&value)
+        .map(|data| data.claims)
+        .ok();
+
+    let claims = match claims {
+        Some(claims) => claims,
+        None => return Either::A(future::ok(Response::inactive())),
+    };
+
+    let msg = db::refresh_token::find::FindWithAccount {
+        id: claims.sid,
+        account_id: claims.sub,
+    };
+    let f = db.send(msg).from_err().and_then(move |res| match res {
+        Ok((session, account)) => match jwt::RefreshToken::decode(&value, &session.keys) {
+            Ok(ref token)
+                if session.is_current(token.jti) && !account.is_token_revoked(token.iat) =>
+            {
+                Ok(Response::new(&account, token.aud.clone(), None, token.iat))
+            }
+            _ => Ok(Response::inactive()),
+        },
+        Err(diesel::result::Error::NotFound) => Ok(Response::inactive()),
+        Err(e) => Err(e.into()),
+    });
+    Either::B(f)
+}
@@ -0,0 +1,273 @@
+use actix::Arbiter;
+use actix_web::{client, http, HttpMessage, HttpRequest, HttpResponse};
+use base64;
+use failure;
+use futures::{future, Future};
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use authn::keys::{Curve, PublicKey};
+use authn::{self, AuthKey};
+use settings::Provider;
+use AppState;
+
+/// For how long, in seconds, clients may cache the keys.
+const MAX_AGE: u32 = 600;
+
+/// How often, in seconds, keys of a provider may be fetched on an unknown `kid`.
+const MIN_REFETCH_INTERVAL: u64 = 30;
+
+lazy_static! {
+    /// Keys of the providers configured with `jwks_url`, kept up to date by `JwksFetcher`.
+    static ref PROVIDER_KEYS: RwLock<BTreeMap<AuthKey, Vec<ProviderKey>>> =
+        RwLock::new(BTreeMap::new());
+    /// When keys of each provider were last fetched on an unknown `kid`.
+    static ref REFETCHED_AT: Mutex<BTreeMap<AuthKey, Instant>> = Mutex::new(BTreeMap::new());
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+    pub keys: Vec<Jwk>,
+}
+
+/// Members of a key, the ones not applicable to its type are omitted.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
+    pub use_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
+
+impl Jwk {
+    fn from_public_key(key: &PublicKey) -> Self {
+        match *key {
+            PublicKey::Ec {
+                curve,
+                ref x,
+                ref y,
+            } => Jwk {
+                kty: "EC".to_owned(),
+                crv: Some(curve.name().to_owned()),
+                x: Some(encode(x)),
+                y: Some(encode(y)),
+                ..Default::default()
+            },
+            PublicKey::Rsa { ref n, ref e } => Jwk {
+                kty: "RSA".to_owned(),
+                n: Some(encode(n)),
+                e: Some(encode(e)),
+                ..Default::default()
+            },
+            PublicKey::Ed25519(ref x) => Jwk {
+                kty: "OKP".to_owned(),
+                crv: Some("Ed25519".to_owned()),
+                x: Some(encode(x)),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn to_public_key(&self) -> Option<PublicKey> {
+        let crv = self.crv.as_ref().map(String::as_str);
+        match (self.kty.as_str(), crv) {
+            ("EC", Some(crv)) => {
+                let curve = Curve::from_name(crv)?;
+                let x = decode(&self.x)?;
+                let y = decode(&self.y)?;
+                let len = curve.coordinate_len();
+                if x.len() == len && y.len() == len {
+                    Some(PublicKey::Ec { curve, x, y })
+                } else {
+                    None
+                }
+            }
+            ("RSA", _) => Some(PublicKey::Rsa {
+                n: decode(&self.n)?,
+                e: decode(&self.e)?,
+            }),
+            ("OKP", Some("Ed25519")) => {
+                let x = decode(&self.x)?;
+                if x.len() == 32 {
+                    Some(PublicKey::Ed25519(x))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+fn encode(value: &[u8]) -> String {
+    base64::encode_config(value, base64::URL_SAFE_NO_PAD)
+}
+
+fn decode(value: &Option<String>) -> Option<Vec<u8>> {
+    let value = value.as_ref()?;
+    base64::decode_config(value, base64::URL_SAFE_NO_PAD).ok()
+}
+
+pub fn call(_req: &HttpRequest<AppState>) -> Result<HttpResponse, authn::Error> {
+    let settings = get_settings!();
+
+    let tokens = &settings.tokens;
+    let keys = tokens
+        .keys
+        .iter()
+        .map(|key| -> Result<Jwk, authn::Error> {
+            let public_key = PublicKey::from_pem(&key.public_key).ok_or_else(|| {
+                error!("Public key of kid = {} isn't supported", key.kid);
+                authn::Error::InternalError
+            })?;
+
+            Ok(Jwk {
+                alg: Some(tokens.algorithm.name().to_owned()),
+                use_: Some("sig".to_owned()),
+                kid: Some(key.kid.to_owned()),
+                ..Jwk::from_public_key(&public_key)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let resp = HttpResponse::Ok()
+        .header(
+            http::header::CACHE_CONTROL,
+            format!("public, max-age={}", MAX_AGE),
+        )
+        .json(Response { keys });
+    Ok(resp)
+}
+
+#[derive(Clone, Debug)]
+pub struct ProviderKey {
+    pub kid: Option<String>,
+    /// PEM encoded, as expected by `frank_jwt`.
+    pub public_key: String,
+}
+
+impl ProviderKey {
+    /// Keys which aren't meant for signatures or of unsupported types are skipped.
+    fn from_jwk(jwk: Jwk) -> Option<Self> {
+        if jwk.use_.as_ref().map_or(false, |v| v != "sig") {
+            return None;
+        }
+
+        let public_key = jwk.to_public_key()?;
+        Some(ProviderKey {
+            kid: jwk.kid,
+            public_key: public_key.to_pem(),
+        })
+    }
+}
+
+/// Fetches the key set published at `url`.
+pub fn fetch(url: &str) -> impl Future<Item = Vec<ProviderKey>, Error = failure::Error> {
+    future::result(client::get(url).finish())
+        .map_err(|e| failure::err_msg(e.to_string()))
+        .and_then(|req| req.send().from_err())
+        .and_then(|resp| {
+            if resp.status().is_success() {
+                future::Either::A(resp.json::<Response>().from_err())
+            } else {
+                let e = format_err!("Unexpected status code: {}", resp.status());
+                future::Either::B(future::err(e))
+            }
+        })
+        .map(|jwks| {
+            jwks.keys
+                .into_iter()
+                .filter_map(ProviderKey::from_jwk)
+                .collect()
+        })
+}
+
+/// Fetches the keys of the provider and stores them.
+pub fn refresh(auth_key: AuthKey, url: &str) -> impl Future<Item = (), Error = ()> {
+    fetch(url).then(move |res| {
+        match res {
+            Ok(keys) => {
+                debug!("Fetched {} keys of {}", keys.len(), auth_key);
+                store_provider_keys(auth_key, keys);
+            }
+            Err(e) => error!("Failed to fetch keys of {}: {}", auth_key, e),
+        }
+        Ok(())
+    })
+}
+
+/// Replaces the keys of the provider with the ones fetched last.
+pub fn store_provider_keys(auth_key: AuthKey, keys: Vec<ProviderKey>) {
+    let mut provider_keys = PROVIDER_KEYS
+        .write()
+        .expect("Provider keys RwLock is poisoned");
+    provider_keys.insert(auth_key, keys);
+}
+
+/// Tokens without `kid` are only accepted while the provider publishes a single key.
+///
+/// Keys are fetched out of schedule when `kid` is unknown, since the provider may have
+/// just rotated its keys. The token is rejected anyway, its retry will be accepted.
+pub fn find_provider_key(
+    auth_key: &AuthKey,
+    provider: &Provider,
+    kid: Option<&str>,
+) -> Option<String> {
+    let key = {
+        let provider_keys = PROVIDER_KEYS
+            .read()
+            .expect("Provider keys RwLock is poisoned");
+
+        provider_keys.get(auth_key).and_then(|keys| {
+            let key = match kid {
+                Some(kid) => keys
+                    .iter()
+                    .find(|key| key.kid.as_ref().map(String::as_str) == Some(kid)),
+                None if keys.len() == 1 => keys.first(),
+                None => None,
+            };
+            key.map(|key| key.public_key.to_owned())
+        })
+    };
+
+    if let (None, Some(kid), Some(url)) = (&key, kid, &provider.jwks_url) {
+        debug!("Unknown kid = {} of {}", kid, auth_key);
+        refetch(auth_key, url);
+    }
+
+    key
+}
+
+/// Fetches keys of the provider unless they were fetched that way recently.
+fn refetch(auth_key: &AuthKey, url: &str) {
+    {
+        let mut refetched_at = REFETCHED_AT
+            .lock()
+            .expect("Provider keys refetch Mutex is poisoned");
+
+        let now = Instant::now();
+        let interval = Duration::from_secs(MIN_REFETCH_INTERVAL);
+        match refetched_at.get(auth_key) {
+            Some(at) if now.duration_since(*at) < interval => return,
+            _ => {}
+        }
+        refetched_at.insert(auth_key.clone(), now);
+    }
+
+    Arbiter::spawn(refresh(auth_key.clone(), url));
+}
@@ -1,13 +1,16 @@
+use base64;
 use chrono::naive::serde::ts_seconds;
-use chrono::{NaiveDateTime, Utc};
+use chrono::{Duration, NaiveDateTime, Utc};
 use frank_jwt;
 use jsonwebtoken;
+use serde::de::{self, Deserialize, Deserializer};
 use serde_json;
 use uuid::Uuid;
 
-use std::fmt;
+use std::{fmt, str};
 
-use authn::AuthKey;
+use authn::keys::{self, PublicKey};
+use authn::{jwks, AuthKey};
 
 const ISSUER: &str = "iam.netology-group.services";
 
@@ -24,17 +27,238 @@ pub enum RawTokenKind<'a> {
 }
 
 impl<'a> RawToken<'a> {
-    fn public_key(&self) -> Option<String> {
+    /// Returns the public key to verify the token with along with the algorithm
+    /// the token has to be signed with.
+    fn verification_key(&self) -> Result<(String, Algorithm), DecodeError> {
         use self::RawTokenKind::*;
 
+        let header = decode_header(self.value)?;
         let settings = get_settings!();
-        match self.kind {
-            Iam => Some(settings.authentication.key.to_owned()),
-            Client(auth_key) => settings.providers.get(auth_key).map(|v| v.key.to_owned()),
+        let (key, algorithm) = match self.kind {
+            Iam => {
+                let tokens = &settings.tokens;
+                // Tokens issued before the keyring was introduced don't have `kid`.
+                let key = match header.kid {
+                    Some(ref kid) => tokens.find_key(kid),
+                    None => tokens.active_key(),
+                };
+                let key = key.ok_or_else(|| DecodeError::UnknownKey)?;
+                (key.public_key.to_owned(), tokens.algorithm)
+            }
+            Client(auth_key) => {
+                let provider = settings
+                    .providers
+                    .get(auth_key)
+                    .ok_or_else(|| DecodeError::UnknownIssuer)?;
+
+                let key = match provider.key {
+                    Some(ref key) => key.to_owned(),
+                    None => {
+                        let kid = header.kid.as_ref().map(String::as_str);
+                        jwks::find_provider_key(auth_key, provider, kid)
+                            .ok_or_else(|| DecodeError::UnknownKey)?
+                    }
+                };
+                (key, provider.algorithm)
+            }
+        };
+
+        // The algorithm is never taken from the token, otherwise a public key
+        // could be used as a secret of HMAC.
+        if header.alg.as_ref().map(String::as_str) != Some(algorithm.name()) {
+            return Err(DecodeError::InvalidAlgorithm);
+        }
+
+        Ok((key, algorithm))
+    }
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: Option<String>,
+    kid: Option<String>,
+}
+
+/// Reads the header of the token before its signature is verified,
+/// since it determines the key to verify the signature with.
+fn decode_header(token: &str) -> Result<Header, DecodeError> {
+    let header = token.split('.').next().unwrap_or_default();
+    let header = base64::decode_config(header, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| DecodeError::InvalidPayload)?;
+
+    serde_json::from_slice::<Header>(&header).map_err(|_| DecodeError::InvalidPayload)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Algorithm {
+    ES256,
+    ES384,
+    ES512,
+    RS256,
+    RS384,
+    RS512,
+    EdDSA,
+}
+
+impl Algorithm {
+    pub fn name(self) -> &'static str {
+        use self::Algorithm::*;
+
+        match self {
+            ES256 => "ES256",
+            ES384 => "ES384",
+            ES512 => "ES512",
+            RS256 => "RS256",
+            RS384 => "RS384",
+            RS512 => "RS512",
+            EdDSA => "EdDSA",
+        }
+    }
+
+    /// Signs the token, `public_key` is only used by EdDSA.
+    pub fn sign(
+        self,
+        header: serde_json::Value,
+        payload: &serde_json::Value,
+        private_key: &str,
+        public_key: &str,
+    ) -> Result<String, EncodeError> {
+        match self.frank_jwt() {
+            Some(algorithm) => {
+                frank_jwt::encode(header, &private_key.to_owned(), payload, algorithm)
+                    .map_err(|_| EncodeError)
+            }
+            None => sign_eddsa(header, payload, private_key, public_key),
+        }
+    }
+
+    /// Verifies the signature of the token and returns its payload.
+    pub fn verify(self, token: &str, public_key: &str) -> Result<serde_json::Value, DecodeError> {
+        match self.frank_jwt() {
+            Some(algorithm) => {
+                frank_jwt::decode(&token.to_owned(), &public_key.to_owned(), algorithm)
+                    .map(|(_header, payload)| payload)
+                    .map_err(|_| DecodeError::InvalidSignature)
+            }
+            None => verify_eddsa(token, public_key),
+        }
+    }
+
+    fn frank_jwt(self) -> Option<frank_jwt::Algorithm> {
+        use self::Algorithm::*;
+
+        match self {
+            ES256 => Some(frank_jwt::Algorithm::ES256),
+            ES384 => Some(frank_jwt::Algorithm::ES384),
+            ES512 => Some(frank_jwt::Algorithm::ES512),
+            RS256 => Some(frank_jwt::Algorithm::RS256),
+            RS384 => Some(frank_jwt::Algorithm::RS384),
+            RS512 => Some(frank_jwt::Algorithm::RS512),
+            EdDSA => None,
         }
     }
 }
 
+impl Default for Algorithm {
+    fn default() -> Self {
+        Algorithm::ES256
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let algorithm = s
+            .parse()
+            .map_err(|_| de::Error::custom(format!("Unsupported algorithm: {}", s)))?;
+        Ok(algorithm)
+    }
+}
+
+impl str::FromStr for Algorithm {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Algorithm::*;
+
+        [ES256, ES384, ES512, RS256, RS384, RS512, EdDSA]
+            .iter()
+            .find(|algorithm| algorithm.name() == s)
+            .cloned()
+            .ok_or(())
+    }
+}
+
+/// `frank_jwt` doesn't support EdDSA, so Ed25519 signatures are handled by `ring`.
+fn sign_eddsa(
+    mut header: serde_json::Value,
+    payload: &serde_json::Value,
+    private_key: &str,
+    public_key: &str,
+) -> Result<String, EncodeError> {
+    use ring::signature::Ed25519KeyPair;
+    use untrusted::Input;
+
+    let seed = keys::ed25519_seed_from_pem(private_key).ok_or(EncodeError)?;
+    let public_key = match PublicKey::from_pem(public_key) {
+        Some(PublicKey::Ed25519(key)) => key,
+        _ => return Err(EncodeError),
+    };
+    let key_pair = Ed25519KeyPair::from_seed_and_public_key(
+        Input::from(&seed),
+        Input::from(&public_key),
+    ).map_err(|_| EncodeError)?;
+
+    header["alg"] = json!(Algorithm::EdDSA.name());
+    header["typ"] = json!("JWT");
+
+    let signing_input = format!(
+        "{}.{}",
+        base64::encode_config(&header.to_string(), base64::URL_SAFE_NO_PAD),
+        base64::encode_config(&payload.to_string(), base64::URL_SAFE_NO_PAD)
+    );
+    let signature = key_pair.sign(signing_input.as_bytes());
+
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        base64::encode_config(signature.as_ref(), base64::URL_SAFE_NO_PAD)
+    ))
+}
+
+fn verify_eddsa(token: &str, public_key: &str) -> Result<serde_json::Value, DecodeError> {
+    use ring::signature::{self, ED25519};
+    use untrusted::Input;
+
+    let public_key = match PublicKey::from_pem(public_key) {
+        Some(PublicKey::Ed25519(key)) => key,
+        _ => return Err(DecodeError::InvalidSignature),
+    };
+
+    let mut parts = token.rsplitn(2, '.');
+    let (signature, signing_input) = match (parts.next(), parts.next()) {
+        (Some(signature), Some(signing_input)) => (signature, signing_input),
+        _ => return Err(DecodeError::InvalidPayload),
+    };
+    let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| DecodeError::InvalidSignature)?;
+
+    signature::verify(
+        &ED25519,
+        Input::from(&public_key),
+        Input::from(signing_input.as_bytes()),
+        Input::from(&signature),
+    ).map_err(|_| DecodeError::InvalidSignature)?;
+
+    let payload = signing_input.split('.').nth(1).unwrap_or_default();
+    let payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| DecodeError::InvalidPayload)?;
+    serde_json::from_slice(&payload).map_err(|_| DecodeError::InvalidPayload)
+}
+
 #[derive(Serialize, Deserialize, PartialEq)]
 pub struct AccessToken {
     pub aud: String,
@@ -43,7 +267,22 @@ pub struct AccessToken {
     pub exp: NaiveDateTime,
     #[serde(with = "ts_seconds")]
     pub iat: NaiveDateTime,
+    #[serde(
+        default,
+        with = "ts_seconds_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub nbf: Option<NaiveDateTime>,
     pub sub: Uuid,
+    /// Always a UUID for the tokens issued by IAM, providers may use any string.
+    /// Tokens issued before the claim was introduced can't be revoked one by one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+    /// Space-delimited scopes of the RPC methods, the token isn't restricted without it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 impl AccessToken {
@@ -55,51 +294,121 @@ impl AccessToken {
             iss: ISSUER.to_owned(),
             exp: NaiveDateTime::from_timestamp(now + i64::from(exp), 0),
             iat: NaiveDateTime::from_timestamp(now, 0),
+            nbf: None,
             sub,
+            jti: Some(Uuid::new_v4().to_string()),
+            act: None,
+            scope: None,
         }
     }
 
     pub fn decode(token: &RawToken) -> Result<AccessToken, DecodeError> {
-        let key = token
-            .public_key()
-            .ok_or_else(|| DecodeError::UnknownIssuer)?;
-
-        if let Ok((_header, payload)) =
-            frank_jwt::decode(&token.value.to_owned(), &key, frank_jwt::Algorithm::ES256)
-        {
-            serde_json::from_value(payload).map_err(|_| DecodeError::InvalidPayload)
-        } else {
-            Err(DecodeError::InvalidSignature)
-        }
+        let (key, algorithm) = token.verification_key()?;
+        let payload = algorithm.verify(token.value, &key)?;
+
+        serde_json::from_value(payload).map_err(|_| DecodeError::InvalidPayload)
     }
 
     pub fn encode(payload: AccessToken) -> Result<String, EncodeError> {
         let settings = get_settings!();
+        let tokens = &settings.tokens;
+        let key = tokens.active_key().ok_or(EncodeError)?;
+        let private_key = key.private_key.as_ref().ok_or(EncodeError)?;
 
-        frank_jwt::encode(
-            json!({}),
-            &settings.tokens.key,
+        tokens.algorithm.sign(
+            json!({ "kid": key.kid }),
             &serde_json::to_value(payload).unwrap(),
-            frank_jwt::Algorithm::ES256,
-        ).map_err(|_| EncodeError)
+            private_key,
+            &key.public_key,
+        )
     }
 
     pub fn default_expires_in() -> u16 {
         let settings = get_settings!();
         settings.tokens.expires_in
     }
+
+    pub fn jti(&self) -> Option<Uuid> {
+        self.jti.as_ref().and_then(|v| Uuid::parse_str(v).ok())
+    }
+
+    pub fn scopes(&self) -> Option<Vec<String>> {
+        self.scope.as_ref().map(|v| parse_scope(v))
+    }
+}
+
+/// Every scope is a `{resource}:read` or `{resource}:write` pair,
+/// where the resource is the one of the RPC methods, e.g. `abac_policy:read`.
+pub fn validate_scope(scope: &str) -> bool {
+    let scopes = parse_scope(scope);
+    !scopes.is_empty() && scopes.iter().all(|scope| is_valid_scope(scope))
+}
+
+/// The scope of a token issued in place of one with the `granted` scope.
+/// The `requested` one may only narrow it, the granted one is inherited if none is requested.
+pub fn narrow_scope(
+    granted: Option<&str>,
+    requested: Option<String>,
+) -> Result<Option<String>, ()> {
+    match (granted, requested) {
+        (Some(granted), None) => Ok(Some(granted.to_owned())),
+        (Some(granted), Some(requested)) => {
+            let granted = parse_scope(granted);
+            if parse_scope(&requested)
+                .iter()
+                .all(|scope| granted.contains(scope))
+            {
+                Ok(Some(requested))
+            } else {
+                Err(())
+            }
+        }
+        (None, requested) => Ok(requested),
+    }
+}
+
+fn is_valid_scope(scope: &str) -> bool {
+    let mut kv = scope.splitn(2, ':');
+    match (kv.next(), kv.next()) {
+        (Some(resource), Some("read")) | (Some(resource), Some("write")) => {
+            !resource.is_empty() && resource.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn parse_scope(scope: &str) -> Vec<String> {
+    scope.split_whitespace().map(|s| s.to_owned()).collect()
 }
 
 impl fmt::Debug for AccessToken {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "AccessToken {{ aud: {}, iss: {}, exp: {}, iat: {}, sub: {} }}",
-            self.aud, self.iss, self.exp, self.iat, self.sub
+            "AccessToken {{ aud: {}, iss: {}, exp: {}, iat: {}, nbf: {:?}, sub: {}, jti: {:?}, \
+             act: {:?}, scope: {:?} }}",
+            self.aud,
+            self.iss,
+            self.exp,
+            self.iat,
+            self.nbf,
+            self.sub,
+            self.jti,
+            self.act,
+            self.scope
         )
     }
 }
 
+/// The party acting on behalf of the subject of a token, following RFC 8693.
+/// The nested actor is the previous one of a delegation chain.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Actor {
+    pub sub: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Box<Actor>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RefreshToken {
     pub aud: String,
@@ -107,10 +416,18 @@ pub struct RefreshToken {
     #[serde(with = "ts_seconds")]
     pub iat: NaiveDateTime,
     pub sub: Uuid,
+    /// Tokens issued before sessions were introduced don't carry it,
+    /// they belong to the earliest session of the account.
+    #[serde(default)]
+    pub sid: Option<Uuid>,
+    /// Tokens issued before rotation was introduced don't carry it,
+    /// they are taken for the first generation of the session.
+    #[serde(default)]
+    pub jti: Option<Uuid>,
 }
 
 impl RefreshToken {
-    pub fn new(aud: String, sub: Uuid) -> Self {
+    pub fn new(aud: String, sub: Uuid, sid: Uuid, jti: Uuid) -> Self {
         let now = Utc::now().timestamp();
 
         RefreshToken {
@@ -118,14 +435,24 @@ impl RefreshToken {
             iss: ISSUER.to_owned(),
             iat: NaiveDateTime::from_timestamp(now, 0),
             sub,
+            sid: Some(sid),
+            jti: Some(jti),
         }
     }
 
-    pub fn decode(token: &str, key: &[u8]) -> Result<RefreshToken, ()> {
+    /// Verifies the token against each of the keys, the newest key goes first.
+    ///
+    /// Previous keys are kept for a while after rotation,
+    /// so that tokens signed with them are still accepted.
+    pub fn decode(token: &str, keys: &[Vec<u8>]) -> Result<RefreshToken, ()> {
         use jsonwebtoken::{Algorithm, Validation};
-        jsonwebtoken::decode(token, key, &Validation::new(Algorithm::HS256))
+
+        let validation = Validation::new(Algorithm::HS256);
+        keys.iter()
+            .filter_map(|key| jsonwebtoken::decode(token, key, &validation).ok())
             .map(|data| data.claims)
-            .map_err(|_| ())
+            .next()
+            .ok_or(())
     }
 
     pub fn encode(payload: &RefreshToken, key: &[u8]) -> Result<String, EncodeError> {
@@ -144,29 +471,216 @@ pub enum DecodeError {
 
     #[fail(display = "Unknown issuer")]
     UnknownIssuer,
+
+    #[fail(display = "Unknown key")]
+    UnknownKey,
+
+    #[fail(display = "Invalid algorithm")]
+    InvalidAlgorithm,
 }
 
 #[derive(Debug)]
 pub struct EncodeError;
 
+#[derive(Debug, Fail, PartialEq)]
+pub enum ValidationError {
+    #[fail(display = "Expired token")]
+    Expired,
+
+    #[fail(display = "Token isn't valid yet")]
+    Premature,
+
+    #[fail(display = "Invalid issuer")]
+    InvalidIssuer,
+
+    #[fail(display = "Invalid audience")]
+    InvalidAudience,
+}
+
+/// Checks the claims of a token, empty `issuers` or `audiences` accept any.
 #[derive(Debug)]
 pub struct Validator {
-    pub exp: NaiveDateTime,
+    pub now: NaiveDateTime,
+    /// Allowed clock skew between IAM and the token issuer.
+    pub leeway: Duration,
+    pub issuers: Vec<String>,
+    pub audiences: Vec<String>,
 }
 
 impl Validator {
-    pub fn call(&self, token: &AccessToken) -> bool {
-        self.exp < token.exp
+    /// Issuers and audiences default to the provider and IAM itself respectively.
+    pub fn for_provider(auth_key: &AuthKey) -> Option<Self> {
+        let settings = get_settings!();
+        let provider = settings.providers.get(auth_key)?;
+
+        let issuers = if provider.issuers.is_empty() {
+            vec![auth_key.provider.to_owned()]
+        } else {
+            provider.issuers.clone()
+        };
+        let audiences = if provider.audiences.is_empty() {
+            vec![ISSUER.to_owned()]
+        } else {
+            provider.audiences.clone()
+        };
+
+        Some(Validator {
+            leeway: Duration::seconds(i64::from(provider.leeway)),
+            issuers,
+            audiences,
+            ..Default::default()
+        })
+    }
+
+    pub fn call(&self, token: &AccessToken) -> Result<(), ValidationError> {
+        if token.exp + self.leeway <= self.now {
+            return Err(ValidationError::Expired);
+        }
+
+        // A token issued in the future isn't valid yet either.
+        let not_before = token.nbf.map_or(token.iat, |nbf| nbf.max(token.iat));
+        if not_before - self.leeway > self.now {
+            return Err(ValidationError::Premature);
+        }
+
+        if !self.issuers.is_empty() && !self.issuers.contains(&token.iss) {
+            return Err(ValidationError::InvalidIssuer);
+        }
+
+        if !self.audiences.is_empty() && !self.audiences.contains(&token.aud) {
+            return Err(ValidationError::InvalidAudience);
+        }
+
+        Ok(())
     }
 }
 
 impl Default for Validator {
     fn default() -> Self {
-        use chrono::Utc;
         let now = Utc::now();
 
         Validator {
-            exp: NaiveDateTime::from_timestamp(now.timestamp(), 0),
+            now: NaiveDateTime::from_timestamp(now.timestamp(), 0),
+            leeway: Duration::zero(),
+            issuers: Vec::new(),
+            audiences: Vec::new(),
+        }
+    }
+}
+
+mod ts_seconds_option {
+    use chrono::NaiveDateTime;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<NaiveDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *value {
+            Some(ref value) => serializer.serialize_some(&value.timestamp()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveDateTime>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<i64>::deserialize(deserializer)? {
+            Some(ts) => NaiveDateTime::from_timestamp_opt(ts, 0)
+                .map(Some)
+                .ok_or_else(|| de::Error::custom(format!("Invalid timestamp: {}", ts))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_token(exp: i64, iat: i64, nbf: Option<i64>) -> AccessToken {
+        let mut token = AccessToken::new(ISSUER.to_owned(), 0, Uuid::nil());
+        let now = token.iat;
+
+        token.iss = "foxford.ru".to_owned();
+        token.exp = now + Duration::seconds(exp);
+        token.iat = now + Duration::seconds(iat);
+        token.nbf = nbf.map(|nbf| now + Duration::seconds(nbf));
+        token
+    }
+
+    fn build_validator(leeway: i64) -> Validator {
+        Validator {
+            leeway: Duration::seconds(leeway),
+            issuers: vec!["foxford.ru".to_owned()],
+            audiences: vec![ISSUER.to_owned()],
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn expired() {
+        let token = build_token(-10, -100, None);
+        assert_eq!(build_validator(0).call(&token), Err(ValidationError::Expired));
+        assert_eq!(build_validator(30).call(&token), Ok(()));
+    }
+
+    #[test]
+    fn premature() {
+        let token = build_token(300, 0, Some(10));
+        assert_eq!(build_validator(0).call(&token), Err(ValidationError::Premature));
+        assert_eq!(build_validator(30).call(&token), Ok(()));
+
+        let token = build_token(300, 10, None);
+        assert_eq!(build_validator(0).call(&token), Err(ValidationError::Premature));
+        assert_eq!(build_validator(30).call(&token), Ok(()));
+    }
+
+    #[test]
+    fn invalid_issuer_and_audience() {
+        let mut token = build_token(300, 0, None);
+        token.aud = "foxford.ru".to_owned();
+        assert_eq!(
+            build_validator(0).call(&token),
+            Err(ValidationError::InvalidAudience)
+        );
+
+        token.iss = "netology.ru".to_owned();
+        assert_eq!(
+            build_validator(0).call(&token),
+            Err(ValidationError::InvalidIssuer)
+        );
+    }
+
+    #[test]
+    fn scope() {
+        assert!(validate_scope("abac_policy:read"));
+        assert!(validate_scope("abac_policy:read  account:write"));
+        assert!(!validate_scope(""));
+        assert!(!validate_scope("abac_policy"));
+        assert!(!validate_scope("abac_policy:delete"));
+        assert!(!validate_scope(":read"));
+
+        let mut token = build_token(300, 0, None);
+        assert_eq!(token.scopes(), None);
+
+        token.scope = Some("abac_policy:read account:write".to_owned());
+        let expected = vec!["abac_policy:read".to_owned(), "account:write".to_owned()];
+        assert_eq!(token.scopes(), Some(expected));
+    }
+
+    #[test]
+    fn narrowed_scope() {
+        let granted = Some("abac_policy:read account:write");
+        let requested = Some("account:write".to_owned());
+        assert_eq!(narrow_scope(granted, None), Ok(granted.map(str::to_owned)));
+        assert_eq!(
+            narrow_scope(granted, requested.clone()),
+            Ok(requested.clone())
+        );
+        assert_eq!(narrow_scope(None, requested.clone()), Ok(requested));
+        assert_eq!(narrow_scope(None, None), Ok(None));
+        assert_eq!(narrow_scope(granted, Some("account:read".to_owned())), Err(()));
+    }
 }
@@ -0,0 +1,283 @@
+use base64;
+
+use std::str;
+
+const RSA_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const EC_OID: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const ED25519_OID: &[u8] = &[0x2b, 0x65, 0x70];
+
+const SEQUENCE: u8 = 0x30;
+const INTEGER: u8 = 0x02;
+const BIT_STRING: u8 = 0x03;
+const OCTET_STRING: u8 = 0x04;
+const NULL: u8 = 0x05;
+const OID: u8 = 0x06;
+
+/// Public key material, either of a PEM encoded SubjectPublicKeyInfo or of a JWK.
+#[derive(Debug, PartialEq)]
+pub enum PublicKey {
+    Ec { curve: Curve, x: Vec<u8>, y: Vec<u8> },
+    Rsa { n: Vec<u8>, e: Vec<u8> },
+    Ed25519(Vec<u8>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Curve {
+    P256,
+    P384,
+    P521,
+}
+
+impl Curve {
+    pub fn name(self) -> &'static str {
+        match self {
+            Curve::P256 => "P-256",
+            Curve::P384 => "P-384",
+            Curve::P521 => "P-521",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Curve::P256, Curve::P384, Curve::P521]
+            .iter()
+            .find(|curve| curve.name() == name)
+            .cloned()
+    }
+
+    pub fn coordinate_len(self) -> usize {
+        match self {
+            Curve::P256 => 32,
+            Curve::P384 => 48,
+            Curve::P521 => 66,
+        }
+    }
+
+    fn oid(self) -> &'static [u8] {
+        match self {
+            Curve::P256 => &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+            Curve::P384 => &[0x2b, 0x81, 0x04, 0x00, 0x22],
+            Curve::P521 => &[0x2b, 0x81, 0x04, 0x00, 0x23],
+        }
+    }
+
+    fn from_oid(oid: &[u8]) -> Option<Self> {
+        [Curve::P256, Curve::P384, Curve::P521]
+            .iter()
+            .find(|curve| curve.oid() == oid)
+            .cloned()
+    }
+}
+
+impl PublicKey {
+    pub fn from_pem(pem: &str) -> Option<Self> {
+        let der = decode_pem(pem)?;
+        let (spki, _) = read(SEQUENCE, &der)?;
+        let (algorithm, rest) = read(SEQUENCE, spki)?;
+        let (bits, _) = read(BIT_STRING, rest)?;
+        let (oid, params) = read(OID, algorithm)?;
+
+        let key = match bits.split_first() {
+            Some((&0, key)) => key,
+            _ => return None,
+        };
+
+        if oid == RSA_OID {
+            let (key, _) = read(SEQUENCE, key)?;
+            let (n, rest) = read(INTEGER, key)?;
+            let (e, _) = read(INTEGER, rest)?;
+            Some(PublicKey::Rsa {
+                n: strip_zeros(n).to_vec(),
+                e: strip_zeros(e).to_vec(),
+            })
+        } else if oid == EC_OID {
+            let (curve, _) = read(OID, params)?;
+            let curve = Curve::from_oid(curve)?;
+            let len = curve.coordinate_len();
+            match key.split_first() {
+                Some((&4, point)) if point.len() == 2 * len => Some(PublicKey::Ec {
+                    curve,
+                    x: point[..len].to_vec(),
+                    y: point[len..].to_vec(),
+                }),
+                _ => None,
+            }
+        } else if oid == ED25519_OID && key.len() == 32 {
+            Some(PublicKey::Ed25519(key.to_vec()))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_pem(&self) -> String {
+        let (algorithm, key) = match *self {
+            PublicKey::Rsa { ref n, ref e } => {
+                let algorithm = [write(OID, RSA_OID), write(NULL, &[])].concat();
+                let key = [write_integer(n), write_integer(e)].concat();
+                (algorithm, write(SEQUENCE, &key))
+            }
+            PublicKey::Ec {
+                curve,
+                ref x,
+                ref y,
+            } => {
+                let algorithm = [write(OID, EC_OID), write(OID, curve.oid())].concat();
+                (algorithm, [&[4][..], &x[..], &y[..]].concat())
+            }
+            PublicKey::Ed25519(ref key) => (write(OID, ED25519_OID), key.to_owned()),
+        };
+
+        let bits = [&[0][..], &key[..]].concat();
+        let spki = [write(SEQUENCE, &algorithm), write(BIT_STRING, &bits)].concat();
+        encode_pem("PUBLIC KEY", &write(SEQUENCE, &spki))
+    }
+}
+
+/// Reads the seed of a PEM encoded PKCS#8 Ed25519 private key.
+pub fn ed25519_seed_from_pem(pem: &str) -> Option<Vec<u8>> {
+    let der = decode_pem(pem)?;
+    let (pkcs8, _) = read(SEQUENCE, &der)?;
+    let (_version, rest) = read(INTEGER, pkcs8)?;
+    let (algorithm, rest) = read(SEQUENCE, rest)?;
+    let (private_key, _) = read(OCTET_STRING, rest)?;
+    let (oid, _) = read(OID, algorithm)?;
+    let (seed, _) = read(OCTET_STRING, private_key)?;
+
+    if oid == ED25519_OID && seed.len() == 32 {
+        Some(seed.to_vec())
+    } else {
+        None
+    }
+}
+
+fn decode_pem(pem: &str) -> Option<Vec<u8>> {
+    let der = pem
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+    base64::decode(&der).ok()
+}
+
+fn encode_pem(label: &str, der: &[u8]) -> String {
+    let der = base64::encode(der);
+    let lines = der
+        .as_bytes()
+        .chunks(64)
+        .map(|line| str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>();
+
+    format!(
+        "-----BEGIN {label}-----\n{}\n-----END {label}-----\n",
+        lines.join("\n"),
+        label = label
+    )
+}
+
+/// Reads a DER element of the expected tag, returns its content and the rest of the input.
+fn read(tag: u8, input: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (&actual_tag, input) = input.split_first()?;
+    let (&len, mut input) = input.split_first()?;
+    if actual_tag != tag {
+        return None;
+    }
+
+    let len = match len {
+        0..=0x7f => len as usize,
+        0x81..=0x82 => {
+            let count = (len & 0x7f) as usize;
+            if input.len() < count {
+                return None;
+            }
+            let (len, rest) = input.split_at(count);
+            input = rest;
+            len.iter().fold(0, |acc, &b| (acc << 8) | b as usize)
+        }
+        _ => return None,
+    };
+
+    if input.len() < len {
+        return None;
+    }
+    Some(input.split_at(len))
+}
+
+fn write(tag: u8, content: &[u8]) -> Vec<u8> {
+    let len = content.len();
+    let mut der = vec![tag];
+    if len < 0x80 {
+        der.push(len as u8);
+    } else if len <= 0xff {
+        der.extend_from_slice(&[0x81, len as u8]);
+    } else {
+        der.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]);
+    }
+    der.extend_from_slice(content);
+    der
+}
+
+/// Writes an unsigned big-endian integer.
+fn write_integer(value: &[u8]) -> Vec<u8> {
+    let value = strip_zeros(value);
+    match value.first() {
+        Some(b) if b & 0x80 == 0 => write(INTEGER, value),
+        _ => write(INTEGER, &[&[0][..], value].concat()),
+    }
+}
+
+fn strip_zeros(value: &[u8]) -> &[u8] {
+    let zeros = value.iter().take_while(|&&b| b == 0).count();
+    &value[zeros..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ec_public_key() {
+        let pem = include_str!("../../tests/keys/iam/public_key.pem");
+        let key = PublicKey::from_pem(pem).unwrap();
+
+        match key {
+            PublicKey::Ec { curve, .. } => assert_eq!(curve, Curve::P256),
+            _ => panic!("{:?}", key),
+        }
+        assert_eq!(key.to_pem(), pem);
+    }
+
+    #[test]
+    fn rsa_public_key() {
+        let pem = include_str!("../../tests/keys/foxford-rsa/public_key.pem");
+        let key = PublicKey::from_pem(pem).unwrap();
+
+        match key {
+            PublicKey::Rsa { ref n, ref e } => {
+                assert_eq!(n.len(), 256);
+                assert_eq!(e, &[1, 0, 1]);
+            }
+            _ => panic!("{:?}", key),
+        }
+        assert_eq!(key.to_pem(), pem);
+    }
+
+    #[test]
+    fn ed25519_keys() {
+        let pem = include_str!("../../tests/keys/foxford-ed25519/public_key.pem");
+        let key = PublicKey::from_pem(pem).unwrap();
+
+        match key {
+            PublicKey::Ed25519(ref v) => assert_eq!(v.len(), 32),
+            _ => panic!("{:?}", key),
+        }
+        assert_eq!(key.to_pem(), pem);
+
+        let pem = include_str!("../../tests/keys/foxford-ed25519/private_key.pem");
+        assert_eq!(ed25519_seed_from_pem(pem).map(|v| v.len()), Some(32));
+    }
+
+    #[test]
+    fn unsupported_curve() {
+        let pem = include_str!("../../tests/keys/foxford/public_key.pem");
+        assert_eq!(PublicKey::from_pem(pem), None);
+    }
+}
@@ -3,7 +3,12 @@ pub use authn::error::Error;
 
 mod auth_key;
 mod error;
+pub mod exchange;
+pub mod introspect;
+pub mod jwks;
 pub mod jwt;
+mod keys;
 pub mod refresh;
 pub mod retrieve;
 pub mod revoke;
+pub mod service_account;
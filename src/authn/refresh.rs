@@ -1,32 +1,33 @@
 use actix_web::{HttpMessage, HttpRequest, HttpResponse, Path};
-use futures::Future;
-use jsonwebtoken;
+use futures::future::{self, Either, Future};
 use serde_json;
-use uuid::Uuid;
 
 use actors::db;
-use authn::{self, jwt};
+use authn::{self, jwt, revoke};
 use AppState;
 
 #[derive(Debug, Deserialize)]
 pub struct Payload {
     #[serde(default = "jwt::AccessToken::default_expires_in")]
     pub expires_in: u16,
+    pub scope: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Response<'a> {
     pub access_token: &'a str,
+    pub refresh_token: &'a str,
     pub expires_in: u16,
     pub token_type: &'a str,
 }
 
 impl<'a> Response<'a> {
-    pub fn new(access_token: &'a str, expires_in: u16) -> Self {
+    pub fn new(access_token: &'a str, refresh_token: &'a str, expires_in: u16) -> Self {
         use TOKEN_TYPE;
         Response {
             token_type: TOKEN_TYPE,
             access_token,
+            refresh_token,
             expires_in,
         }
     }
@@ -35,7 +36,7 @@ impl<'a> Response<'a> {
 pub fn call(
     (req, path): (HttpRequest<AppState>, Path<String>),
 ) -> impl Future<Item = HttpResponse, Error = authn::Error> {
-    use extract_authorization_header;
+    use {extract_authorization_header, Authorization};
 
     let meta = req.state().rpc_meta.clone();
     let headers = req.headers().clone();
@@ -44,71 +45,108 @@ pub fn call(
         .map_err(|_| authn::Error::InternalError)
         .and_then(|body| {
             if body.is_empty() {
-                Ok(authn::jwt::AccessToken::default_expires_in())
+                Ok((authn::jwt::AccessToken::default_expires_in(), None))
             } else {
                 if let Ok(payload) = serde_json::from_slice::<Payload>(&body) {
                     let settings = get_settings!();
                     if payload.expires_in <= settings.tokens.expires_in_max {
-                        return Ok(payload.expires_in);
+                        return match payload.scope {
+                            Some(ref scope) if !jwt::validate_scope(scope) => {
+                                Err(authn::Error::InvalidScope)
+                            }
+                            _ => Ok((payload.expires_in, payload.scope)),
+                        };
                     }
                 }
 
                 Err(authn::Error::BadRequest)
             }
         })
-        .and_then(move |expires_in| {
-            if let Ok(header) = extract_authorization_header(&headers) {
-                if let Some(v) = header {
-                    Ok((expires_in, v.to_owned()))
-                } else {
-                    Err(authn::Error::Forbidden)
-                }
-            } else {
-                Err(authn::Error::Unauthorized)
+        .and_then(move |params| {
+            let jwt = match extract_authorization_header(&headers) {
+                Ok(Some(Authorization::Bearer(v))) => v.to_owned(),
+                Ok(None) => return Err(authn::Error::Forbidden),
+                _ => return Err(authn::Error::Unauthorized),
+            };
+            Ok((params, jwt))
+        })
+        .and_then(move |(params, jwt)| {
+            let (account_id, session_id) = revoke::parse_session(&path.into_inner(), &jwt)?;
+            Ok((params, jwt, account_id, session_id))
+        })
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |(params, jwt, account_id, session_id)| {
+                let msg = db::refresh_token::find::FindWithAccount {
+                    id: session_id,
+                    account_id,
+                };
+                db.send(msg)
+                    .from_err()
+                    .and_then(|res| res.map_err(|_| authn::Error::NotFound))
+                    .and_then(move |(token, account)| {
+                        if account.disabled_at.is_some() {
+                            Err(authn::Error::Forbidden)
+                        } else {
+                            Ok((params, jwt, token, account))
+                        }
+                    })
             }
         })
-        .and_then(move |(expires_in, jwt)| {
-            let key = path.into_inner();
-            let account_id: Uuid = if key == "me" {
-                let data = jsonwebtoken::dangerous_unsafe_decode::<jwt::RefreshToken>(&jwt)
-                    .map_err(|_| authn::Error::Unauthorized)?;
-                data.claims.sub
-            } else {
-                Uuid::parse_str(&key).map_err(|_| authn::Error::NotFound)?
-            };
+        .and_then(|(params, jwt, refresh_token, account)| {
+            if refresh_token.keys.is_empty() {
+                return Err(authn::Error::InternalError);
+            }
+
+            let token = jwt::RefreshToken::decode(&jwt, &refresh_token.keys)
+                .map_err(|_| authn::Error::Unauthorized)?;
+            if account.is_token_revoked(token.iat) {
+                return Err(authn::Error::Unauthorized);
+            }
 
-            Ok((expires_in, jwt, account_id))
+            // The scope granted to the session may only be narrowed.
+            let (expires_in, scope) = params;
+            let scope = jwt::narrow_scope(refresh_token.scope.as_ref().map(String::as_str), scope)
+                .map_err(|_| authn::Error::InvalidScope)?;
+            Ok(((expires_in, scope), token, refresh_token.id))
         })
-        .and_then(|(expires_in, jwt, account_id)| {
+        .and_then({
             let db = meta.db.unwrap();
-
-            let msg = db::refresh_token::find::FindWithAccount(account_id);
-            db.send(msg)
-                .from_err()
-                .and_then(|res| res.map_err(|_| authn::Error::NotFound))
-                .and_then(move |(token, account)| {
-                    if account.disabled_at.is_some() {
-                        Err(authn::Error::Forbidden)
-                    } else {
-                        Ok((expires_in, jwt, token))
+            move |(params, old_token, session_id)| {
+                let msg = db::refresh_token::rotate::Rotate {
+                    id: session_id,
+                    jti: old_token.jti,
+                };
+                db.send(msg).from_err().and_then(move |res| match res {
+                    Ok(Some(new_token)) => Either::A(future::ok((params, old_token, new_token))),
+                    Ok(None) => {
+                        let f = revoke::revoke_family(&db, session_id).and_then(|session| {
+                            revoke::report_reuse(&session);
+                            Err(authn::Error::Unauthorized)
+                        });
+                        Either::B(f)
                     }
+                    Err(e) => Either::A(future::err(e.into())),
                 })
+            }
         })
-        .and_then(|(expires_in, jwt, refresh_token)| {
-            let key = refresh_token
+        .and_then(|((expires_in, scope), old_token, new_token)| {
+            let mut payload = jwt::AccessToken::new(
+                old_token.aud.clone(),
+                u32::from(expires_in),
+                old_token.sub,
+            );
+            payload.scope = scope;
+            let access_token = jwt::AccessToken::encode(payload)?;
+
+            let payload =
+                jwt::RefreshToken::new(old_token.aud, old_token.sub, new_token.id, new_token.jti);
+            let key = new_token
                 .keys
                 .get(0)
                 .ok_or_else(|| authn::Error::InternalError)?;
+            let refresh_token = jwt::RefreshToken::encode(&payload, key)?;
 
-            let token =
-                jwt::RefreshToken::decode(&jwt, key).map_err(|_| authn::Error::Unauthorized)?;
-            Ok((expires_in, token))
-        })
-        .and_then(|(expires_in, refresh_token)| {
-            let payload =
-                jwt::AccessToken::new(refresh_token.aud, u32::from(expires_in), refresh_token.sub);
-            let access_token = jwt::AccessToken::encode(payload)?;
-
-            Ok(HttpResponse::Ok().json(Response::new(&access_token, expires_in)))
+            Ok(HttpResponse::Ok().json(Response::new(&access_token, &refresh_token, expires_in)))
         })
 }
@@ -2,15 +2,57 @@ use actix_web::{self, HttpMessage, HttpRequest, HttpResponse, Path};
 use futures::future::{self, Either, Future};
 
 use actors::db;
-use authn::{self, jwt, AuthKey};
+use authn::{self, exchange, jwt, AuthKey};
+use rpc;
 use AppState;
 
+const CLIENT_CREDENTIALS: &str = "client_credentials";
+
+/// `client_token` is required by the client credentials grant, the rest of
+/// the optional parameters by the token exchange one.
 #[derive(Debug, Deserialize, PartialEq)]
 struct Payload {
     pub grant_type: String,
-    pub client_token: String,
+    pub client_token: Option<String>,
+    pub subject_token: Option<String>,
+    pub subject_token_type: Option<String>,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    pub audience: Option<String>,
     #[serde(default = "jwt::AccessToken::default_expires_in")]
     pub expires_in: u16,
+    pub scope: Option<String>,
+}
+
+impl Payload {
+    fn into_client_credentials(self) -> Result<(String, u16, Option<String>), authn::Error> {
+        match self.client_token {
+            Some(client_token) if self.grant_type == CLIENT_CREDENTIALS => {
+                Ok((client_token, self.expires_in, self.scope))
+            }
+            _ => Err(authn::Error::InvalidRequest),
+        }
+    }
+
+    fn into_exchange_request(self) -> Result<exchange::Request, authn::Error> {
+        let token_type = Some(exchange::ACCESS_TOKEN_TYPE);
+        if self.subject_token_type.as_ref().map(String::as_str) != token_type
+            || self.actor_token_type.as_ref().map(String::as_str) != token_type
+        {
+            return Err(authn::Error::InvalidRequest);
+        }
+
+        match (self.subject_token, self.actor_token, self.audience) {
+            (Some(subject_token), Some(actor_token), Some(audience)) => Ok(exchange::Request {
+                subject_token,
+                actor_token,
+                audience,
+                expires_in: self.expires_in,
+                scope: self.scope,
+            }),
+            _ => Err(authn::Error::InvalidRequest),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -65,34 +107,58 @@ pub fn call(
                 if let Ok(payload) = res {
                     let settings = get_settings!();
 
-                    if payload.grant_type == "client_credentials"
-                        && payload.expires_in <= settings.tokens.expires_in_max
-                    {
-                        return Ok(payload);
+                    if payload.expires_in <= settings.tokens.expires_in_max {
+                        return match payload.scope {
+                            Some(ref scope) if !jwt::validate_scope(scope) => {
+                                Err(authn::Error::InvalidScope)
+                            }
+                            _ => Ok(payload),
+                        };
                     }
                 }
 
                 Err(authn::Error::InvalidRequest)
             })
         })
-        .and_then(|payload| {
-            let auth_key = path.into_inner();
-
-            let client_token = {
-                let raw_token = jwt::RawToken {
-                    kind: jwt::RawTokenKind::Client(&auth_key),
-                    value: &payload.client_token,
-                };
-                jwt::AccessToken::decode(&raw_token)?
-            };
-
-            let validator = jwt::Validator::default();
-            if validator.call(&client_token) {
-                Ok((payload.expires_in, client_token.sub, auth_key))
+        .and_then(move |payload| {
+            if payload.grant_type == exchange::GRANT_TYPE {
+                let f = future::result(payload.into_exchange_request())
+                    .and_then(move |req| exchange::call(meta, req));
+                Either::A(f)
             } else {
-                Err(authn::Error::InvalidClient)
+                let f = future::result(payload.into_client_credentials()).and_then(
+                    move |(client_token, expires_in, scope)| {
+                        client_credentials(meta, path.into_inner(), client_token, expires_in, scope)
+                    },
+                );
+                Either::B(f)
             }
         })
+}
+
+fn client_credentials(
+    meta: rpc::Meta,
+    auth_key: AuthKey,
+    client_token: String,
+    expires_in: u16,
+    scope: Option<String>,
+) -> impl Future<Item = HttpResponse, Error = authn::Error> {
+    let client_token = {
+        let raw_token = jwt::RawToken {
+            kind: jwt::RawTokenKind::Client(&auth_key),
+            value: &client_token,
+        };
+        jwt::AccessToken::decode(&raw_token).map_err(authn::Error::from)
+    };
+
+    future::result(client_token)
+        .and_then(move |client_token| {
+            let validator =
+                jwt::Validator::for_provider(&auth_key).ok_or(authn::Error::InvalidClient)?;
+            validator.call(&client_token)?;
+
+            Ok((expires_in, client_token.sub, auth_key))
+        })
         .and_then({
             let db = meta.db.clone().unwrap();
             move |(expires_in, sub, auth_key)| {
@@ -105,7 +171,7 @@ pub fn call(
             }
         })
         .and_then({
-            let db = meta.db.unwrap();
+            let db = meta.db.clone().unwrap();
             move |(expires_in, sub, label, namespace)| {
                 use models::identity::PrimaryKey;
 
@@ -116,25 +182,49 @@ pub fn call(
                 };
                 let msg = db::identity::upsert::Upsert(pk);
                 db.send(msg).from_err().and_then(|res| Ok(res?)).and_then(
-                    move |(identity, account, refresh_token)| {
+                    move |(identity, account)| {
                         if account.disabled_at.is_some() {
                             Err(authn::Error::Forbidden)
                         } else {
-                            Ok((expires_in, identity, refresh_token, namespace))
+                            Ok((expires_in, identity, namespace))
                         }
                     },
                 )
             }
         })
-        .and_then(|(expires_in, identity, refresh_token, namespace)| {
-            let payload = jwt::AccessToken::new(
-                namespace.label.clone(),
+        .and_then({
+            let db = meta.db.unwrap();
+            move |(expires_in, identity, namespace)| {
+                use models::NewRefreshToken;
+
+                // Every authentication starts a new session, the scope is granted to it.
+                let changeset = NewRefreshToken::try_new(identity.account_id, namespace.label)
+                    .map(|changeset| NewRefreshToken { scope, ..changeset })
+                    .map_err(|_| authn::Error::InternalError);
+
+                future::result(changeset).and_then(move |changeset| {
+                    let msg = db::refresh_token::insert::Insert(changeset);
+                    db.send(msg)
+                        .from_err()
+                        .and_then(move |res| Ok((expires_in, res?)))
+                })
+            }
+        })
+        .and_then(|(expires_in, refresh_token)| {
+            let mut payload = jwt::AccessToken::new(
+                refresh_token.aud.clone(),
                 u32::from(expires_in),
-                identity.account_id,
+                refresh_token.account_id,
             );
+            payload.scope = refresh_token.scope.clone();
             let access_token = jwt::AccessToken::encode(payload)?;
 
-            let payload = jwt::RefreshToken::new(namespace.label, identity.account_id);
+            let payload = jwt::RefreshToken::new(
+                refresh_token.aud,
+                refresh_token.account_id,
+                refresh_token.id,
+                refresh_token.jti,
+            );
             let key = refresh_token
                 .keys
                 .get(0)
@@ -162,8 +252,14 @@ mod tests {
         let payload = serde_json::from_str::<Payload>(s).unwrap();
         let expected = Payload {
             grant_type: "foo".to_owned(),
-            client_token: "bar".to_owned(),
+            client_token: Some("bar".to_owned()),
+            subject_token: None,
+            subject_token_type: None,
+            actor_token: None,
+            actor_token_type: None,
+            audience: None,
             expires_in: 10,
+            scope: None,
         };
         assert_eq!(payload, expected);
 
@@ -174,8 +270,14 @@ mod tests {
         let payload = serde_json::from_str::<Payload>(s).unwrap();
         let expected = Payload {
             grant_type: "foo".to_owned(),
-            client_token: "bar".to_owned(),
+            client_token: Some("bar".to_owned()),
+            subject_token: None,
+            subject_token_type: None,
+            actor_token: None,
+            actor_token_type: None,
+            audience: None,
             expires_in: 300,
+            scope: None,
         };
         assert_eq!(payload, expected);
     }
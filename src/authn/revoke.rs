@@ -1,10 +1,12 @@
+use actix::Addr;
 use actix_web::{HttpMessage, HttpRequest, HttpResponse, Path};
 use futures::future::{self, Future};
 use jsonwebtoken;
 use uuid::Uuid;
 
-use actors::db;
+use actors::{db, DbExecutor};
 use authn::{self, jwt};
+use models::{RefreshToken, RenewedRefreshToken};
 use AppState;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,37 +23,28 @@ impl<'a> Response<'a> {
 pub fn call(
     (req, path): (HttpRequest<AppState>, Path<String>),
 ) -> impl Future<Item = HttpResponse, Error = authn::Error> {
-    use extract_authorization_header;
+    use {extract_authorization_header, Authorization};
 
     let meta = req.state().rpc_meta.clone();
 
-    let auth_header = if let Ok(header) = extract_authorization_header(req.headers()) {
-        if let Some(v) = header {
-            Ok(v.to_owned())
-        } else {
-            Err(authn::Error::Forbidden)
-        }
-    } else {
-        Err(authn::Error::Unauthorized)
+    let auth_header = match extract_authorization_header(req.headers()) {
+        Ok(Some(Authorization::Bearer(v))) => Ok(v.to_owned()),
+        Ok(None) => Err(authn::Error::Forbidden),
+        _ => Err(authn::Error::Unauthorized),
     };
 
     future::result(auth_header)
         .and_then(move |jwt| {
-            let key = path.into_inner();
-            let account_id: Uuid = if key == "me" {
-                let data = jsonwebtoken::dangerous_unsafe_decode::<jwt::RefreshToken>(&jwt)
-                    .map_err(|_| authn::Error::Unauthorized)?;
-                data.claims.sub
-            } else {
-                Uuid::parse_str(&key).map_err(|_| authn::Error::NotFound)?
-            };
-
-            Ok((jwt, account_id))
+            let (account_id, session_id) = parse_session(&path.into_inner(), &jwt)?;
+            Ok((jwt, account_id, session_id))
         })
         .and_then({
             let db = meta.db.clone().unwrap();
-            move |(jwt, account_id)| {
-                let msg = db::refresh_token::find::FindWithAccount(account_id);
+            move |(jwt, account_id, session_id)| {
+                let msg = db::refresh_token::find::FindWithAccount {
+                    id: session_id,
+                    account_id,
+                };
                 db.send(msg)
                     .from_err()
                     .and_then(|res| res.map_err(|_| authn::Error::NotFound))
@@ -65,32 +58,32 @@ pub fn call(
             }
         })
         .and_then(|(jwt, refresh_token)| {
-            let key = refresh_token
-                .keys
-                .get(0)
-                .ok_or_else(|| authn::Error::InternalError)?;
+            if refresh_token.keys.is_empty() {
+                return Err(authn::Error::InternalError);
+            }
+
+            let old_token = jwt::RefreshToken::decode(&jwt, &refresh_token.keys)
+                .map_err(|_| authn::Error::Unauthorized)?;
+            let reused = !refresh_token.is_current(old_token.jti);
 
-            jwt::RefreshToken::decode(&jwt, key).map_err(|_| authn::Error::Unauthorized)
+            Ok((old_token, refresh_token.id, reused))
         })
         .and_then({
             let db = meta.db.unwrap();
-            |old_token| {
-                use models::NewRefreshToken;
-
-                let changeset = NewRefreshToken::try_new(old_token.sub)
-                    .map_err(|_| authn::Error::InternalError);
-
-                future::result(changeset).and_then(move |changeset| {
-                    let msg = db::refresh_token::update::Update(changeset);
-                    db.send(msg).from_err().and_then(|res| {
-                        let new_token = res.map_err(|_| authn::Error::InternalError)?;
+            move |(old_token, session_id, reused)| {
+                revoke_family(&db, session_id).and_then(move |new_token| {
+                    if reused {
+                        report_reuse(&new_token);
+                        Err(authn::Error::Unauthorized)
+                    } else {
                         Ok((old_token, new_token))
-                    })
+                    }
                 })
             }
         })
         .and_then(|(old_token, new_token)| {
-            let payload = jwt::RefreshToken::new(old_token.aud, old_token.sub);
+            let payload =
+                jwt::RefreshToken::new(old_token.aud, old_token.sub, new_token.id, new_token.jti);
 
             let key = new_token
                 .keys
@@ -102,3 +95,53 @@ pub fn call(
             Ok(HttpResponse::Ok().json(Response::new(&refresh_token)))
         })
 }
+
+/// Extracts identifiers of the account and the session the refresh token was issued for,
+/// the session isn't known for tokens issued before sessions were introduced.
+///
+/// The token is verified later on, using the key of the session.
+pub fn parse_session(key: &str, jwt: &str) -> Result<(Uuid, Option<Uuid>), authn::Error> {
+    let claims = jsonwebtoken::dangerous_unsafe_decode::<jwt::RefreshToken>(jwt)
+        .map(|data| data.claims)
+        .ok();
+
+    let account_id = if key == "me" {
+        claims
+            .as_ref()
+            .map(|claims| claims.sub)
+            .ok_or_else(|| authn::Error::Unauthorized)?
+    } else {
+        Uuid::parse_str(key).map_err(|_| authn::Error::NotFound)?
+    };
+
+    let session_id = claims
+        .map(|claims| claims.sid)
+        .ok_or_else(|| authn::Error::Unauthorized)?;
+
+    Ok((account_id, session_id))
+}
+
+/// Invalidates every refresh token of the session by replacing its key.
+pub fn revoke_family(
+    db: &Addr<DbExecutor>,
+    session_id: Uuid,
+) -> impl Future<Item = RefreshToken, Error = authn::Error> {
+    let changeset =
+        RenewedRefreshToken::try_new(session_id).map_err(|_| authn::Error::InternalError);
+
+    let db = db.clone();
+    future::result(changeset).and_then(move |changeset| {
+        let msg = db::refresh_token::update::Update(changeset);
+        db.send(msg)
+            .from_err()
+            .and_then(|res| res.map_err(|_| authn::Error::InternalError))
+    })
+}
+
+/// A refresh token being used after it was replaced means that it was most likely leaked.
+pub fn report_reuse(session: &RefreshToken) {
+    warn!(
+        "Refresh token reuse detected, revoked all refresh tokens of session = {}, account = {}",
+        session.id, session.account_id
+    );
+}
@@ -0,0 +1,116 @@
+use actix_web::{self, HttpRequest, HttpResponse};
+use diesel;
+use futures::future::{self, Either, Future};
+use uuid::Uuid;
+
+use actors::db;
+use authn::{self, jwt};
+use AppState;
+
+#[derive(Debug, Deserialize)]
+struct Payload {
+    pub grant_type: String,
+    pub client_id: Uuid,
+    pub client_secret: String,
+    pub audience: String,
+    #[serde(default = "jwt::AccessToken::default_expires_in")]
+    pub expires_in: u16,
+}
+
+/// No refresh token is issued, service accounts just obtain a new access token.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response<'a> {
+    pub access_token: &'a str,
+    pub expires_in: u16,
+    pub token_type: &'a str,
+}
+
+impl<'a> Response<'a> {
+    pub fn new(access_token: &'a str, expires_in: u16) -> Self {
+        use TOKEN_TYPE;
+        Response {
+            token_type: TOKEN_TYPE,
+            access_token,
+            expires_in,
+        }
+    }
+}
+
+pub fn call(
+    req: HttpRequest<AppState>,
+) -> impl Future<Item = HttpResponse, Error = authn::Error> {
+    use actix_web::FromRequest;
+
+    let meta = req.state().rpc_meta.clone();
+    let content_type = req
+        .headers()
+        .get("Content-Type")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_owned())
+        .ok_or(authn::Error::InvalidRequest);
+
+    future::result(content_type)
+        .and_then(move |content_type| {
+            let f = match content_type.as_ref() {
+                "application/x-www-form-urlencoded" => {
+                    let f = actix_web::Form::<Payload>::extract(&req).map(|v| v.into_inner());
+                    Either::A(f)
+                }
+                "application/json" => {
+                    let f = actix_web::Json::<Payload>::extract(&req).map(|v| v.into_inner());
+                    Either::B(f)
+                }
+                _ => return Either::B(future::err(authn::Error::InvalidRequest)),
+            };
+
+            let f = f.then(|res| {
+                if let Ok(payload) = res {
+                    let settings = get_settings!();
+
+                    if payload.grant_type == "client_credentials"
+                        && payload.expires_in <= settings.tokens.expires_in_max
+                    {
+                        return Ok(payload);
+                    }
+                }
+
+                Err(authn::Error::InvalidRequest)
+            });
+            Either::A(f)
+        })
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |payload| {
+                let msg = db::client_credential::find::FindWithAccount(payload.client_id);
+                db.send(msg).from_err().and_then(move |res| match res {
+                    Ok((credential, account)) => {
+                        if !credential.verify(&payload.client_secret) {
+                            Err(authn::Error::InvalidClient)
+                        } else if account.disabled_at.is_some() || account.deleted_at.is_some() {
+                            Err(authn::Error::Forbidden)
+                        } else {
+                            Ok((payload, account.id))
+                        }
+                    }
+                    Err(diesel::result::Error::NotFound) => Err(authn::Error::InvalidClient),
+                    Err(e) => Err(e.into()),
+                })
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |(payload, account_id)| {
+                let msg = db::namespace::find::Find::ByLabel(payload.audience);
+                db.send(msg).from_err().and_then(move |res| {
+                    let namespace = res.map_err(|_| authn::Error::InvalidTarget)?;
+                    Ok((namespace.label, payload.expires_in, account_id))
+                })
+            }
+        })
+        .and_then(|(aud, expires_in, account_id)| {
+            let payload = jwt::AccessToken::new(aud, u32::from(expires_in), account_id);
+            let access_token = jwt::AccessToken::encode(payload)?;
+
+            Ok(HttpResponse::Ok().json(Response::new(&access_token, expires_in)))
+        })
+}
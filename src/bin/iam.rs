@@ -2,6 +2,7 @@ extern crate actix;
 extern crate actix_web;
 extern crate diesel;
 extern crate env_logger;
+#[macro_use]
 extern crate iam;
 extern crate migrations_internals;
 #[macro_use]
@@ -12,6 +13,10 @@ use actix_web::server;
 use diesel::{r2d2, PgConnection};
 
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use iam::actors::{db::DecisionCache, DbExecutor, JwksFetcher, Sweeper};
 
 fn main() {
     env_logger::init();
@@ -38,7 +43,28 @@ fn main() {
 
     let sys = System::new("iam");
 
-    let app = move || iam::build_app(pool.clone());
+    // The cache is shared by all workers, so that a change made through one of them
+    // invalidates decisions cached by the others.
+    let cache = Arc::new(DecisionCache::from_settings());
+
+    let sweeper_interval = {
+        let settings = get_settings!();
+        Duration::from_secs(settings.sweeper.interval)
+    };
+    let sweeper_db = {
+        let pool = pool.clone();
+        let cache = cache.clone();
+        SyncArbiter::start(1, move || DbExecutor(pool.clone(), cache.clone()))
+    };
+    Sweeper::new(sweeper_db, sweeper_interval).start();
+
+    let jwks_refresh_interval = {
+        let settings = get_settings!();
+        Duration::from_secs(settings.jwks.refresh_interval)
+    };
+    JwksFetcher::new(jwks_refresh_interval).start();
+
+    let app = move || iam::build_app(pool.clone(), cache.clone());
     server::new(app).bind("0.0.0.0:8080").unwrap().start();
 
     let _ = sys.run();
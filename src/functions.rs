@@ -0,0 +1,9 @@
+use diesel::sql_types::{Bool, Nullable, Timestamptz};
+
+sql_function!(fn coalesce(x: Nullable<Timestamptz>, y: Timestamptz) -> Timestamptz);
+
+sql_function!(fn greatest(x: Timestamptz, y: Timestamptz) -> Timestamptz);
+
+sql_function!(
+    fn abac_is_active(not_before: Nullable<Timestamptz>, expires_at: Nullable<Timestamptz>) -> Bool
+);
@@ -3,6 +3,7 @@
 extern crate abac;
 extern crate actix;
 extern crate actix_web;
+extern crate base64;
 extern crate chrono;
 extern crate config;
 #[macro_use]
@@ -26,6 +27,7 @@ extern crate serde;
 extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
+extern crate untrusted;
 extern crate uuid;
 
 #[cfg(test)]
@@ -36,7 +38,9 @@ use actix::prelude::*;
 use actix_web::{http, App, HttpResponse};
 use diesel::{r2d2, PgConnection};
 
-use actors::DbExecutor;
+use std::sync::Arc;
+
+use actors::{db::DecisionCache, DbExecutor};
 use rpc::{Meta, Server};
 
 #[macro_use]
@@ -45,6 +49,7 @@ pub mod settings;
 pub mod abac_attribute;
 pub mod actors;
 pub mod authn;
+pub mod functions;
 pub mod models;
 pub mod rpc;
 pub mod schema;
@@ -54,6 +59,7 @@ lazy_static! {
 }
 
 const TOKEN_TYPE: &str = "Bearer";
+const API_KEY_TYPE: &str = "ApiKey";
 
 pub type DbPool = r2d2::Pool<r2d2::ConnectionManager<PgConnection>>;
 
@@ -63,8 +69,8 @@ pub struct AppState {
     pub rpc_meta: Meta,
 }
 
-pub fn build_app(pool: DbPool) -> App<AppState> {
-    App::with_state(build_app_state(pool))
+pub fn build_app(pool: DbPool, cache: Arc<DecisionCache>) -> App<AppState> {
+    App::with_state(build_app_state(pool, cache))
         .middleware(actix_web::middleware::Logger::default())
         .resource("/", |r| r.method(http::Method::POST).with_async(rpc::index))
         .resource("/auth/{auth_key}/token", |r| {
@@ -89,29 +95,60 @@ pub fn build_app(pool: DbPool) -> App<AppState> {
         .resource("/accounts/{key}/revoke", |r| {
             r.method(http::Method::POST).with_async(authn::revoke::call)
         })
+        .resource("/service_accounts/token", |r| {
+            use actix_web::pred;
+
+            r.route()
+                .filter(pred::Not(
+                    pred::Any(pred::Header(
+                        "Content-Type",
+                        "application/x-www-form-urlencoded",
+                    )).or(pred::Header("Content-Type", "application/json")),
+                ))
+                .f(|_| HttpResponse::NotAcceptable());
+
+            r.method(http::Method::POST)
+                .with_async(authn::service_account::call)
+        })
+        .resource("/introspect", |r| {
+            r.method(http::Method::POST).with_async(authn::introspect::call)
+        })
+        .resource("/.well-known/jwks.json", |r| {
+            r.method(http::Method::GET).f(authn::jwks::call)
+        })
 }
 
-pub fn build_app_state(pool: DbPool) -> AppState {
-    let addr = SyncArbiter::start(num_cpus::get(), move || DbExecutor(pool.clone()));
+pub fn build_app_state(pool: DbPool, cache: Arc<DecisionCache>) -> AppState {
+    let addr = SyncArbiter::start(num_cpus::get(), move || DbExecutor(pool.clone(), cache.clone()));
 
     AppState {
         rpc_server: rpc::build_server(),
         rpc_meta: Meta {
             db: Some(addr.clone()),
             subject: None,
+            allowed_namespace_ids: None,
+            scopes: None,
         },
     }
 }
 
+/// Credentials of the `Authorization` header, either an access token or a personal API key.
+#[derive(Debug, PartialEq)]
+pub enum Authorization<'a> {
+    Bearer(&'a str),
+    ApiKey(&'a str),
+}
+
 pub fn extract_authorization_header(
     headers: &actix_web::http::HeaderMap,
-) -> Result<Option<&str>, ()> {
+) -> Result<Option<Authorization>, ()> {
     let auth_header = headers.get("Authorization").map(|v| v.to_str());
     match auth_header {
         Some(Ok(header)) => {
             let mut kv = header.splitn(2, ' ');
             match (kv.next(), kv.next()) {
-                (Some(TOKEN_TYPE), Some(v)) => Ok(Some(v)),
+                (Some(TOKEN_TYPE), Some(v)) => Ok(Some(Authorization::Bearer(v))),
+                (Some(API_KEY_TYPE), Some(v)) => Ok(Some(Authorization::ApiKey(v))),
                 _ => {
                     error!("Bad auth header: {}", header);
                     Err(())
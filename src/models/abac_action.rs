@@ -0,0 +1,22 @@
+use abac::AbacAttribute;
+use chrono::{DateTime, Utc};
+
+use schema::abac_action;
+
+#[derive(Queryable, Debug)]
+pub struct AbacAction {
+    pub inbound: AbacAttribute,
+    pub outbound: AbacAttribute,
+    pub created_at: DateTime<Utc>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "abac_action"]
+pub struct NewAbacAction {
+    pub inbound: AbacAttribute,
+    pub outbound: AbacAttribute,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,22 @@
+use abac::AbacAttribute;
+use chrono::{DateTime, Utc};
+
+use schema::abac_object;
+
+#[derive(Queryable, Debug)]
+pub struct AbacObject {
+    pub inbound: AbacAttribute,
+    pub outbound: AbacAttribute,
+    pub created_at: DateTime<Utc>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "abac_object"]
+pub struct NewAbacObject {
+    pub inbound: AbacAttribute,
+    pub outbound: AbacAttribute,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,72 @@
+use abac::AbacAttribute;
+use chrono::{DateTime, Utc};
+use diesel::{
+    deserialize::{self, FromSql},
+    pg::Pg,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Text,
+};
+use uuid::Uuid;
+
+use std::io::Write;
+
+use schema::abac_policy;
+
+#[derive(Queryable, Debug)]
+pub struct AbacPolicy {
+    pub subject: Vec<AbacAttribute>,
+    pub object: Vec<AbacAttribute>,
+    pub action: Vec<AbacAttribute>,
+    pub namespace_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub effect: Effect,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "abac_policy"]
+pub struct NewAbacPolicy {
+    pub subject: Vec<AbacAttribute>,
+    pub object: Vec<AbacAttribute>,
+    pub action: Vec<AbacAttribute>,
+    pub namespace_id: Uuid,
+    pub effect: Effect,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(AsExpression, FromSqlRow, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+impl Default for Effect {
+    fn default() -> Self {
+        Effect::Allow
+    }
+}
+
+impl ToSql<Text, Pg> for Effect {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let v: &[u8] = match *self {
+            Effect::Allow => b"allow",
+            Effect::Deny => b"deny",
+        };
+        out.write_all(v)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Effect {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"allow" => Ok(Effect::Allow),
+            b"deny" => Ok(Effect::Deny),
+            _ => Err("Unrecognized policy effect".into()),
+        }
+    }
+}
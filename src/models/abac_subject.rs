@@ -0,0 +1,22 @@
+use abac::AbacAttribute;
+use chrono::{DateTime, Utc};
+
+use schema::abac_subject;
+
+#[derive(Queryable, Debug)]
+pub struct AbacSubject {
+    pub inbound: AbacAttribute,
+    pub outbound: AbacAttribute,
+    pub created_at: DateTime<Utc>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "abac_subject"]
+pub struct NewAbacSubject {
+    pub inbound: AbacAttribute,
+    pub outbound: AbacAttribute,
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
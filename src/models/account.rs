@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde_json::Value;
 use uuid::Uuid;
 
@@ -11,4 +11,13 @@ pub struct Account {
     pub constraints: Value,
     pub disabled_at: Option<DateTime<Utc>>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub tokens_revoked_before: Option<DateTime<Utc>>,
+}
+
+impl Account {
+    /// Whether the tokens of the account issued at `iat` have been revoked all at once.
+    pub fn is_token_revoked(&self, iat: NaiveDateTime) -> bool {
+        self.tokens_revoked_before
+            .map_or(false, |revoked_before| revoked_before.naive_utc() > iat)
+    }
 }
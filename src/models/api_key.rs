@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use models::{Account, Secret};
+use schema::api_key;
+
+/// A personal key of an account, presented as `{id}.{secret}`.
+/// Only a hash of the secret is kept.
+#[derive(Associations, Identifiable, Queryable, Debug)]
+#[belongs_to(Account)]
+#[table_name = "api_key"]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub label: String,
+    pub secret_hash: Vec<u8>,
+    pub namespace_ids: Option<Vec<Uuid>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    /// Splits a presented key into the identifier and the secret.
+    pub fn parse(value: &str) -> Option<(Uuid, &str)> {
+        let mut kv = value.splitn(2, '.');
+        match (kv.next(), kv.next()) {
+            (Some(id), Some(secret)) => Uuid::parse_str(id).ok().map(|id| (id, secret)),
+            _ => None,
+        }
+    }
+
+    pub fn verify(&self, secret: &str) -> bool {
+        Secret::verify(&self.secret_hash, secret)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= Utc::now())
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "api_key"]
+pub struct NewApiKey {
+    pub account_id: Uuid,
+    pub label: String,
+    pub secret_hash: Vec<u8>,
+    pub namespace_ids: Option<Vec<Uuid>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
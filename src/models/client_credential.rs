@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use models::{Account, Secret};
+use schema::client_credential;
+
+/// Credentials of a service account, `id` is the client identifier.
+/// Only a hash of the secret is kept.
+#[derive(Associations, Identifiable, Queryable, Debug)]
+#[belongs_to(Account)]
+#[table_name = "client_credential"]
+pub struct ClientCredential {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub secret_hash: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClientCredential {
+    pub fn verify(&self, secret: &str) -> bool {
+        Secret::verify(&self.secret_hash, secret)
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "client_credential"]
+pub struct NewClientCredential {
+    pub account_id: Uuid,
+    pub secret_hash: Vec<u8>,
+}
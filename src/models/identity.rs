@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use std::{fmt, str};
 
-use models::Namespace;
+use models::{Account, Namespace};
 use schema::identity;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -59,6 +59,7 @@ impl str::FromStr for PrimaryKey {
 }
 
 #[derive(Associations, Identifiable, Queryable, Clone, Debug, Deserialize)]
+#[belongs_to(Account)]
 #[belongs_to(Namespace, foreign_key = "provider")]
 #[primary_key(provider, label, uid)]
 #[table_name = "identity"]
@@ -1,13 +1,29 @@
+mod abac_action;
+mod abac_object;
+pub mod abac_policy;
+mod abac_subject;
 mod account;
+mod api_key;
+mod client_credential;
 pub mod identity;
 mod namespace;
 mod refresh_token;
+mod revoked_access_token;
+mod secret;
 
 pub mod prelude {
+    pub use models::abac_action::{AbacAction, NewAbacAction};
+    pub use models::abac_object::{AbacObject, NewAbacObject};
+    pub use models::abac_policy::{AbacPolicy, NewAbacPolicy};
+    pub use models::abac_subject::{AbacSubject, NewAbacSubject};
     pub use models::account::Account;
+    pub use models::api_key::{ApiKey, NewApiKey};
+    pub use models::client_credential::{ClientCredential, NewClientCredential};
     pub use models::identity::{Identity, NewIdentity};
     pub use models::namespace::{Namespace, NewNamespace};
-    pub use models::refresh_token::{NewRefreshToken, RefreshToken};
+    pub use models::refresh_token::{NewRefreshToken, RefreshToken, RenewedRefreshToken};
+    pub use models::revoked_access_token::{NewRevokedAccessToken, RevokedAccessToken};
+    pub use models::secret::Secret;
 }
 
 pub use self::prelude::*;
@@ -4,38 +4,86 @@ use uuid::Uuid;
 use models::Account;
 use schema::refresh_token;
 
-#[derive(Associations, Identifiable, Queryable, Debug)]
+/// A session of the account, every session is signed with its own key.
+#[derive(Associations, Identifiable, Queryable, QueryableByName, Debug)]
 #[belongs_to(Account)]
-#[primary_key(account_id)]
 #[table_name = "refresh_token"]
 pub struct RefreshToken {
     pub account_id: Uuid,
     pub algorithm: String,
     pub keys: Vec<Vec<u8>>,
     pub created_at: DateTime<Utc>,
+    pub jti: Uuid,
+    pub id: Uuid,
+    pub aud: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Scope granted to the session, access tokens refreshed within it may only narrow it.
+    pub scope: Option<String>,
 }
 
-#[derive(AsChangeset, Identifiable, Insertable, Debug)]
-#[primary_key(account_id)]
+impl RefreshToken {
+    /// Whether the token identified by `jti` is the current one of the session.
+    ///
+    /// A token without identifier is the first generation, that is current
+    /// until the session is rotated for the first time.
+    pub fn is_current(&self, jti: Option<Uuid>) -> bool {
+        match jti {
+            Some(jti) => self.jti == jti,
+            None => self.last_used_at.is_none(),
+        }
+    }
+}
+
+#[derive(Insertable, Debug)]
 #[table_name = "refresh_token"]
 pub struct NewRefreshToken {
     pub account_id: Uuid,
+    pub aud: String,
     pub algorithm: String,
     pub keys: Vec<Vec<u8>>,
+    pub jti: Uuid,
+    pub scope: Option<String>,
 }
 
 impl NewRefreshToken {
-    pub fn try_new(account_id: Uuid) -> Result<Self, ()> {
-        use ring::rand::SecureRandom;
-        use SYSTEM_RANDOM;
-
-        let mut buf = vec![0; 64];
-        SYSTEM_RANDOM.fill(&mut buf).map_err(|_| ())?;
-
+    pub fn try_new(account_id: Uuid, aud: String) -> Result<Self, ()> {
         Ok(NewRefreshToken {
             account_id,
+            aud,
+            algorithm: "HS256".to_owned(),
+            keys: vec![generate_key()?],
+            jti: Uuid::new_v4(),
+            scope: None,
+        })
+    }
+}
+
+/// Replaces the key of the session, which invalidates all of its refresh tokens.
+#[derive(AsChangeset, Identifiable, Debug)]
+#[table_name = "refresh_token"]
+pub struct RenewedRefreshToken {
+    pub id: Uuid,
+    pub algorithm: String,
+    pub keys: Vec<Vec<u8>>,
+    pub jti: Uuid,
+}
+
+impl RenewedRefreshToken {
+    pub fn try_new(id: Uuid) -> Result<Self, ()> {
+        Ok(RenewedRefreshToken {
+            id,
             algorithm: "HS256".to_owned(),
-            keys: vec![buf],
+            keys: vec![generate_key()?],
+            jti: Uuid::new_v4(),
         })
     }
 }
+
+fn generate_key() -> Result<Vec<u8>, ()> {
+    use ring::rand::SecureRandom;
+    use SYSTEM_RANDOM;
+
+    let mut buf = vec![0; 64];
+    SYSTEM_RANDOM.fill(&mut buf).map_err(|_| ())?;
+    Ok(buf)
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use models::Account;
+use schema::revoked_access_token;
+
+/// An access token rejected until it would expire anyway.
+#[derive(Associations, Identifiable, Queryable, Debug)]
+#[belongs_to(Account)]
+#[primary_key(jti, account_id)]
+#[table_name = "revoked_access_token"]
+pub struct RevokedAccessToken {
+    pub jti: Uuid,
+    pub account_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[table_name = "revoked_access_token"]
+pub struct NewRevokedAccessToken {
+    pub jti: Uuid,
+    pub account_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
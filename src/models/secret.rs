@@ -0,0 +1,46 @@
+use base64;
+use ring::{constant_time, digest};
+
+use std::fmt;
+
+const SECRET_LEN: usize = 32;
+
+/// A newly generated secret, it's handed out once and never stored. Only its hash is kept.
+///
+/// Unlike passwords, secrets are random enough not to be guessed, so a single SHA-256
+/// hash is kept instead of a key derivation that would cost too much on every request.
+pub struct Secret {
+    pub value: String,
+    pub hash: Vec<u8>,
+}
+
+impl Secret {
+    pub fn generate() -> Result<Self, ()> {
+        use ring::rand::SecureRandom;
+        use SYSTEM_RANDOM;
+
+        let mut secret = vec![0; SECRET_LEN];
+        SYSTEM_RANDOM.fill(&mut secret).map_err(|_| ())?;
+
+        let value = base64::encode_config(&secret, base64::URL_SAFE_NO_PAD);
+        let hash = Self::hash(&value);
+        Ok(Secret { value, hash })
+    }
+
+    pub fn hash(value: &str) -> Vec<u8> {
+        digest::digest(&digest::SHA256, value.as_bytes())
+            .as_ref()
+            .to_vec()
+    }
+
+    /// Compares the hash of `value` with the kept one in constant time.
+    pub fn verify(hash: &[u8], value: &str) -> bool {
+        constant_time::verify_slices_are_equal(hash, &Self::hash(value)).is_ok()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret {{ value: [redacted] }}")
+    }
+}
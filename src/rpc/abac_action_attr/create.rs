@@ -1,18 +1,23 @@
-use abac::{models::AbacAction, AbacAttribute};
+use abac::AbacAttribute;
 use futures::{future, Future};
 
-use rpc;
+use models::AbacAction;
+use rpc::{self, validity::Validity};
 
 #[derive(Debug, Deserialize)]
 pub struct Request {
     pub inbound: AbacAttribute,
     pub outbound: AbacAttribute,
+    #[serde(flatten)]
+    pub validity: Validity,
 }
 
 #[derive(Debug, Serialize)]
 pub struct Response {
     inbound: AbacAttribute,
     outbound: AbacAttribute,
+    #[serde(flatten)]
+    validity: Validity,
 }
 
 impl From<AbacAction> for Response {
@@ -20,25 +25,21 @@ impl From<AbacAction> for Response {
         Response {
             inbound: action.inbound,
             outbound: action.outbound,
+            validity: Validity {
+                not_before: action.not_before,
+                expires_at: action.expires_at,
+            },
         }
     }
 }
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    use abac_attribute::{CollectionKind, OperationKind};
     use actors::db::abac_action_attr;
-    use rpc::authorize_collection;
-
-    let collection = CollectionKind::AbacAction;
-    let operation = OperationKind::Create;
 
-    future::result(rpc::forbid_anonymous(meta.subject))
+    authorize(&meta, &req)
         .and_then({
-            let db = meta.db.clone().unwrap();
-            let inbound_ns_id = req.inbound.namespace_id;
-            move |subject_id| {
-                authorize_collection(&db, inbound_ns_id, subject_id, collection, operation)
-            }
+            let validity = req.validity;
+            move |_| rpc::validity::check(validity)
         })
         .and_then({
             let db = meta.db.unwrap();
@@ -51,3 +52,28 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
             }
         })
 }
+
+/// Checks the subject of the request is allowed to create action attribute links.
+pub fn authorize(meta: &rpc::Meta, req: &Request) -> impl Future<Item = (), Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
+    use rpc::authorize_collection;
+
+    let collection = CollectionKind::AbacAction;
+    let operation = OperationKind::Create;
+
+    future::result(rpc::forbid_anonymous(meta.subject)).and_then({
+        let db = meta.db.clone().unwrap();
+        let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+        let inbound_ns_id = req.inbound.namespace_id;
+        move |subject_id| {
+            authorize_collection(
+                &db,
+                &allowed_ns_ids,
+                inbound_ns_id,
+                subject_id,
+                collection,
+                operation,
+            )
+        }
+    })
+}
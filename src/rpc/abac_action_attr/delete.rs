@@ -6,29 +6,41 @@ pub type Request = rpc::abac_action_attr::create::Request;
 pub type Response = rpc::abac_action_attr::create::Response;
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    use abac_attribute::{CollectionKind, OperationKind};
     use actors::db::abac_action_attr;
+
+    authorize(&meta, &req).and_then({
+        let db = meta.db.unwrap();
+        move |_| {
+            let msg = abac_action_attr::delete::Delete::from(req);
+            db.send(msg).from_err().and_then(|res| {
+                debug!("abac action delete res: {:?}", res);
+                Ok(Response::from(res?))
+            })
+        }
+    })
+}
+
+/// Checks the subject of the request is allowed to delete action attribute links.
+pub fn authorize(meta: &rpc::Meta, req: &Request) -> impl Future<Item = (), Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
     use rpc::authorize_collection;
 
     let collection = CollectionKind::AbacAction;
     let operation = OperationKind::Delete;
 
-    future::result(rpc::forbid_anonymous(meta.subject))
-        .and_then({
-            let db = meta.db.clone().unwrap();
-            let inbound_ns_id = req.inbound.namespace_id;
-            move |subject_id| {
-                authorize_collection(&db, inbound_ns_id, subject_id, collection, operation)
-            }
-        })
-        .and_then({
-            let db = meta.db.unwrap();
-            move |_| {
-                let msg = abac_action_attr::delete::Delete::from(req);
-                db.send(msg).from_err().and_then(|res| {
-                    debug!("abac action delete res: {:?}", res);
-                    Ok(Response::from(res?))
-                })
-            }
-        })
+    future::result(rpc::forbid_anonymous(meta.subject)).and_then({
+        let db = meta.db.clone().unwrap();
+        let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+        let inbound_ns_id = req.inbound.namespace_id;
+        move |subject_id| {
+            authorize_collection(
+                &db,
+                &allowed_ns_ids,
+                inbound_ns_id,
+                subject_id,
+                collection,
+                operation,
+            )
+        }
+    })
 }
@@ -16,9 +16,17 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
     future::result(rpc::forbid_anonymous(meta.subject))
         .and_then({
             let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
             let inbound_ns_id = req.inbound.namespace_id;
             move |subject_id| {
-                authorize_collection(&db, inbound_ns_id, subject_id, collection, operation)
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    inbound_ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                )
             }
         })
         .and_then({
@@ -13,13 +13,21 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
     future::result(rpc::forbid_anonymous(meta.subject))
         .and_then({
             let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
             let ns_id = req.filter.attribute.namespace_id;
 
             move |subject_id| {
                 let collection = CollectionKind::AbacAction;
                 let operation = OperationKind::List;
 
-                authorize_collection(&db, ns_id, subject_id, collection, operation)
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                )
             }
         })
         .and_then({
@@ -34,6 +42,7 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
                     tree::Select {
                         direction: req.filter.direction,
                         attribute: req.filter.attribute,
+                        recursive: req.filter.recursive,
                         limit: req.pagination.limit,
                         offset: req.pagination.offset,
                     },
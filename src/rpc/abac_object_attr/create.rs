@@ -1,18 +1,23 @@
-use abac::{models::AbacObject, AbacAttribute};
+use abac::AbacAttribute;
 use futures::{future, Future};
 
-use rpc;
+use models::AbacObject;
+use rpc::{self, validity::Validity};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Request {
     pub inbound: AbacAttribute,
     pub outbound: AbacAttribute,
+    #[serde(flatten)]
+    pub validity: Validity,
 }
 
 #[derive(Debug, Serialize)]
 pub struct Response {
     inbound: AbacAttribute,
     outbound: AbacAttribute,
+    #[serde(flatten)]
+    validity: Validity,
 }
 
 impl From<AbacObject> for Response {
@@ -20,44 +25,21 @@ impl From<AbacObject> for Response {
         Response {
             inbound: object.inbound,
             outbound: object.outbound,
+            validity: Validity {
+                not_before: object.not_before,
+                expires_at: object.expires_at,
+            },
         }
     }
 }
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    use abac_attribute::{CollectionKind, OperationKind, UriKind};
-    use actors::db::{abac_object_attr, abac_object_target};
-    use rpc::authorize_collection;
-    use settings;
-
-    let Request { inbound, outbound } = req.clone();
-
-    let inbound_ns_id = inbound.namespace_id;
-    let outbound_ns_id = outbound.namespace_id;
+    use actors::db::abac_object_attr;
 
-    let collection = CollectionKind::AbacObject;
-    let operation = OperationKind::Create;
-
-    future::result(rpc::forbid_anonymous(meta.subject))
+    authorize(&meta, &req)
         .and_then({
-            let db = meta.db.clone().unwrap();
-            move |subject_id| {
-                authorize_collection(&db, inbound_ns_id, subject_id, collection, operation).or_else(
-                    move |_| {
-                        authorize_collection(&db, outbound_ns_id, subject_id, collection, operation)
-                            .and_then(move |_| {
-                                let iam_namespace_id = settings::iam_namespace_id();
-                                let outbound_ns_uri = AbacAttribute::new(
-                                    iam_namespace_id,
-                                    UriKind::Namespace(outbound_ns_id),
-                                );
-                                let msg =
-                                    abac_object_target::HasTarget(vec![inbound], outbound_ns_uri);
-                                db.send(msg).from_err().and_then(rpc::ensure_authorized)
-                            })
-                    },
-                )
-            }
+            let validity = req.validity;
+            move |_| rpc::validity::check(validity)
         })
         .and_then({
             let db = meta.db.unwrap();
@@ -70,3 +52,51 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
             }
         })
 }
+
+/// Checks the subject of the request is allowed to create object attribute links.
+pub fn authorize(meta: &rpc::Meta, req: &Request) -> impl Future<Item = (), Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind, UriKind};
+    use actors::db::abac_object_target;
+    use rpc::authorize_collection;
+    use settings;
+
+    let Request { inbound, outbound, .. } = req.clone();
+
+    let inbound_ns_id = inbound.namespace_id;
+    let outbound_ns_id = outbound.namespace_id;
+
+    let collection = CollectionKind::AbacObject;
+    let operation = OperationKind::Create;
+
+    future::result(rpc::forbid_anonymous(meta.subject)).and_then({
+        let db = meta.db.clone().unwrap();
+        let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+        move |subject_id| {
+            authorize_collection(
+                &db,
+                &allowed_ns_ids,
+                inbound_ns_id,
+                subject_id,
+                collection,
+                operation,
+            ).or_else(move |_| {
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    outbound_ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                ).and_then(move |_| {
+                    let iam_namespace_id = settings::iam_namespace_id();
+                    let outbound_ns_uri = AbacAttribute::new(
+                        iam_namespace_id,
+                        UriKind::Namespace(outbound_ns_id),
+                    );
+                    let msg = abac_object_target::HasTarget(vec![inbound], outbound_ns_uri);
+                    db.send(msg).from_err().and_then(rpc::ensure_authorized)
+                })
+            })
+        }
+    })
+}
@@ -13,6 +13,7 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
     future::result(rpc::forbid_anonymous(meta.subject))
         .and_then({
             let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
             let namespace_ids = req.filter.namespace_ids.clone();
 
             move |subject_id| {
@@ -20,7 +21,14 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
                 let operation = OperationKind::List;
 
                 let futures = namespace_ids.into_iter().map(move |ns_id| {
-                    authorize_collection(&db, ns_id, subject_id, collection, operation)
+                    authorize_collection(
+                        &db,
+                        &allowed_ns_ids,
+                        ns_id,
+                        subject_id,
+                        collection,
+                        operation,
+                    )
                 });
 
                 future::join_all(futures)
@@ -9,6 +9,9 @@ pub mod list;
 pub mod read;
 pub mod tree;
 
+const READ_SCOPE: &str = "abac_object_attr:read";
+const WRITE_SCOPE: &str = "abac_object_attr:write";
+
 build_rpc_trait! {
     pub trait Rpc {
         type Metadata;
@@ -37,22 +40,22 @@ impl Rpc for RpcImpl {
     type Metadata = rpc::Meta;
 
     fn create(&self, meta: rpc::Meta, req: create::Request) -> BoxFuture<create::Response> {
-        Box::new(create::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| create::call(meta, req)).from_err())
     }
 
     fn read(&self, meta: rpc::Meta, req: read::Request) -> BoxFuture<read::Response> {
-        Box::new(read::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| read::call(meta, req)).from_err())
     }
 
     fn delete(&self, meta: rpc::Meta, req: delete::Request) -> BoxFuture<delete::Response> {
-        Box::new(delete::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| delete::call(meta, req)).from_err())
     }
 
     fn list(&self, meta: rpc::Meta, req: list::Request) -> BoxFuture<list::Response> {
-        Box::new(list::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| list::call(meta, req)).from_err())
     }
 
     fn tree(&self, meta: rpc::Meta, req: tree::Request) -> BoxFuture<tree::Response> {
-        Box::new(tree::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| tree::call(meta, req)).from_err())
     }
 }
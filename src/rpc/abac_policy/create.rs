@@ -1,8 +1,9 @@
-use abac::{models::AbacPolicy, AbacAttribute};
+use abac::AbacAttribute;
 use futures::{future, Future};
 use uuid::Uuid;
 
-use rpc;
+use models::{abac_policy::Effect, AbacPolicy};
+use rpc::{self, validity::Validity};
 
 #[derive(Debug, Deserialize)]
 pub struct Request {
@@ -10,6 +11,10 @@ pub struct Request {
     pub subject: Vec<AbacAttribute>,
     pub object: Vec<AbacAttribute>,
     pub action: Vec<AbacAttribute>,
+    #[serde(default)]
+    pub effect: Effect,
+    #[serde(flatten)]
+    pub validity: Validity,
 }
 
 #[derive(Debug, Serialize)]
@@ -18,6 +23,9 @@ pub struct Response {
     pub subject: Vec<AbacAttribute>,
     pub object: Vec<AbacAttribute>,
     pub action: Vec<AbacAttribute>,
+    pub effect: Effect,
+    #[serde(flatten)]
+    pub validity: Validity,
 }
 
 impl From<AbacPolicy> for Response {
@@ -27,23 +35,22 @@ impl From<AbacPolicy> for Response {
             subject: policy.subject,
             object: policy.object,
             action: policy.action,
+            effect: policy.effect,
+            validity: Validity {
+                not_before: policy.not_before,
+                expires_at: policy.expires_at,
+            },
         }
     }
 }
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    use abac_attribute::{CollectionKind, OperationKind};
     use actors::db::abac_policy;
-    use rpc::authorize_collection;
 
-    let collection = CollectionKind::AbacPolicy;
-    let operation = OperationKind::Create;
-
-    future::result(rpc::forbid_anonymous(meta.subject))
+    authorize(&meta, &req)
         .and_then({
-            let db = meta.db.clone().unwrap();
-            let ns_id = req.namespace_id;
-            move |subject_id| authorize_collection(&db, ns_id, subject_id, collection, operation)
+            let validity = req.validity;
+            move |_| rpc::validity::check(validity)
         })
         .and_then({
             let db = meta.db.unwrap();
@@ -56,3 +63,28 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
             }
         })
 }
+
+/// Checks the subject of the request is allowed to create policies.
+pub fn authorize(meta: &rpc::Meta, req: &Request) -> impl Future<Item = (), Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
+    use rpc::authorize_collection;
+
+    let collection = CollectionKind::AbacPolicy;
+    let operation = OperationKind::Create;
+
+    future::result(rpc::forbid_anonymous(meta.subject)).and_then({
+        let db = meta.db.clone().unwrap();
+        let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+        let ns_id = req.namespace_id;
+        move |subject_id| {
+            authorize_collection(
+                &db,
+                &allowed_ns_ids,
+                ns_id,
+                subject_id,
+                collection,
+                operation,
+            )
+        }
+    })
+}
@@ -7,6 +7,10 @@ pub mod create;
 pub mod delete;
 pub mod list;
 pub mod read;
+pub mod simulate;
+
+const READ_SCOPE: &str = "abac_policy:read";
+const WRITE_SCOPE: &str = "abac_policy:write";
 
 build_rpc_trait! {
     pub trait Rpc {
@@ -23,6 +27,9 @@ build_rpc_trait! {
 
         #[rpc(meta, name = "abac_policy.list")]
         fn list(&self, Self::Metadata, list::Request) -> BoxFuture<list::Response>;
+
+        #[rpc(meta, name = "abac_policy.simulate")]
+        fn simulate(&self, Self::Metadata, simulate::Request) -> BoxFuture<simulate::Response>;
     }
 }
 
@@ -33,18 +40,22 @@ impl Rpc for RpcImpl {
     type Metadata = rpc::Meta;
 
     fn create(&self, meta: rpc::Meta, req: create::Request) -> BoxFuture<create::Response> {
-        Box::new(create::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| create::call(meta, req)).from_err())
     }
 
     fn read(&self, meta: rpc::Meta, req: read::Request) -> BoxFuture<read::Response> {
-        Box::new(read::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| read::call(meta, req)).from_err())
     }
 
     fn delete(&self, meta: rpc::Meta, req: delete::Request) -> BoxFuture<delete::Response> {
-        Box::new(delete::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| delete::call(meta, req)).from_err())
     }
 
     fn list(&self, meta: rpc::Meta, req: list::Request) -> BoxFuture<list::Response> {
-        Box::new(list::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| list::call(meta, req)).from_err())
+    }
+
+    fn simulate(&self, meta: rpc::Meta, req: simulate::Request) -> BoxFuture<simulate::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| simulate::call(meta, req)).from_err())
     }
 }
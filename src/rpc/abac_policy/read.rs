@@ -16,8 +16,18 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
     future::result(rpc::forbid_anonymous(meta.subject))
         .and_then({
             let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
             let ns_id = req.namespace_id;
-            move |subject_id| authorize_collection(&db, ns_id, subject_id, collection, operation)
+            move |subject_id| {
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                )
+            }
         })
         .and_then({
             let db = meta.db.unwrap();
@@ -0,0 +1,172 @@
+use futures::{future, Future};
+
+use std::collections::BTreeMap;
+
+use actors::db::{
+    authz::{Authz, AuthzBatch},
+    simulate,
+};
+use rpc::{self, validity::Validity};
+use settings;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub changes: Vec<Change>,
+    #[serde(flatten)]
+    pub batch: rpc::authz::batch::Request,
+}
+
+/// A proposed change, given the same way as a call of the method making it.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "method", content = "params")]
+pub enum Change {
+    #[serde(rename = "abac_policy.create")]
+    PolicyCreate(rpc::abac_policy::create::Request),
+    #[serde(rename = "abac_policy.delete")]
+    PolicyDelete(rpc::abac_policy::delete::Request),
+    #[serde(rename = "abac_subject_attr.create")]
+    SubjectCreate(rpc::abac_subject_attr::create::Request),
+    #[serde(rename = "abac_subject_attr.delete")]
+    SubjectDelete(rpc::abac_subject_attr::delete::Request),
+    #[serde(rename = "abac_object_attr.create")]
+    ObjectCreate(rpc::abac_object_attr::create::Request),
+    #[serde(rename = "abac_object_attr.delete")]
+    ObjectDelete(rpc::abac_object_attr::delete::Request),
+    #[serde(rename = "abac_action_attr.create")]
+    ActionCreate(rpc::abac_action_attr::create::Request),
+    #[serde(rename = "abac_action_attr.delete")]
+    ActionDelete(rpc::abac_action_attr::delete::Request),
+}
+
+impl Change {
+    /// Checks the subject is allowed to make the change, the same way the method making it does.
+    fn authorize(&self, meta: &rpc::Meta) -> Box<Future<Item = (), Error = rpc::Error>> {
+        use self::Change::*;
+
+        match self {
+            PolicyCreate(req) => Box::new(rpc::abac_policy::create::authorize(meta, req)),
+            PolicyDelete(req) => Box::new(rpc::abac_policy::delete::authorize(meta, req)),
+            SubjectCreate(req) => Box::new(rpc::abac_subject_attr::create::authorize(meta, req)),
+            SubjectDelete(req) => Box::new(rpc::abac_subject_attr::delete::authorize(meta, req)),
+            ObjectCreate(req) => Box::new(rpc::abac_object_attr::create::authorize(meta, req)),
+            ObjectDelete(req) => Box::new(rpc::abac_object_attr::delete::authorize(meta, req)),
+            ActionCreate(req) => Box::new(rpc::abac_action_attr::create::authorize(meta, req)),
+            ActionDelete(req) => Box::new(rpc::abac_action_attr::delete::authorize(meta, req)),
+        }
+    }
+
+    fn validity(&self) -> Validity {
+        use self::Change::*;
+
+        match self {
+            PolicyCreate(req) => req.validity,
+            SubjectCreate(req) => req.validity,
+            ObjectCreate(req) => req.validity,
+            ActionCreate(req) => req.validity,
+            _ => Validity::default(),
+        }
+    }
+}
+
+impl From<Change> for simulate::Change {
+    fn from(change: Change) -> Self {
+        use self::Change::*;
+        use actors::db::simulate::Change::*;
+
+        match change {
+            PolicyCreate(req) => InsertPolicy(From::from(req)),
+            PolicyDelete(req) => DeletePolicy(From::from(req)),
+            SubjectCreate(req) => InsertSubject(From::from(req)),
+            SubjectDelete(req) => DeleteSubject(From::from(req)),
+            ObjectCreate(req) => InsertObject(From::from(req)),
+            ObjectDelete(req) => DeleteObject(From::from(req)),
+            ActionCreate(req) => InsertAction(From::from(req)),
+            ActionDelete(req) => DeleteAction(From::from(req)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response(BTreeMap<String, Outcome>);
+
+#[derive(Debug, Serialize)]
+pub struct Outcome {
+    before: bool,
+    after: bool,
+}
+
+impl From<Vec<simulate::Outcome>> for Response {
+    fn from(outcomes: Vec<simulate::Outcome>) -> Self {
+        let outcomes = outcomes
+            .into_iter()
+            .map(|o| {
+                let outcome = Outcome {
+                    before: o.before,
+                    after: o.after,
+                };
+                (o.id, outcome)
+            })
+            .collect();
+
+        Response(outcomes)
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    let authorized = req
+        .changes
+        .iter()
+        .map(|change| change.authorize(&meta))
+        .collect::<Vec<_>>();
+
+    future::result(rpc::forbid_anonymous(meta.subject))
+        .and_then(move |_| future::join_all(authorized))
+        .and_then({
+            let res = check_simulation(&req);
+            move |_| res
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let iam_namespace_id = settings::iam_namespace_id();
+                let checks = req
+                    .batch
+                    .checks
+                    .into_iter()
+                    .map(|check| {
+                        let mut msg = Authz::from(check.request);
+                        msg.namespace_ids.push(iam_namespace_id);
+                        msg.namespace_ids.dedup();
+
+                        (check.id, msg)
+                    })
+                    .collect();
+
+                let msg = simulate::Simulate {
+                    changes: req.changes.into_iter().map(From::from).collect(),
+                    checks: AuthzBatch(checks),
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("abac policy simulate res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
+
+fn check_simulation(req: &Request) -> rpc::Result<()> {
+    let limit_max = {
+        let settings = get_settings!();
+        usize::from(settings.pagination.limit_max)
+    };
+
+    if req.changes.len() > limit_max {
+        return Err(rpc::Error::BadRequest);
+    }
+
+    for change in &req.changes {
+        rpc::validity::check(change.validity())?;
+    }
+
+    rpc::authz::batch::check_batch(&req.batch)
+}
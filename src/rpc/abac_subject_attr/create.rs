@@ -1,18 +1,23 @@
-use abac::{models::AbacSubject, AbacAttribute};
+use abac::AbacAttribute;
 use futures::{future, Future};
 
-use rpc;
+use models::AbacSubject;
+use rpc::{self, validity::Validity};
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Request {
     pub inbound: AbacAttribute,
     pub outbound: AbacAttribute,
+    #[serde(flatten)]
+    pub validity: Validity,
 }
 
 #[derive(Debug, Serialize)]
 pub struct Response {
     inbound: AbacAttribute,
     outbound: AbacAttribute,
+    #[serde(flatten)]
+    validity: Validity,
 }
 
 impl From<AbacSubject> for Response {
@@ -20,62 +25,21 @@ impl From<AbacSubject> for Response {
         Response {
             inbound: subject.inbound,
             outbound: subject.outbound,
+            validity: Validity {
+                not_before: subject.not_before,
+                expires_at: subject.expires_at,
+            },
         }
     }
 }
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    use abac_attribute::{CollectionKind, OperationKind};
     use actors::db;
-    use rpc::authorize_collection;
-    use uuid::Uuid;
-
-    let Request { inbound, outbound } = req.clone();
 
-    let inbound_ns_id = inbound.namespace_id;
-    let outbound_ns_id = outbound.namespace_id;
-
-    let collection = CollectionKind::AbacSubject;
-    let operation = OperationKind::Create;
-
-    future::result(rpc::forbid_anonymous(meta.subject))
+    authorize(&meta, &req)
         .and_then({
-            let db = meta.db.clone().unwrap();
-            move |subject_id| {
-                authorize_collection(&db, inbound_ns_id, subject_id, collection, operation).or_else(
-                    move |_| {
-                        authorize_collection(&db, outbound_ns_id, subject_id, collection, operation)
-                            .and_then(move |_| {
-                                if inbound.key == "uri" {
-                                    let mut parts = inbound.value.splitn(2, '/');
-                                    if let (Some("account"), Some(uuid)) =
-                                        (parts.next(), parts.next())
-                                    {
-                                        return Uuid::parse_str(uuid)
-                                            .map_err(|_| rpc::error::Error::Forbidden);
-                                    }
-                                }
-                                Err(rpc::error::Error::Forbidden)
-                            })
-                            .and_then({
-                                move |account_id| {
-                                    let msg =
-                                        db::identity::select::Select::ByAccountIdAndProvider {
-                                            account_id,
-                                            provider: outbound_ns_id,
-                                        };
-                                    db.send(msg).from_err().and_then(|res| {
-                                        if res?.is_empty() {
-                                            Err(rpc::error::Error::Forbidden)
-                                        } else {
-                                            Ok(())
-                                        }
-                                    })
-                                }
-                            })
-                    },
-                )
-            }
+            let validity = req.validity;
+            move |_| rpc::validity::check(validity)
         })
         .and_then({
             let db = meta.db.unwrap();
@@ -88,3 +52,67 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
             }
         })
 }
+
+/// Checks the subject of the request is allowed to create subject attribute links.
+pub fn authorize(meta: &rpc::Meta, req: &Request) -> impl Future<Item = (), Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
+    use actors::db;
+    use rpc::authorize_collection;
+    use uuid::Uuid;
+
+    let Request { inbound, outbound, .. } = req.clone();
+
+    let inbound_ns_id = inbound.namespace_id;
+    let outbound_ns_id = outbound.namespace_id;
+
+    let collection = CollectionKind::AbacSubject;
+    let operation = OperationKind::Create;
+
+    future::result(rpc::forbid_anonymous(meta.subject)).and_then({
+        let db = meta.db.clone().unwrap();
+        let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+        move |subject_id| {
+            authorize_collection(
+                &db,
+                &allowed_ns_ids,
+                inbound_ns_id,
+                subject_id,
+                collection,
+                operation,
+            ).or_else(move |_| {
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    outbound_ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                ).and_then(move |_| {
+                    if inbound.key == "uri" {
+                        let mut parts = inbound.value.splitn(2, '/');
+                        if let (Some("account"), Some(uuid)) = (parts.next(), parts.next()) {
+                            return Uuid::parse_str(uuid)
+                                .map_err(|_| rpc::error::Error::Forbidden);
+                        }
+                    }
+                    Err(rpc::error::Error::Forbidden)
+                })
+                .and_then({
+                    move |account_id| {
+                        let msg = db::identity::select::Select::ByAccountIdAndProvider {
+                            account_id,
+                            provider: outbound_ns_id,
+                        };
+                        db.send(msg).from_err().and_then(|res| {
+                            if res?.is_empty() {
+                                Err(rpc::error::Error::Forbidden)
+                            } else {
+                                Ok(())
+                            }
+                        })
+                    }
+                })
+            })
+        }
+    })
+}
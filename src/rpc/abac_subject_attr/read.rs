@@ -10,7 +10,7 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
     use actors::db::abac_subject_attr;
     use rpc::authorize_collection;
 
-    let Request { inbound, outbound } = req.clone();
+    let Request { inbound, outbound, .. } = req.clone();
 
     let inbound_ns_id = inbound.namespace_id;
     let outbound_ns_id = outbound.namespace_id;
@@ -21,12 +21,25 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
     future::result(rpc::forbid_anonymous(meta.subject))
         .and_then({
             let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
             move |subject_id| {
-                authorize_collection(&db, inbound_ns_id, subject_id, collection, operation).or_else(
-                    move |_| {
-                        authorize_collection(&db, outbound_ns_id, subject_id, collection, operation)
-                    },
-                )
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    inbound_ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                ).or_else(move |_| {
+                    authorize_collection(
+                        &db,
+                        &allowed_ns_ids,
+                        outbound_ns_id,
+                        subject_id,
+                        collection,
+                        operation,
+                    )
+                })
             }
         })
         .and_then({
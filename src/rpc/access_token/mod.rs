@@ -0,0 +1,40 @@
+use futures::Future;
+use jsonrpc::BoxFuture;
+
+use rpc;
+
+mod revoke;
+mod revoke_all;
+
+const WRITE_SCOPE: &str = "access_token:write";
+
+build_rpc_trait! {
+    pub trait Rpc {
+        type Metadata;
+
+        #[rpc(meta, name = "access_token.revoke")]
+        fn revoke(&self, Self::Metadata, revoke::Request) -> BoxFuture<revoke::Response>;
+
+        #[rpc(meta, name = "access_token.revoke_all")]
+        fn revoke_all(&self, Self::Metadata, revoke_all::Request) -> BoxFuture<revoke_all::Response>;
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct RpcImpl;
+
+impl Rpc for RpcImpl {
+    type Metadata = rpc::Meta;
+
+    fn revoke(&self, meta: rpc::Meta, req: revoke::Request) -> BoxFuture<revoke::Response> {
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| revoke::call(meta, req)).from_err())
+    }
+
+    fn revoke_all(
+        &self,
+        meta: rpc::Meta,
+        req: revoke_all::Request,
+    ) -> BoxFuture<revoke_all::Response> {
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| revoke_all::call(meta, req)).from_err())
+    }
+}
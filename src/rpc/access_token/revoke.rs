@@ -0,0 +1,66 @@
+use chrono::{DateTime, Duration, Utc};
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use abac_attribute::OperationKind;
+use actors::db::revoked_access_token;
+use models::{NewRevokedAccessToken, RevokedAccessToken};
+use rpc;
+
+/// `id` is the `jti` claim of the token.
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub id: Uuid,
+    pub account_id: Uuid,
+}
+
+pub type Response = rpc::Response<Uuid, ResponseData>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResponseData {
+    pub account_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RevokedAccessToken> for Response {
+    fn from(token: RevokedAccessToken) -> Self {
+        Response {
+            id: token.jti,
+            data: ResponseData {
+                account_id: token.account_id,
+                expires_at: token.expires_at,
+                created_at: token.created_at,
+            },
+        }
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                rpc::authorize_account(&db, subject_id, req.account_id, OperationKind::Update)
+                    .and_then(move |_| Ok(req))
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |req| {
+                // The token itself isn't known, so it's kept for as long as any token may live.
+                let settings = get_settings!();
+                let expires_in = Duration::seconds(i64::from(settings.tokens.expires_in_max));
+
+                let msg = revoked_access_token::insert::Insert(NewRevokedAccessToken {
+                    jti: req.id,
+                    account_id: req.account_id,
+                    expires_at: Utc::now() + expires_in,
+                });
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("access token revoke res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
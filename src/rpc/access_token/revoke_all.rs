@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use abac_attribute::OperationKind;
+use actors::db::account;
+use rpc;
+
+/// All of the tokens are revoked if `issued_before` is omitted.
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub account_id: Uuid,
+    pub issued_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+    pub account_id: Uuid,
+    pub issued_before: DateTime<Utc>,
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    let now = Utc::now();
+    let issued_before = req.issued_before.unwrap_or(now);
+
+    // Revoking the tokens yet to be issued would lock the account out.
+    let res = if issued_before > now {
+        Err(rpc::Error::BadRequest)
+    } else {
+        rpc::forbid_restricted(&meta)
+    };
+
+    future::result(res)
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                rpc::authorize_account(&db, subject_id, req.account_id, OperationKind::Update)
+                    .and_then(move |_| Ok(req.account_id))
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |account_id| {
+                let msg = account::update::RevokeTokens {
+                    id: account_id,
+                    issued_before,
+                };
+                db.send(msg).from_err().and_then(move |res| {
+                    debug!("access token revoke all res: {:?}", res);
+                    let account = res?;
+                    Ok(Response {
+                        account_id: account.id,
+                        issued_before: account.tokens_revoked_before.unwrap_or(issued_before),
+                    })
+                })
+            }
+        })
+}
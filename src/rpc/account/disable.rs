@@ -10,7 +10,7 @@ pub type Request = rpc::account::read::Request;
 pub type Response = rpc::account::read::Response;
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    let subject = rpc::forbid_anonymous(meta.subject);
+    let subject = rpc::forbid_restricted(&meta);
     future::result(subject)
         .and_then({
             let db = meta.db.clone().unwrap();
@@ -0,0 +1,87 @@
+use abac::AbacAttribute;
+use futures::future::{self, Future};
+
+use actors::db::{account, authz::Authz};
+use rpc;
+use rpc::account::read::{self, Include};
+use settings;
+
+/// Not deleted accounts are listed unless `deleted` is set, either state of `disabled` by default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Filter {
+    pub disabled: Option<bool>,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Request {
+    #[serde(default)]
+    pub filter: Filter,
+    #[serde(default)]
+    pub include: Vec<Include>,
+
+    #[serde(flatten)]
+    pub pagination: rpc::pagination::Pagination,
+}
+
+pub type Response = rpc::ListResponse<read::Response>;
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind, UriKind};
+
+    let Request {
+        filter,
+        include,
+        pagination,
+    } = req;
+
+    let subject = rpc::forbid_restricted(&meta);
+    future::result(subject)
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                let iam_namespace_id = settings::iam_namespace_id();
+
+                let msg = Authz {
+                    namespace_ids: vec![iam_namespace_id],
+                    subject: vec![AbacAttribute::new(
+                        iam_namespace_id,
+                        UriKind::Account(subject_id),
+                    )],
+                    object: vec![AbacAttribute::new(
+                        iam_namespace_id,
+                        CollectionKind::Account,
+                    )],
+                    action: vec![AbacAttribute::new(iam_namespace_id, OperationKind::List)],
+                };
+
+                db.send(msg).from_err().and_then(rpc::ensure_authorized)
+            }
+        })
+        .and_then({
+            let limit = pagination.limit;
+            move |_| rpc::pagination::check_limit(limit)
+        })
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |_| {
+                let msg = account::select::Select {
+                    disabled: filter.disabled,
+                    deleted: filter.deleted,
+                    limit: pagination.limit,
+                    offset: pagination.offset,
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("account select res: {:?}", res);
+                    Ok(res?)
+                })
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |accounts| read::fetch_related(&db, accounts, &include).map(Response::from)
+        })
+}
@@ -5,7 +5,12 @@ use rpc;
 
 mod disable;
 mod enable;
+mod list;
 mod read;
+mod rotate_refresh_key;
+
+const READ_SCOPE: &str = "account:read";
+const WRITE_SCOPE: &str = "account:write";
 
 build_rpc_trait! {
     pub trait Rpc {
@@ -14,11 +19,17 @@ build_rpc_trait! {
         #[rpc(meta, name = "account.read")]
         fn read(&self, Self::Metadata, read::Request) -> BoxFuture<read::Response>;
 
+        #[rpc(meta, name = "account.list")]
+        fn list(&self, Self::Metadata, list::Request) -> BoxFuture<list::Response>;
+
         #[rpc(meta, name = "account.disable")]
         fn disable(&self, Self::Metadata, disable::Request) -> BoxFuture<disable::Response>;
 
         #[rpc(meta, name = "account.enable")]
         fn enable(&self, Self::Metadata, enable::Request) -> BoxFuture<enable::Response>;
+
+        #[rpc(meta, name = "account.rotate_refresh_key")]
+        fn rotate_refresh_key(&self, Self::Metadata, rotate_refresh_key::Request) -> BoxFuture<rotate_refresh_key::Response>;
     }
 }
 
@@ -29,14 +40,27 @@ impl Rpc for RpcImpl {
     type Metadata = rpc::Meta;
 
     fn read(&self, meta: rpc::Meta, req: read::Request) -> BoxFuture<read::Response> {
-        Box::new(read::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| read::call(meta, req)).from_err())
+    }
+
+    fn list(&self, meta: rpc::Meta, req: list::Request) -> BoxFuture<list::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| list::call(meta, req)).from_err())
     }
 
     fn disable(&self, meta: rpc::Meta, req: disable::Request) -> BoxFuture<disable::Response> {
-        Box::new(disable::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| disable::call(meta, req)).from_err())
     }
 
     fn enable(&self, meta: rpc::Meta, req: enable::Request) -> BoxFuture<enable::Response> {
-        Box::new(enable::call(meta, req).from_err())
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| enable::call(meta, req)).from_err())
+    }
+
+    fn rotate_refresh_key(
+        &self,
+        meta: rpc::Meta,
+        req: rotate_refresh_key::Request,
+    ) -> BoxFuture<rotate_refresh_key::Response> {
+        let f = rpc::scoped(meta, WRITE_SCOPE, |meta| rotate_refresh_key::call(meta, req));
+        Box::new(f.from_err())
     }
 }
@@ -1,10 +1,13 @@
 use abac::AbacAttribute;
+use actix::Addr;
 use chrono::{DateTime, Utc};
 use diesel;
 use futures::future::{self, Either, Future};
+use serde_json::Value;
 use uuid::Uuid;
 
 use actors::db::{account, authz::Authz};
+use actors::DbExecutor;
 use models::Account;
 use rpc;
 use settings;
@@ -12,6 +15,17 @@ use settings;
 #[derive(Debug, Deserialize)]
 pub struct Request {
     pub id: Uuid,
+    #[serde(default)]
+    pub include: Vec<Include>,
+}
+
+/// Optional parts of the account, omitted from the response unless requested.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Include {
+    Identities,
+    Namespaces,
+    Constraints,
 }
 
 pub type Response = rpc::Response<Uuid, ResponseData>;
@@ -19,6 +33,14 @@ pub type Response = rpc::Response<Uuid, ResponseData>;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResponseData {
     pub disabled_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identities: Option<Vec<rpc::identity::read::Response>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespaces: Option<Vec<rpc::namespace::read::Response>>,
 }
 
 impl From<Account> for Response {
@@ -27,18 +49,46 @@ impl From<Account> for Response {
             id: account.id,
             data: ResponseData {
                 disabled_at: account.disabled_at,
+                deleted_at: account.deleted_at,
+                constraints: None,
+                identities: None,
+                namespaces: None,
             },
         }
     }
 }
 
+/// Builds responses of the accounts along with their parts of `include`.
+pub fn fetch_related(
+    db: &Addr<DbExecutor>,
+    accounts: Vec<Account>,
+    include: &[Include],
+) -> impl Future<Item = Vec<Response>, Error = rpc::Error> {
+    let msg = account::related::SelectRelated {
+        accounts,
+        identities: include.contains(&Include::Identities),
+        namespaces: include.contains(&Include::Namespaces),
+    };
+    let with_constraints = include.contains(&Include::Constraints);
+
+    db.send(msg).from_err().and_then(move |res| {
+        let items = res?
+            .into_iter()
+            .map(|related| build_response(related, with_constraints))
+            .collect();
+        Ok(items)
+    })
+}
+
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    let subject = rpc::forbid_anonymous(meta.subject);
+    let Request { id, include } = req;
+
+    let subject = rpc::forbid_restricted(&meta);
     future::result(subject)
         .and_then({
             let db = meta.db.clone().unwrap();
             move |subject_id| {
-                let msg = account::find::Find::Active(req.id);
+                let msg = account::find::Find::Active(id);
                 db.send(msg).from_err().and_then(move |res| {
                     debug!("account find res: {:?}", res);
 
@@ -53,7 +103,7 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
             }
         })
         .and_then({
-            let db = meta.db.unwrap();
+            let db = meta.db.clone().unwrap();
             move |(account, subject_id)| {
                 use abac_attribute::{CollectionKind, OperationKind, UriKind};
 
@@ -104,5 +154,31 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
                 }
             }
         })
-        .and_then(|account| Ok(Response::from(account)))
+        .and_then({
+            let db = meta.db.unwrap();
+            move |account| {
+                fetch_related(&db, vec![account], &include)
+                    .and_then(|mut items| items.pop().ok_or(rpc::Error::InternalError))
+            }
+        })
+}
+
+fn build_response(related: account::related::Related, with_constraints: bool) -> Response {
+    let account::related::Related {
+        account,
+        identities,
+        namespaces,
+    } = related;
+
+    let constraints = if with_constraints {
+        Some(account.constraints.clone())
+    } else {
+        None
+    };
+
+    let mut resp = Response::from(account);
+    resp.data.constraints = constraints;
+    resp.data.identities = identities.map(|v| v.into_iter().map(From::from).collect());
+    resp.data.namespaces = namespaces.map(|v| v.into_iter().map(From::from).collect());
+    resp
 }
@@ -0,0 +1,54 @@
+use abac::AbacAttribute;
+use futures::future::{self, Future};
+
+use actors::db::{authz::Authz, refresh_token};
+use rpc;
+use settings;
+
+pub type Request = rpc::account::read::Request;
+pub type Response = rpc::ListResponse<rpc::session::read::Response>;
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                use abac_attribute::{CollectionKind, OperationKind, UriKind};
+
+                let iam_namespace_id = settings::iam_namespace_id();
+
+                let msg = Authz {
+                    namespace_ids: vec![iam_namespace_id],
+                    subject: vec![AbacAttribute::new(
+                        iam_namespace_id,
+                        UriKind::Account(subject_id),
+                    )],
+                    object: vec![AbacAttribute::new(
+                        iam_namespace_id,
+                        CollectionKind::Account,
+                    )],
+                    action: vec![AbacAttribute::new(iam_namespace_id, OperationKind::Update)],
+                };
+
+                db.send(msg).from_err().and_then(rpc::ensure_authorized)
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let keep = {
+                    let settings = get_settings!();
+                    settings.tokens.refresh_keys_kept
+                };
+
+                let msg = refresh_token::update::RotateKeys {
+                    account_id: req.id,
+                    keep,
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("refresh token rotate keys res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use actors::db::api_key;
+use models::{ApiKey, NewApiKey, Secret};
+use rpc;
+
+/// A key without `namespace_ids` isn't restricted to any namespaces.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Request {
+    pub label: String,
+    pub namespace_ids: Option<Vec<Uuid>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub type Response = rpc::Response<Uuid, ResponseData>;
+
+/// `key` is only returned on creation, it can't be retrieved later on.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResponseData {
+    pub account_id: Uuid,
+    pub label: String,
+    pub namespace_ids: Option<Vec<Uuid>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl From<ApiKey> for Response {
+    fn from(key: ApiKey) -> Self {
+        Response {
+            id: key.id,
+            data: ResponseData {
+                account_id: key.account_id,
+                label: key.label,
+                namespace_ids: key.namespace_ids,
+                expires_at: key.expires_at,
+                created_at: key.created_at,
+                key: None,
+            },
+        }
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    let Request {
+        label,
+        namespace_ids,
+        expires_at,
+    } = req;
+    let namespace_ids = restrict(meta.allowed_namespace_ids.clone(), namespace_ids);
+
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then(move |subject_id| {
+            if expires_at.map_or(false, |expires_at| expires_at <= Utc::now()) {
+                return Err(rpc::Error::BadRequest);
+            }
+
+            let secret = Secret::generate().map_err(|_| rpc::Error::InternalError)?;
+            let key = NewApiKey {
+                account_id: subject_id,
+                label,
+                secret_hash: secret.hash.clone(),
+                namespace_ids: namespace_ids?,
+                expires_at,
+            };
+            Ok((key, secret))
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |(key, secret)| {
+                db.send(api_key::insert::Insert(key))
+                    .from_err()
+                    .and_then(move |res| {
+                        debug!("api key insert res: {:?}", res);
+                        let mut resp = Response::from(res?);
+                        resp.data.key = Some(format!("{}.{}", resp.id, secret.value));
+                        Ok(resp)
+                    })
+            }
+        })
+}
+
+/// A key created with a restricted key is never restricted less than the latter.
+fn restrict(
+    allowed_ns_ids: Option<Vec<Uuid>>,
+    requested_ns_ids: Option<Vec<Uuid>>,
+) -> rpc::Result<Option<Vec<Uuid>>> {
+    match (allowed_ns_ids, requested_ns_ids) {
+        (Some(allowed), Some(requested)) => {
+            if requested.iter().all(|id| allowed.contains(id)) {
+                Ok(Some(requested))
+            } else {
+                Err(rpc::Error::Forbidden)
+            }
+        }
+        (Some(allowed), None) => Ok(Some(allowed)),
+        (None, requested) => Ok(requested),
+    }
+}
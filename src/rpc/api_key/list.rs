@@ -0,0 +1,37 @@
+use futures::future::{self, Future};
+
+use actors::db::api_key;
+use rpc;
+use rpc::pagination::Pagination;
+
+/// Only the keys of the caller's own account are listed.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Request {
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+pub type Response = rpc::ListResponse<rpc::api_key::create::Response>;
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then({
+            let limit = req.pagination.limit;
+            move |subject_id| rpc::pagination::check_limit(limit).map(|_| subject_id)
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |account_id| {
+                let msg = api_key::select::Select {
+                    account_id,
+                    limit: req.pagination.limit,
+                    offset: req.pagination.offset,
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("api key select res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
@@ -0,0 +1,45 @@
+use futures::Future;
+use jsonrpc::BoxFuture;
+
+use rpc;
+
+mod create;
+mod list;
+mod revoke;
+
+const READ_SCOPE: &str = "api_key:read";
+const WRITE_SCOPE: &str = "api_key:write";
+
+build_rpc_trait! {
+    pub trait Rpc {
+        type Metadata;
+
+        #[rpc(meta, name = "api_key.create")]
+        fn create(&self, Self::Metadata, create::Request) -> BoxFuture<create::Response>;
+
+        #[rpc(meta, name = "api_key.list")]
+        fn list(&self, Self::Metadata, list::Request) -> BoxFuture<list::Response>;
+
+        #[rpc(meta, name = "api_key.revoke")]
+        fn revoke(&self, Self::Metadata, revoke::Request) -> BoxFuture<revoke::Response>;
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct RpcImpl;
+
+impl Rpc for RpcImpl {
+    type Metadata = rpc::Meta;
+
+    fn create(&self, meta: rpc::Meta, req: create::Request) -> BoxFuture<create::Response> {
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| create::call(meta, req)).from_err())
+    }
+
+    fn list(&self, meta: rpc::Meta, req: list::Request) -> BoxFuture<list::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| list::call(meta, req)).from_err())
+    }
+
+    fn revoke(&self, meta: rpc::Meta, req: revoke::Request) -> BoxFuture<revoke::Response> {
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| revoke::call(meta, req)).from_err())
+    }
+}
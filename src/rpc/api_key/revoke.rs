@@ -0,0 +1,30 @@
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use actors::db::api_key;
+use rpc;
+
+/// Keys of other accounts are reported as missing.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Request {
+    pub id: Uuid,
+}
+
+pub type Response = rpc::api_key::create::Response;
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    future::result(rpc::forbid_restricted(&meta)).and_then({
+        let db = meta.db.unwrap();
+        move |account_id| {
+            let msg = api_key::delete::Delete {
+                id: req.id,
+                account_id,
+            };
+            db.send(msg).from_err().and_then(|res| {
+                debug!("api key delete res: {:?}", res);
+                Ok(Response::from(res?))
+            })
+        }
+    })
+}
@@ -0,0 +1,73 @@
+use futures::{future, Future};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use actors::db::authz::{Authz, AuthzBatch};
+use rpc;
+use settings;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub checks: Vec<Check>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Check {
+    pub id: String,
+    #[serde(flatten)]
+    pub request: rpc::authz::Request,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response(BTreeMap<String, bool>);
+
+impl From<Vec<(String, bool)>> for Response {
+    fn from(decisions: Vec<(String, bool)>) -> Self {
+        Response(decisions.into_iter().collect())
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    let iam_namespace_id = settings::iam_namespace_id();
+
+    future::result(check_batch(&req))
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let checks = req
+                    .checks
+                    .into_iter()
+                    .map(|check| {
+                        let mut msg = Authz::from(check.request);
+                        msg.namespace_ids.push(iam_namespace_id);
+                        msg.namespace_ids.dedup();
+
+                        (check.id, msg)
+                    })
+                    .collect();
+
+                db.send(AuthzBatch(checks)).from_err().and_then(|res| {
+                    debug!("authz batch res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
+
+pub fn check_batch(req: &Request) -> rpc::Result<()> {
+    let limit_max = {
+        let settings = get_settings!();
+        usize::from(settings.pagination.limit_max)
+    };
+
+    if req.checks.len() > limit_max {
+        return Err(rpc::Error::BadRequest);
+    }
+
+    let mut ids = BTreeSet::new();
+    if req.checks.iter().all(|check| ids.insert(check.id.as_str())) {
+        Ok(())
+    } else {
+        Err(rpc::Error::BadRequest)
+    }
+}
@@ -0,0 +1,139 @@
+use abac::AbacAttribute;
+use futures::{future, Future};
+
+use actors::db::{explain, tree};
+use rpc;
+use settings;
+
+pub type Request = rpc::authz::Request;
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    granted: bool,
+    subject: Expansion,
+    object: Expansion,
+    action: Expansion,
+    policies: Vec<rpc::abac_policy::read::Response>,
+    candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Serialize)]
+struct Expansion {
+    attributes: Vec<AbacAttribute>,
+    links: Vec<Link>,
+}
+
+#[derive(Debug, Serialize)]
+struct Link {
+    inbound: AbacAttribute,
+    outbound: AbacAttribute,
+    depth: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct Candidate {
+    policy: rpc::abac_policy::read::Response,
+    missing: Missing,
+}
+
+#[derive(Debug, Serialize)]
+struct Missing {
+    subject: Vec<AbacAttribute>,
+    object: Vec<AbacAttribute>,
+    action: Vec<AbacAttribute>,
+}
+
+impl From<explain::Explanation> for Response {
+    fn from(explanation: explain::Explanation) -> Self {
+        Response {
+            granted: explanation.granted,
+            subject: Expansion::from(explanation.subject),
+            object: Expansion::from(explanation.object),
+            action: Expansion::from(explanation.action),
+            policies: explanation.policies.into_iter().map(From::from).collect(),
+            candidates: explanation
+                .candidates
+                .into_iter()
+                .map(Candidate::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<explain::Expansion> for Expansion {
+    fn from(expansion: explain::Expansion) -> Self {
+        Expansion {
+            attributes: expansion.attributes,
+            links: expansion.links.into_iter().map(Link::from).collect(),
+        }
+    }
+}
+
+impl From<tree::Link> for Link {
+    fn from(link: tree::Link) -> Self {
+        Link {
+            inbound: link.inbound,
+            outbound: link.outbound,
+            depth: link.depth,
+        }
+    }
+}
+
+impl From<explain::Candidate> for Candidate {
+    fn from(candidate: explain::Candidate) -> Self {
+        Candidate {
+            policy: From::from(candidate.policy),
+            missing: Missing {
+                subject: candidate.missing_subject,
+                object: candidate.missing_object,
+                action: candidate.missing_action,
+            },
+        }
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
+    use actors::db::authz::Authz;
+    use rpc::authorize_collection;
+
+    // The decision is made within the IAM namespace as well, so are the policies listed.
+    let mut authz = Authz::from(req);
+    authz.namespace_ids.push(settings::iam_namespace_id());
+    authz.namespace_ids.dedup();
+
+    future::result(rpc::forbid_anonymous(meta.subject))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+            let namespace_ids = authz.namespace_ids.clone();
+
+            move |subject_id| {
+                let collection = CollectionKind::AbacPolicy;
+                let operation = OperationKind::List;
+
+                let futures = namespace_ids.into_iter().map(move |ns_id| {
+                    authorize_collection(
+                        &db,
+                        &allowed_ns_ids,
+                        ns_id,
+                        subject_id,
+                        collection,
+                        operation,
+                    )
+                });
+
+                future::join_all(futures)
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let msg = explain::Explain { authz };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("authz explain res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
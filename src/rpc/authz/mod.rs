@@ -0,0 +1,97 @@
+use abac::AbacAttribute;
+use futures::Future;
+use jsonrpc::BoxFuture;
+use uuid::Uuid;
+
+use actors::db::authz::Authz;
+use rpc;
+use settings;
+
+pub mod batch;
+pub mod explain;
+pub mod permissions;
+pub mod subjects;
+
+const READ_SCOPE: &str = "authorize:read";
+
+build_rpc_trait! {
+    pub trait Rpc {
+        type Metadata;
+
+        #[rpc(meta, name = "authorize")]
+        fn authz(&self, Self::Metadata, Request) -> BoxFuture<Response>;
+
+        #[rpc(meta, name = "authorize.batch")]
+        fn batch(&self, Self::Metadata, batch::Request) -> BoxFuture<batch::Response>;
+
+        #[rpc(meta, name = "authorize.explain")]
+        fn explain(&self, Self::Metadata, explain::Request) -> BoxFuture<explain::Response>;
+
+        #[rpc(meta, name = "authorize.permissions")]
+        fn permissions(&self, Self::Metadata, permissions::Request) -> BoxFuture<permissions::Response>;
+
+        #[rpc(meta, name = "authorize.subjects")]
+        fn subjects(&self, Self::Metadata, subjects::Request) -> BoxFuture<subjects::Response>;
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub namespace_ids: Vec<Uuid>,
+    pub subject: Vec<AbacAttribute>,
+    pub object: Vec<AbacAttribute>,
+    pub action: Vec<AbacAttribute>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response(bool);
+
+impl Response {
+    pub fn new(value: bool) -> Self {
+        Response(value)
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct RpcImpl;
+
+impl Rpc for RpcImpl {
+    type Metadata = rpc::Meta;
+
+    fn authz(&self, meta: rpc::Meta, req: Request) -> BoxFuture<Response> {
+        let fut = rpc::scoped(meta, READ_SCOPE, |meta| {
+            let iam_namespace_id = settings::iam_namespace_id();
+
+            let mut msg = Authz::from(req);
+            msg.namespace_ids.push(iam_namespace_id);
+            msg.namespace_ids.dedup();
+
+            let db = meta.db.unwrap();
+            db.send(msg)
+                .from_err::<rpc::error::Error>()
+                .and_then(|res| Ok(Response::new(res?)))
+        });
+
+        Box::new(fut.from_err())
+    }
+
+    fn batch(&self, meta: rpc::Meta, req: batch::Request) -> BoxFuture<batch::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| batch::call(meta, req)).from_err())
+    }
+
+    fn explain(&self, meta: rpc::Meta, req: explain::Request) -> BoxFuture<explain::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| explain::call(meta, req)).from_err())
+    }
+
+    fn permissions(
+        &self,
+        meta: rpc::Meta,
+        req: permissions::Request,
+    ) -> BoxFuture<permissions::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| permissions::call(meta, req)).from_err())
+    }
+
+    fn subjects(&self, meta: rpc::Meta, req: subjects::Request) -> BoxFuture<subjects::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| subjects::call(meta, req)).from_err())
+    }
+}
@@ -0,0 +1,101 @@
+use abac::AbacAttribute;
+use futures::future::{self, Either, Future};
+use uuid::Uuid;
+
+use actors::db::permissions;
+use rpc;
+use settings;
+
+pub type Request = rpc::ListRequest<Filter>;
+pub type Response = rpc::ListResponse<Permission>;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Filter {
+    pub account_id: Uuid,
+    pub namespace_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Permission {
+    namespace_id: Uuid,
+    object: Vec<AbacAttribute>,
+    action: Vec<AbacAttribute>,
+}
+
+impl From<permissions::Permission> for Permission {
+    fn from(permission: permissions::Permission) -> Self {
+        Permission {
+            namespace_id: permission.namespace_id,
+            object: permission.object,
+            action: permission.action,
+        }
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind, UriKind};
+    use rpc::authorize_collection;
+
+    future::result(rpc::forbid_anonymous(meta.subject))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+            let account_id = req.filter.account_id;
+            let namespace_ids = req.filter.namespace_ids.clone();
+
+            move |subject_id| {
+                // Any account is allowed to look up its own permissions,
+                // within the namespaces the request is restricted to.
+                if subject_id == account_id {
+                    let res = namespace_ids
+                        .iter()
+                        .map(|&ns_id| rpc::ensure_namespace_allowed(&allowed_ns_ids, ns_id))
+                        .collect::<rpc::Result<Vec<_>>>()
+                        .map(|_| ());
+                    return Either::A(future::result(res));
+                }
+
+                let collection = CollectionKind::AbacPolicy;
+                let operation = OperationKind::List;
+
+                let futures = namespace_ids.into_iter().map(move |ns_id| {
+                    authorize_collection(
+                        &db,
+                        &allowed_ns_ids,
+                        ns_id,
+                        subject_id,
+                        collection,
+                        operation,
+                    )
+                });
+
+                Either::B(future::join_all(futures).map(|_| ()))
+            }
+        })
+        .and_then({
+            let limit = req.pagination.limit;
+            move |_| rpc::pagination::check_limit(limit)
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let iam_namespace_id = settings::iam_namespace_id();
+                let subject = AbacAttribute::new(
+                    iam_namespace_id,
+                    UriKind::Account(req.filter.account_id),
+                );
+
+                let msg = permissions::Permissions {
+                    subject: vec![subject],
+                    namespace_ids: req.filter.namespace_ids,
+                    limit: req.pagination.limit,
+                    offset: req.pagination.offset,
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("authz permissions res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
@@ -0,0 +1,63 @@
+use abac::AbacAttribute;
+use futures::{future, Future};
+use uuid::Uuid;
+
+use actors::db::subjects;
+use rpc;
+
+pub type Request = rpc::ListRequest<Filter>;
+pub type Response = rpc::ListResponse<AbacAttribute>;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Filter {
+    pub namespace_id: Uuid,
+    pub object: AbacAttribute,
+    pub action: AbacAttribute,
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
+    use rpc::authorize_collection;
+
+    future::result(rpc::forbid_anonymous(meta.subject))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+            let ns_id = req.filter.namespace_id;
+
+            move |subject_id| {
+                let collection = CollectionKind::AbacPolicy;
+                let operation = OperationKind::List;
+
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                )
+            }
+        })
+        .and_then({
+            let limit = req.pagination.limit;
+            move |_| rpc::pagination::check_limit(limit)
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let msg = subjects::Subjects {
+                    namespace_id: req.filter.namespace_id,
+                    object: req.filter.object,
+                    action: req.filter.action,
+                    limit: req.pagination.limit,
+                    offset: req.pagination.offset,
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("authz subjects res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
@@ -1,7 +1,10 @@
+use abac::AbacAttribute;
 use actix;
 use diesel;
 use jsonrpc;
 
+use actors::db;
+
 pub type Result<T> = ::std::result::Result<T, Error>;
 
 #[derive(Debug, Fail)]
@@ -15,8 +18,17 @@ pub enum Error {
     #[fail(display = "Bad request")]
     BadRequest,
 
+    #[fail(display = "Internal error")]
+    InternalError,
+
     #[fail(display = "Forbidden")]
     Forbidden,
+
+    #[fail(display = "Insufficient scope")]
+    InsufficientScope(String),
+
+    #[fail(display = "Link would create a cycle")]
+    Cycle(Vec<AbacAttribute>),
 }
 
 impl From<actix::MailboxError> for Error {
@@ -31,12 +43,24 @@ impl From<diesel::result::Error> for Error {
     }
 }
 
+impl From<db::Error> for Error {
+    fn from(e: db::Error) -> Self {
+        match e {
+            db::Error::Db(e) => Error::Db(e),
+            db::Error::Cycle(path) => Error::Cycle(path),
+        }
+    }
+}
+
 macro_rules! server_error {
     ($code:expr, $error:expr) => {
+        server_error!($code, $error, None)
+    };
+    ($code:expr, $error:expr, $data:expr) => {
         jsonrpc::Error {
             code: jsonrpc::ErrorCode::ServerError($code),
             message: $error.to_string(),
-            data: None,
+            data: $data,
         }
     };
 }
@@ -50,7 +74,12 @@ impl From<Error> for jsonrpc::Error {
                 _ => server_error!(422, e),
             },
             Error::BadRequest => server_error!(400, e),
+            Error::InternalError => jsonrpc::Error::internal_error(),
             Error::Forbidden => server_error!(403, e),
+            Error::InsufficientScope(ref scope) => {
+                server_error!(403, e, Some(json!({ "scope": scope })))
+            }
+            Error::Cycle(ref path) => server_error!(409, e, Some(json!({ "path": path }))),
         }
     }
 }
@@ -39,7 +39,7 @@ impl From<Identity> for Response {
 }
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    let subject = rpc::forbid_anonymous(meta.subject);
+    let subject = rpc::forbid_restricted(&meta);
     future::result(subject)
         .and_then({
             let db = meta.db.clone().unwrap();
@@ -101,7 +101,7 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
                 let msg = identity::insert::InsertWithAccount(req.id);
                 db.send(msg).from_err().and_then(|res| {
                     debug!("identity insert res: {:?}", res);
-                    let (identity, _, _) = res?;
+                    let (identity, _) = res?;
                     Ok(Response::from(identity))
                 })
             }
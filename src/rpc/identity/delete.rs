@@ -11,7 +11,7 @@ pub type Request = rpc::identity::read::Request;
 pub type Response = rpc::identity::read::Response;
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    let subject = rpc::forbid_anonymous(meta.subject);
+    let subject = rpc::forbid_restricted(&meta);
     let namespace_id = req.id.provider;
 
     future::result(subject)
@@ -40,7 +40,7 @@ pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error
         ));
     }
 
-    let subject = rpc::forbid_anonymous(meta.subject);
+    let subject = rpc::forbid_restricted(&meta);
     future::result(subject)
         .and_then({
             let db = meta.db.clone().unwrap();
@@ -11,7 +11,7 @@ pub type Request = rpc::identity::create::Request;
 pub type Response = rpc::identity::create::Response;
 
 pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
-    let subject = rpc::forbid_anonymous(meta.subject);
+    let subject = rpc::forbid_restricted(&meta);
     let namespace_id = req.id.provider;
 
     future::result(subject)
@@ -2,7 +2,7 @@ use abac::AbacAttribute;
 use actix::Addr;
 use actix_web::{self, HttpMessage, HttpRequest, HttpResponse};
 use diesel::QueryResult;
-use futures::future::{self, Either, Future};
+use futures::future::{self, Either, Future, IntoFuture};
 use jsonrpc::{self, MetaIoHandler, Metadata};
 use serde_json;
 use uuid::Uuid;
@@ -10,43 +10,64 @@ use uuid::Uuid;
 use std::fmt;
 
 use abac_attribute::{CollectionKind, OperationKind, UriKind};
-use actors::{db::authz::Authz, DbExecutor};
+use actors::{
+    db::{api_key, authz::Authz, revoked_access_token, tree},
+    DbExecutor,
+};
 use authn;
+use models::ApiKey;
 use rpc::abac_action_attr::Rpc as AbacActionRpc;
 use rpc::abac_object_attr::Rpc as AbacObjectRpc;
 use rpc::abac_policy::Rpc as AbacPolicyRpc;
 use rpc::abac_subject_attr::Rpc as AbacSubjectRpc;
+use rpc::access_token::Rpc as AccessTokenRpc;
 use rpc::account::Rpc as AccountRpc;
+use rpc::api_key::Rpc as ApiKeyRpc;
 use rpc::authz::Rpc as AuthRpc;
 pub use rpc::error::{Error, Result};
 use rpc::identity::Rpc as IdentityRpc;
 use rpc::namespace::Rpc as NamespaceRpc;
 use rpc::pagination::Pagination;
 use rpc::ping::Rpc as PingRpc;
+use rpc::service_account::Rpc as ServiceAccountRpc;
+use rpc::session::Rpc as SessionRpc;
 use AppState;
 
 pub mod abac_action_attr;
 pub mod abac_object_attr;
 pub mod abac_policy;
 pub mod abac_subject_attr;
+pub mod access_token;
 pub mod account;
+pub mod api_key;
 pub mod authz;
 pub mod error;
 pub mod identity;
 pub mod namespace;
 mod pagination;
 mod ping;
+pub mod service_account;
+pub mod session;
+mod validity;
 
 // TODO: remove Default on new jsonrpc_core version
 #[derive(Clone, Default)]
 pub struct Meta {
     pub db: Option<Addr<DbExecutor>>,
     pub subject: Option<Uuid>,
+    /// Namespaces the request is restricted to, set for restricted API keys only.
+    pub allowed_namespace_ids: Option<Vec<Uuid>>,
+    /// Scopes the request is restricted to, set for scoped access tokens only.
+    pub scopes: Option<Vec<String>>,
 }
 
 impl fmt::Debug for Meta {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Meta {{ subject: {:?} }}", self.subject)
+        write!(
+            f,
+            "Meta {{ subject: {:?}, allowed_namespace_ids: {:?}, scopes: {:?} }}",
+            self.subject, self.allowed_namespace_ids, self.scopes
+        )
     }
 }
 
@@ -84,6 +105,18 @@ pub fn build_server() -> Server {
     let rpc = account::RpcImpl {};
     io.extend_with(rpc.to_delegate());
 
+    let rpc = session::RpcImpl {};
+    io.extend_with(rpc.to_delegate());
+
+    let rpc = access_token::RpcImpl {};
+    io.extend_with(rpc.to_delegate());
+
+    let rpc = service_account::RpcImpl {};
+    io.extend_with(rpc.to_delegate());
+
+    let rpc = api_key::RpcImpl {};
+    io.extend_with(rpc.to_delegate());
+
     io
 }
 
@@ -115,6 +148,8 @@ pub struct ListRequestFilter {
 pub struct TreeRequestFilter {
     pub direction: DirectionKind,
     pub attribute: AbacAttribute,
+    #[serde(default)]
+    pub recursive: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -127,7 +162,24 @@ pub enum DirectionKind {
 #[derive(Debug, Serialize)]
 pub struct ListResponse<T>(Vec<T>);
 
-pub type TreeResponse = ListResponse<AbacAttribute>;
+pub type TreeResponse = ListResponse<TreeNode>;
+
+#[derive(Debug, Serialize)]
+pub struct TreeNode {
+    #[serde(flatten)]
+    pub attribute: AbacAttribute,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<i32>,
+}
+
+impl From<tree::Node> for TreeNode {
+    fn from(node: tree::Node) -> Self {
+        TreeNode {
+            attribute: node.attribute,
+            distance: node.distance,
+        }
+    }
+}
 
 impl<T, I> From<Vec<I>> for ListResponse<T>
 where
@@ -151,6 +203,48 @@ pub fn forbid_anonymous(subject: Option<Uuid>) -> Result<Uuid> {
     subject.ok_or_else(|| Error::Forbidden)
 }
 
+/// Operations outside of namespace collections belong to the IAM namespace,
+/// a restricted API key has to include it to perform them.
+pub fn forbid_restricted(meta: &Meta) -> Result<Uuid> {
+    use settings;
+
+    let subject_id = forbid_anonymous(meta.subject)?;
+    ensure_namespace_allowed(&meta.allowed_namespace_ids, settings::iam_namespace_id())?;
+    Ok(subject_id)
+}
+
+pub fn ensure_namespace_allowed(allowed_ns_ids: &Option<Vec<Uuid>>, ns_id: Uuid) -> Result<()> {
+    match *allowed_ns_ids {
+        Some(ref ids) if !ids.contains(&ns_id) => Err(Error::Forbidden),
+        _ => Ok(()),
+    }
+}
+
+/// Calls the method unless the request is restricted to scopes other than the one it requires.
+pub fn scoped<F, R>(
+    meta: Meta,
+    scope: &'static str,
+    f: F,
+) -> impl Future<Item = R::Item, Error = Error>
+where
+    F: FnOnce(Meta) -> R,
+    R: IntoFuture<Error = Error>,
+{
+    match ensure_scope(&meta.scopes, scope) {
+        Ok(()) => Either::A(f(meta).into_future()),
+        Err(e) => Either::B(future::err(e)),
+    }
+}
+
+fn ensure_scope(scopes: &Option<Vec<String>>, scope: &str) -> Result<()> {
+    match *scopes {
+        Some(ref scopes) if !scopes.iter().any(|v| v == scope) => {
+            Err(Error::InsufficientScope(scope.to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn index(
     req: HttpRequest<AppState>,
 ) -> impl Future<Item = HttpResponse, Error = actix_web::Error> {
@@ -160,49 +254,44 @@ pub fn index(
         .json()
         .from_err()
         .and_then(move |request: jsonrpc::Request| {
-            use extract_authorization_header;
-
-            let headers = req.headers();
-            let res = match extract_authorization_header(&headers) {
-                Ok(Some(value)) => {
-                    let raw_token = authn::jwt::RawToken {
-                        kind: authn::jwt::RawTokenKind::Iam,
-                        value,
-                    };
-                    match authn::jwt::AccessToken::decode(&raw_token) {
-                        Ok(token) => {
-                            let validator = authn::jwt::Validator::default();
-                            if validator.call(&token) {
-                                meta.subject = Some(token.sub);
-                                Ok(())
-                            } else {
-                                debug!("Invalid JWT");
-                                Err(())
-                            }
-                        }
-                        Err(e) => {
-                            error!("{}", e);
-                            Err(())
-                        }
+            use {extract_authorization_header, Authorization};
+
+            let db = meta.db.clone().unwrap();
+            let subject = match extract_authorization_header(req.headers()) {
+                Ok(Some(Authorization::Bearer(value))) => match decode_access_token(value) {
+                    Ok(token) => {
+                        let scopes = token.scopes();
+                        let f = ensure_not_revoked(&db, token).map(|sub| (Some(sub), None, scopes));
+                        Either::A(Either::A(f))
                     }
+                    Err(()) => Either::B(future::err(())),
+                },
+                Ok(Some(Authorization::ApiKey(value))) => {
+                    let f = authenticate_api_key(&db, value)
+                        .map(|(sub, allowed_ns_ids)| (Some(sub), allowed_ns_ids, None));
+                    Either::A(Either::B(f))
                 }
-                Ok(None) => Ok(()),
-                Err(_) => Err(()),
+                Ok(None) => Either::B(future::ok((None, None, None))),
+                Err(()) => Either::B(future::err(())),
             };
 
-            if res.is_ok() {
-                Either::A(
-                    req.state()
-                        .rpc_server
-                        .handle_rpc_request(request, meta)
-                        .map_err(|_| actix_web::error::ErrorInternalServerError("")),
-                )
-            } else {
-                Either::B(
+            subject.then(move |res| match res {
+                Ok((subject, allowed_namespace_ids, scopes)) => {
+                    meta.subject = subject;
+                    meta.allowed_namespace_ids = allowed_namespace_ids;
+                    meta.scopes = scopes;
+                    Either::A(
+                        req.state()
+                            .rpc_server
+                            .handle_rpc_request(request, meta)
+                            .map_err(|_| actix_web::error::ErrorInternalServerError("")),
+                    )
+                }
+                Err(()) => Either::B(
                     reject_request(&request)
                         .map_err(|_| actix_web::error::ErrorInternalServerError("")),
-                )
-            }
+                ),
+            })
         })
         .then(|res| {
             res.or_else(|_| {
@@ -221,6 +310,85 @@ pub fn index(
         })
 }
 
+fn decode_access_token(value: &str) -> ::std::result::Result<authn::jwt::AccessToken, ()> {
+    let raw_token = authn::jwt::RawToken {
+        kind: authn::jwt::RawTokenKind::Iam,
+        value,
+    };
+    let token = authn::jwt::AccessToken::decode(&raw_token).map_err(|e| error!("{}", e))?;
+
+    let validator = authn::jwt::Validator::default();
+    validator.call(&token).map_err(|e| debug!("Invalid JWT: {}", e))?;
+
+    Ok(token)
+}
+
+/// Resolves to the subject of the token unless the token has been revoked.
+fn ensure_not_revoked(
+    db: &Addr<DbExecutor>,
+    token: authn::jwt::AccessToken,
+) -> impl Future<Item = Uuid, Error = ()> {
+    let msg = revoked_access_token::find::IsRevoked {
+        jti: token.jti(),
+        account_id: token.sub,
+        issued_at: token.iat,
+    };
+
+    db.send(msg).then(move |res| match res {
+        Ok(Ok(false)) => Ok(token.sub),
+        Ok(Ok(true)) => {
+            debug!("Revoked JWT: {:?}", token);
+            Err(())
+        }
+        Ok(Err(e)) => {
+            error!("{}", e);
+            Err(())
+        }
+        Err(e) => {
+            error!("{}", e);
+            Err(())
+        }
+    })
+}
+
+/// Resolves to the account of the key along with the namespaces the key is restricted to.
+fn authenticate_api_key(
+    db: &Addr<DbExecutor>,
+    value: &str,
+) -> impl Future<Item = (Uuid, Option<Vec<Uuid>>), Error = ()> {
+    let (id, secret) = match ApiKey::parse(value) {
+        Some((id, secret)) => (id, secret.to_owned()),
+        None => {
+            debug!("Bad API key");
+            return Either::A(future::err(()));
+        }
+    };
+
+    let msg = api_key::find::FindWithAccount(id);
+    let f = db.send(msg).then(move |res| match res {
+        Ok(Ok((key, account))) => {
+            if !key.verify(&secret) || key.is_expired() {
+                debug!("Invalid API key: {}", key.id);
+                Err(())
+            } else if account.disabled_at.is_some() || account.deleted_at.is_some() {
+                debug!("API key of disabled account: {}", key.id);
+                Err(())
+            } else {
+                Ok((key.account_id, key.namespace_ids))
+            }
+        }
+        Ok(Err(e)) => {
+            debug!("API key lookup failed: {}", e);
+            Err(())
+        }
+        Err(e) => {
+            error!("{}", e);
+            Err(())
+        }
+    });
+    Either::B(f)
+}
+
 fn reject_request(
     request: &jsonrpc::Request,
 ) -> impl Future<Item = Option<jsonrpc::Response>, Error = ()> {
@@ -263,12 +431,18 @@ fn reject_call(call: &jsonrpc::Call) -> impl Future<Item = Option<jsonrpc::Outpu
 
 fn authorize_collection(
     db: &Addr<DbExecutor>,
+    allowed_ns_ids: &Option<Vec<Uuid>>,
     ns_id: Uuid,
     subject_id: Uuid,
     collection: CollectionKind,
     operation: OperationKind,
 ) -> impl Future<Item = (), Error = Error> {
     use settings;
+
+    if let Err(e) = ensure_namespace_allowed(allowed_ns_ids, ns_id) {
+        return Either::A(future::err(e));
+    }
+
     let iam_namespace_id = settings::iam_namespace_id();
 
     let subject = AbacAttribute::new(iam_namespace_id, UriKind::Account(subject_id));
@@ -283,7 +457,40 @@ fn authorize_collection(
         action: vec![action],
     };
 
-    db.send(msg).from_err().and_then(ensure_authorized)
+    let f = db.send(msg).from_err().and_then(ensure_authorized);
+    Either::B(f)
+}
+
+/// Any account is allowed to manage its own sessions and tokens,
+/// the ones of other accounts require a permission on the account.
+fn authorize_account(
+    db: &Addr<DbExecutor>,
+    subject_id: Uuid,
+    account_id: Uuid,
+    operation: OperationKind,
+) -> impl Future<Item = (), Error = Error> {
+    use settings;
+
+    if subject_id == account_id {
+        return Either::A(future::ok(()));
+    }
+
+    let iam_namespace_id = settings::iam_namespace_id();
+    let msg = Authz {
+        namespace_ids: vec![iam_namespace_id],
+        subject: vec![AbacAttribute::new(
+            iam_namespace_id,
+            UriKind::Account(subject_id),
+        )],
+        object: vec![AbacAttribute::new(
+            iam_namespace_id,
+            UriKind::Account(account_id),
+        )],
+        action: vec![AbacAttribute::new(iam_namespace_id, operation)],
+    };
+
+    let f = db.send(msg).from_err().and_then(ensure_authorized);
+    Either::B(f)
 }
 
 #[cfg(test)]
@@ -392,7 +599,28 @@ mod tests {
                 key: "role".to_owned(),
                 value: "client".to_owned(),
             },
+            recursive: false,
         };
         assert_eq!(f, expected);
     }
+
+    #[test]
+    fn deserialize_recursive_tree_request_filter() {
+        let s = r#"{
+            "direction": "outbound",
+            "attribute": {
+                "namespace_id": "bab37008-3dc5-492c-af73-80c241241d71",
+                "key": "uri",
+                "value": "account/25a0c367-756a-42e1-ac5a-e7a2b6b64420"
+            },
+            "recursive": true
+        }"#;
+
+        let f = serde_json::from_str::<TreeRequestFilter>(s);
+        assert!(f.is_ok());
+
+        let f = f.unwrap();
+        assert_eq!(f.direction, DirectionKind::Outbound);
+        assert!(f.recursive);
+    }
 }
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use actors::db::client_credential;
+use models::Secret;
+use rpc;
+use settings;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Request {}
+
+pub type Response = rpc::Response<Uuid, ResponseData>;
+
+/// `client_secret` is only returned here, it can't be retrieved later on.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResponseData {
+    pub client_id: Uuid,
+    pub client_secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub fn call(meta: rpc::Meta, _req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    use abac_attribute::{CollectionKind, OperationKind};
+    use rpc::authorize_collection;
+
+    let collection = CollectionKind::Account;
+    let operation = OperationKind::Create;
+
+    future::result(rpc::forbid_anonymous(meta.subject))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            let allowed_ns_ids = meta.allowed_namespace_ids.clone();
+            let ns_id = settings::iam_namespace_id();
+            move |subject_id| {
+                authorize_collection(
+                    &db,
+                    &allowed_ns_ids,
+                    ns_id,
+                    subject_id,
+                    collection,
+                    operation,
+                )
+            }
+        })
+        .and_then(|_| Secret::generate().map_err(|_| rpc::Error::InternalError))
+        .and_then({
+            let db = meta.db.unwrap();
+            move |secret| {
+                let msg = client_credential::insert::InsertWithAccount {
+                    secret_hash: secret.hash.clone(),
+                };
+                db.send(msg).from_err().and_then(move |res| {
+                    debug!("service account insert res: {:?}", res);
+                    let (credential, account) = res?;
+                    Ok(Response {
+                        id: account.id,
+                        data: ResponseData {
+                            client_id: credential.id,
+                            client_secret: secret.value,
+                            created_at: credential.created_at,
+                        },
+                    })
+                })
+            }
+        })
+}
@@ -0,0 +1,28 @@
+use futures::Future;
+use jsonrpc::BoxFuture;
+
+use rpc;
+
+mod create;
+
+const WRITE_SCOPE: &str = "service_account:write";
+
+build_rpc_trait! {
+    pub trait Rpc {
+        type Metadata;
+
+        #[rpc(meta, name = "service_account.create")]
+        fn create(&self, Self::Metadata, create::Request) -> BoxFuture<create::Response>;
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct RpcImpl;
+
+impl Rpc for RpcImpl {
+    type Metadata = rpc::Meta;
+
+    fn create(&self, meta: rpc::Meta, req: create::Request) -> BoxFuture<create::Response> {
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| create::call(meta, req)).from_err())
+    }
+}
@@ -0,0 +1,40 @@
+use futures::future::{self, Future};
+
+use abac_attribute::OperationKind;
+use actors::db::refresh_token;
+use rpc;
+
+pub type Request = rpc::session::read::Request;
+pub type Response = rpc::session::read::Response;
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                let msg = refresh_token::find::Find(req.id);
+                db.send(msg).from_err().and_then(move |res| {
+                    debug!("session find res: {:?}", res);
+                    Ok((res?, subject_id))
+                })
+            }
+        })
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |(token, subject_id)| {
+                let account_id = token.account_id;
+                rpc::authorize_account(&db, subject_id, account_id, OperationKind::Update)
+                    .and_then(move |_| Ok(token.id))
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |id| {
+                let msg = refresh_token::delete::Delete(id);
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("session delete res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
@@ -0,0 +1,45 @@
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use abac_attribute::OperationKind;
+use actors::db::refresh_token;
+use rpc;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Filter {
+    pub account_id: Uuid,
+}
+
+pub type Request = rpc::ListRequest<Filter>;
+pub type Response = rpc::ListResponse<rpc::session::read::Response>;
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    let account_id = req.filter.account_id;
+
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                rpc::authorize_account(&db, subject_id, account_id, OperationKind::Read)
+            }
+        })
+        .and_then({
+            let limit = req.pagination.limit;
+            move |_| rpc::pagination::check_limit(limit)
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |_| {
+                let msg = refresh_token::select::Select {
+                    account_id,
+                    limit: req.pagination.limit,
+                    offset: req.pagination.offset,
+                };
+                db.send(msg).from_err().and_then(|res| {
+                    debug!("session select res: {:?}", res);
+                    Ok(Response::from(res?))
+                })
+            }
+        })
+}
@@ -0,0 +1,45 @@
+use futures::Future;
+use jsonrpc::BoxFuture;
+
+use rpc;
+
+mod delete;
+mod list;
+mod read;
+
+const READ_SCOPE: &str = "session:read";
+const WRITE_SCOPE: &str = "session:write";
+
+build_rpc_trait! {
+    pub trait Rpc {
+        type Metadata;
+
+        #[rpc(meta, name = "session.read")]
+        fn read(&self, Self::Metadata, read::Request) -> BoxFuture<read::Response>;
+
+        #[rpc(meta, name = "session.delete")]
+        fn delete(&self, Self::Metadata, delete::Request) -> BoxFuture<delete::Response>;
+
+        #[rpc(meta, name = "session.list")]
+        fn list(&self, Self::Metadata, list::Request) -> BoxFuture<list::Response>;
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct RpcImpl;
+
+impl Rpc for RpcImpl {
+    type Metadata = rpc::Meta;
+
+    fn read(&self, meta: rpc::Meta, req: read::Request) -> BoxFuture<read::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| read::call(meta, req)).from_err())
+    }
+
+    fn delete(&self, meta: rpc::Meta, req: delete::Request) -> BoxFuture<delete::Response> {
+        Box::new(rpc::scoped(meta, WRITE_SCOPE, |meta| delete::call(meta, req)).from_err())
+    }
+
+    fn list(&self, meta: rpc::Meta, req: list::Request) -> BoxFuture<list::Response> {
+        Box::new(rpc::scoped(meta, READ_SCOPE, |meta| list::call(meta, req)).from_err())
+    }
+}
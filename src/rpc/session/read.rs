@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use futures::future::{self, Future};
+use uuid::Uuid;
+
+use abac_attribute::OperationKind;
+use actors::db::refresh_token;
+use models::RefreshToken;
+use rpc;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub id: Uuid,
+}
+
+pub type Response = rpc::Response<Uuid, ResponseData>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResponseData {
+    pub account_id: Uuid,
+    pub aud: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<RefreshToken> for Response {
+    fn from(token: RefreshToken) -> Self {
+        Response {
+            id: token.id,
+            data: ResponseData {
+                account_id: token.account_id,
+                aud: token.aud,
+                created_at: token.created_at,
+                last_used_at: token.last_used_at,
+            },
+        }
+    }
+}
+
+pub fn call(meta: rpc::Meta, req: Request) -> impl Future<Item = Response, Error = rpc::Error> {
+    future::result(rpc::forbid_restricted(&meta))
+        .and_then({
+            let db = meta.db.clone().unwrap();
+            move |subject_id| {
+                let msg = refresh_token::find::Find(req.id);
+                db.send(msg).from_err().and_then(move |res| {
+                    debug!("session find res: {:?}", res);
+                    Ok((res?, subject_id))
+                })
+            }
+        })
+        .and_then({
+            let db = meta.db.unwrap();
+            move |(token, subject_id)| {
+                let account_id = token.account_id;
+                rpc::authorize_account(&db, subject_id, account_id, OperationKind::Read)
+                    .and_then(|_| Ok(Response::from(token)))
+            }
+        })
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+
+use rpc::{Error, Result};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Validity {
+    pub not_before: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub fn check(validity: Validity) -> Result<()> {
+    match (validity.not_before, validity.expires_at) {
+        (Some(not_before), Some(expires_at)) if not_before >= expires_at => Err(Error::BadRequest),
+        _ => Ok(()),
+    }
+}
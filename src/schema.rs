@@ -1,9 +1,88 @@
+// ABAC tables are created by the abac-rs migrations, these definitions additionally
+// cover the columns added by the local ones.
+table! {
+    use abac::sql_types::AbacAttribute;
+    use diesel::sql_types::*;
+
+    abac_policy (subject, object, action, namespace_id) {
+        subject -> Array<AbacAttribute>,
+        object -> Array<AbacAttribute>,
+        action -> Array<AbacAttribute>,
+        namespace_id -> Uuid,
+        created_at -> Timestamptz,
+        effect -> Text,
+        not_before -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    use abac::sql_types::AbacAttribute;
+    use diesel::sql_types::*;
+
+    abac_subject (inbound, outbound) {
+        inbound -> AbacAttribute,
+        outbound -> AbacAttribute,
+        created_at -> Timestamptz,
+        not_before -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    use abac::sql_types::AbacAttribute;
+    use diesel::sql_types::*;
+
+    abac_object (inbound, outbound) {
+        inbound -> AbacAttribute,
+        outbound -> AbacAttribute,
+        created_at -> Timestamptz,
+        not_before -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    use abac::sql_types::AbacAttribute;
+    use diesel::sql_types::*;
+
+    abac_action (inbound, outbound) {
+        inbound -> AbacAttribute,
+        outbound -> AbacAttribute,
+        created_at -> Timestamptz,
+        not_before -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+    }
+}
+
 table! {
     account (id) {
         id -> Uuid,
         constraints -> Jsonb,
         disabled_at -> Nullable<Timestamptz>,
         deleted_at -> Nullable<Timestamptz>,
+        tokens_revoked_before -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    api_key (id) {
+        id -> Uuid,
+        account_id -> Uuid,
+        label -> Text,
+        secret_hash -> Bytea,
+        namespace_ids -> Nullable<Array<Uuid>>,
+        expires_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    client_credential (id) {
+        id -> Uuid,
+        account_id -> Uuid,
+        secret_hash -> Bytea,
+        created_at -> Timestamptz,
     }
 }
 
@@ -28,17 +107,42 @@ table! {
 }
 
 table! {
-    refresh_token (account_id) {
+    refresh_token (id) {
         account_id -> Uuid,
         algorithm -> Text,
         keys -> Array<Bytea>,
         created_at -> Timestamptz,
+        jti -> Uuid,
+        id -> Uuid,
+        aud -> Text,
+        last_used_at -> Nullable<Timestamptz>,
+        scope -> Nullable<Text>,
+    }
+}
+
+table! {
+    revoked_access_token (jti, account_id) {
+        jti -> Uuid,
+        account_id -> Uuid,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
     }
 }
 
+joinable!(api_key -> account (account_id));
+joinable!(client_credential -> account (account_id));
 joinable!(identity -> account (account_id));
 joinable!(identity -> namespace (provider));
 joinable!(namespace -> account (account_id));
 joinable!(refresh_token -> account (account_id));
+joinable!(revoked_access_token -> account (account_id));
 
-allow_tables_to_appear_in_same_query!(account, identity, namespace, refresh_token,);
+allow_tables_to_appear_in_same_query!(
+    account,
+    api_key,
+    client_credential,
+    identity,
+    namespace,
+    refresh_token,
+    revoked_access_token,
+);
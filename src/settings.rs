@@ -9,6 +9,10 @@ use std::path::PathBuf;
 use std::sync::RwLock;
 
 use authn;
+use authn::jwt::Algorithm;
+
+/// Identifier of the token key configured in the legacy single key form.
+pub const LEGACY_KID: &str = "default";
 
 lazy_static! {
     #[allow(missing_debug_implementations)]
@@ -26,33 +30,114 @@ macro_rules! get_settings {
 #[derive(Debug, Default, Deserialize)]
 pub struct Settings {
     pub iam_namespace_id: Uuid,
-    pub authentication: Authentication,
+    /// Public key of the legacy single key form of `tokens`.
+    #[serde(default)]
+    pub authentication: Option<Authentication>,
     pub tokens: Tokens,
     pub providers: BTreeMap<authn::AuthKey, Provider>,
+    #[serde(default)]
+    pub jwks: Jwks,
     pub pagination: Pagination,
+    #[serde(default)]
+    pub sweeper: Sweeper,
+    #[serde(default)]
+    pub decision_cache: DecisionCache,
 }
 
 #[derive(Debug, Default, Deserialize)]
 pub struct Authentication {
     pub keyfile: PathBuf,
-    #[serde(skip_deserializing)]
-    pub key: String,
 }
 
+/// Either `keys` with `active_kid` or, in the legacy form, the single private `keyfile`
+/// along with the public `authentication.keyfile`. The latter is taken for the only key
+/// of the keyring, identified by `LEGACY_KID`.
 #[derive(Debug, Default, Deserialize)]
 pub struct Tokens {
-    pub keyfile: PathBuf,
-    #[serde(skip_deserializing)]
-    pub key: String,
+    /// Identifier of the key used to sign access tokens.
+    #[serde(default)]
+    pub active_kid: String,
+    #[serde(default)]
+    pub algorithm: Algorithm,
+    #[serde(default)]
+    pub keys: Vec<TokenKey>,
+    #[serde(default)]
+    pub keyfile: Option<PathBuf>,
     pub expires_in: u16,
     pub expires_in_max: u16,
+    /// Previous refresh token keys still accepted after rotation.
+    #[serde(default = "default_refresh_keys_kept")]
+    pub refresh_keys_kept: u16,
+    /// Sessions not used for that long are expired, in seconds.
+    #[serde(default = "default_session_expires_in")]
+    pub session_expires_in: u32,
+}
+
+fn default_refresh_keys_kept() -> u16 {
+    2
+}
+
+fn default_session_expires_in() -> u32 {
+    // 30 days.
+    2_592_000
+}
+
+impl Tokens {
+    pub fn active_key(&self) -> Option<&TokenKey> {
+        self.find_key(&self.active_kid)
+    }
+
+    pub fn find_key(&self, kid: &str) -> Option<&TokenKey> {
+        self.keys.iter().find(|key| key.kid == kid)
+    }
+}
+
+/// Keys other than the active one are only used to verify access tokens,
+/// so their private keys may be omitted.
+#[derive(Debug, Default, Deserialize)]
+pub struct TokenKey {
+    pub kid: String,
+    pub private_keyfile: Option<PathBuf>,
+    pub public_keyfile: PathBuf,
+    #[serde(skip_deserializing)]
+    pub private_key: Option<String>,
+    #[serde(skip_deserializing)]
+    pub public_key: String,
 }
 
+/// Either `keyfile` read once or `jwks_url` fetched every `jwks.refresh_interval`.
 #[derive(Debug, Default, Deserialize)]
 pub struct Provider {
-    pub keyfile: PathBuf,
+    #[serde(default)]
+    pub algorithm: Algorithm,
+    pub keyfile: Option<PathBuf>,
+    pub jwks_url: Option<String>,
+    /// Expected `iss` of client tokens, the provider itself if empty.
+    #[serde(default)]
+    pub issuers: Vec<String>,
+    /// Expected `aud` of client tokens, IAM itself if empty.
+    #[serde(default)]
+    pub audiences: Vec<String>,
+    /// Allowed clock skew in seconds.
+    #[serde(default)]
+    pub leeway: u32,
     #[serde(skip_deserializing)]
-    pub key: String,
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Jwks {
+    /// In seconds.
+    pub refresh_interval: u64,
+}
+
+impl Default for Jwks {
+    fn default() -> Self {
+        Jwks {
+            refresh_interval: 300,
+        }
+    }
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -61,6 +146,37 @@ pub struct Pagination {
     pub limit_max: u16,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Sweeper {
+    /// In seconds.
+    pub interval: u64,
+}
+
+impl Default for Sweeper {
+    fn default() -> Self {
+        Sweeper { interval: 60 }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct DecisionCache {
+    /// Zero disables the cache.
+    pub size: usize,
+    /// In seconds.
+    pub ttl: u64,
+}
+
+impl Default for DecisionCache {
+    fn default() -> Self {
+        DecisionCache {
+            size: 10_000,
+            ttl: 5,
+        }
+    }
+}
+
 pub fn init() -> Result<(), failure::Error> {
     info!("Initializing settings");
     let mut settings = SETTINGS.write().unwrap();
@@ -69,15 +185,70 @@ pub fn init() -> Result<(), failure::Error> {
     c.merge(File::with_name("Settings.toml"))?;
     *settings = c.try_into::<Settings>()?;
 
-    let mut file = fs::File::open(&settings.authentication.keyfile)?;
-    file.read_to_string(&mut settings.authentication.key)?;
+    if let Some(private_keyfile) = settings.tokens.keyfile.take() {
+        if !settings.tokens.keys.is_empty() {
+            return Err(failure::err_msg(
+                "Either tokens.keyfile or tokens.keys must be set",
+            ));
+        }
+
+        let public_keyfile = match settings.authentication {
+            Some(ref authentication) => authentication.keyfile.clone(),
+            None => return Err(failure::err_msg("authentication.keyfile is missing")),
+        };
 
-    let mut file = fs::File::open(&settings.tokens.keyfile)?;
-    file.read_to_string(&mut settings.tokens.key)?;
+        settings.tokens.active_kid = LEGACY_KID.to_owned();
+        settings.tokens.keys.push(TokenKey {
+            kid: LEGACY_KID.to_owned(),
+            private_keyfile: Some(private_keyfile),
+            public_keyfile,
+            ..Default::default()
+        });
+    }
+
+    if settings.sweeper.interval == 0 {
+        return Err(failure::err_msg("sweeper.interval must be positive"));
+    }
+
+    if settings.jwks.refresh_interval == 0 {
+        return Err(failure::err_msg("jwks.refresh_interval must be positive"));
+    }
+
+    if settings.decision_cache.size > 0 && settings.decision_cache.ttl == 0 {
+        return Err(failure::err_msg("decision_cache.ttl must be positive"));
+    }
+
+    for key in &mut settings.tokens.keys {
+        let mut file = fs::File::open(&key.public_keyfile)?;
+        file.read_to_string(&mut key.public_key)?;
+
+        if let Some(ref keyfile) = key.private_keyfile {
+            let mut private_key = String::new();
+            let mut file = fs::File::open(keyfile)?;
+            file.read_to_string(&mut private_key)?;
+            key.private_key = Some(private_key);
+        }
+    }
+
+    match settings.tokens.active_key() {
+        Some(key) if key.private_key.is_some() => {}
+        _ => return Err(failure::err_msg("Private key of the active token key is missing")),
+    }
 
-    for provider in settings.providers.values_mut() {
-        let mut file = fs::File::open(&provider.keyfile)?;
-        file.read_to_string(&mut provider.key)?;
+    for (auth_key, provider) in &mut settings.providers {
+        match (&provider.keyfile, &provider.jwks_url) {
+            (Some(keyfile), None) => {
+                let mut key = String::new();
+                let mut file = fs::File::open(keyfile)?;
+                file.read_to_string(&mut key)?;
+                provider.key = Some(key);
+            }
+            (None, Some(_)) => {}
+            _ => {
+                let msg = format!("Either keyfile or jwks_url of {} must be set", auth_key);
+                return Err(failure::err_msg(msg));
+            }
+        }
     }
 
     Ok(())
@@ -19,11 +19,13 @@ lazy_static! {
         let template = r#"{
             "jsonrpc": "2.0",
             "result": {
+                "expires_at": null,
                 "inbound": {
                     "key": "operation",
                     "namespace_id": "FOXFORD_NAMESPACE_ID",
                     "value": "OPERATION"
                 },
+                "not_before": null,
                 "outbound": {
                     "key": "operation",
                     "namespace_id": "IAM_NAMESPACE_ID",
@@ -160,6 +162,70 @@ mod with_client {
     }
 }
 
+#[test]
+fn cannot_create_record_closing_cycle() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+
+        let record = build_record();
+        diesel::insert_into(abac_action::table)
+            .values(NewAbacAction {
+                inbound: record.outbound,
+                outbound: record.inbound,
+            })
+            .execute(&conn)
+            .unwrap();
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    let template = r#"{
+        "jsonrpc": "2.0",
+        "error": {
+            "code": 409,
+            "message": "Link would create a cycle",
+            "data": {
+                "path": [
+                    {
+                        "key": "operation",
+                        "namespace_id": "FOXFORD_NAMESPACE_ID",
+                        "value": "OPERATION"
+                    },
+                    {
+                        "key": "operation",
+                        "namespace_id": "IAM_NAMESPACE_ID",
+                        "value": "any"
+                    },
+                    {
+                        "key": "operation",
+                        "namespace_id": "FOXFORD_NAMESPACE_ID",
+                        "value": "OPERATION"
+                    }
+                ]
+            }
+        },
+        "id": "qwerty"
+    }"#;
+    let json = template
+        .replace("IAM_NAMESPACE_ID", &IAM_NAMESPACE_ID.to_string())
+        .replace("OPERATION", &OPERATION.to_string())
+        .replace("FOXFORD_NAMESPACE_ID", &FOXFORD_NAMESPACE_ID.to_string());
+    assert_eq!(body, shared::strip_json(&json));
+
+    {
+        let conn = get_conn!(pool);
+        assert_eq!(find_record(&conn), Ok(0));
+    }
+}
+
 #[test]
 fn anonymous_cannot_create_record() {
     let shared::Server { mut srv, pool } = shared::build_server();
@@ -18,11 +18,13 @@ lazy_static! {
         let template = r#"{
             "jsonrpc": "2.0",
             "result": {
+                "expires_at": null,
                 "inbound": {
                     "key": "operation",
                     "namespace_id": "FOXFORD_NAMESPACE_ID",
                     "value": "OPERATION"
                 },
+                "not_before": null,
                 "outbound": {
                     "key": "operation",
                     "namespace_id": "IAM_NAMESPACE_ID",
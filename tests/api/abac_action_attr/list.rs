@@ -62,11 +62,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "operation",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "create"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "operation",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -74,11 +76,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "operation",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "read"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "operation",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -86,11 +90,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "action",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "create"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "action",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -126,11 +132,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "operation",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
                         "value": "create"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "operation",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -207,11 +215,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "operation",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
                         "value": "create"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "operation",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -259,11 +269,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "action",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "create"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "action",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -311,11 +323,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "action",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "create"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "action",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -364,11 +378,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "operation",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "create"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "operation",
                             "namespace_id": "IAM_NAMESPACE_ID",
@@ -408,11 +424,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "operation",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "read"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "operation",
                             "namespace_id": "IAM_NAMESPACE_ID",
@@ -420,11 +438,13 @@ mod with_client {
                         }
                     },
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "action",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "create"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "action",
                             "namespace_id": "IAM_NAMESPACE_ID",
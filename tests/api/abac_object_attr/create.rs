@@ -21,11 +21,13 @@ lazy_static! {
         let template = r#"{
             "jsonrpc": "2.0",
             "result": {
+                "expires_at": null,
                 "inbound": {
                     "key": "uri",
                     "namespace_id": "FOXFORD_NAMESPACE_ID",
                     "value": "webinar/WEBINAR_ID"
                 },
+                "not_before": null,
                 "outbound": {
                     "key": "uri",
                     "namespace_id": "IAM_NAMESPACE_ID",
@@ -173,11 +175,13 @@ mod when_storage_use_case {
             let template = r#"{
                 "jsonrpc": "2.0",
                 "result": {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "STORAGE_NAMESPACE_ID",
                         "value": "SET_URI"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -1,4 +1,5 @@
 use actix_web::HttpMessage;
+use chrono::{Duration, Utc};
 use diesel::{self, prelude::*};
 use serde_json;
 use uuid::Uuid;
@@ -7,7 +8,8 @@ use abac::prelude::*;
 use abac::schema::*;
 
 use iam::abac_attribute::{CollectionKind, OperationKind, UriKind};
-use iam::models::{Account, Namespace};
+use iam::models::{Account, Namespace, NewAbacObject as NewLink};
+use iam::schema::abac_object as link;
 
 use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
 use shared::{
@@ -77,11 +79,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "webinar/1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "type",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -89,11 +93,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "webinar/2"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "type",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -101,11 +107,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "STORAGE_NAMESPACE_ID",
                         "value": "bucket-1/set-1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -113,11 +121,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "webinar/1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "kind",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -153,11 +163,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
                         "value": "webinar/1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "type",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
@@ -233,11 +245,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
                         "value": "webinar/1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "type",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
@@ -272,11 +286,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "STORAGE_NAMESPACE_ID",
                         "value": "bucket-1/set-1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -324,11 +340,84 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "webinar/1"
                     },
+                    "not_before": null,
+                    "outbound": {
+                        "key": "kind",
+                        "namespace_id": "FOXFORD_NAMESPACE_ID",
+                        "value": "math"
+                    }
+                }
+            ],
+            "id": "qwerty"
+        }"#;
+        let resp_json =
+            resp_template.replace("FOXFORD_NAMESPACE_ID", &FOXFORD_NAMESPACE_ID.to_string());
+        assert_eq!(body, shared::strip_json(&resp_json));
+    }
+
+    #[test]
+    fn cannot_list_expired_records() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+
+            diesel::insert_into(link::table)
+                .values(NewLink {
+                    inbound: AbacAttribute {
+                        namespace_id: *FOXFORD_NAMESPACE_ID,
+                        key: "uri".to_owned(),
+                        value: "webinar/2".to_owned(),
+                    },
+                    outbound: AbacAttribute {
+                        namespace_id: *FOXFORD_NAMESPACE_ID,
+                        key: "kind".to_owned(),
+                        value: "physics".to_owned(),
+                    },
+                    not_before: None,
+                    expires_at: Some(Utc::now() - Duration::hours(1)),
+                })
+                .execute(&conn)
+                .unwrap();
+        }
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "abac_object_attr.list",
+            "params": [{
+                "filter": {
+                    "namespace_ids": vec![*FOXFORD_NAMESPACE_ID],
+                    "key": "kind",
+                }
+            }],
+            "id": "qwerty"
+        });
+
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*FOXFORD_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_template = r#"{
+            "jsonrpc": "2.0",
+            "result": [
+                {
+                    "expires_at": null,
+                    "inbound": {
+                        "key": "uri",
+                        "namespace_id": "FOXFORD_NAMESPACE_ID",
+                        "value": "webinar/1"
+                    },
+                    "not_before": null,
                     "outbound": {
                         "key": "kind",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -376,11 +465,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "webinar/1"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "type",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -420,11 +511,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "webinar/2"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "type",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -432,11 +525,13 @@ mod with_client {
                         }
                     },
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "STORAGE_NAMESPACE_ID",
                             "value": "bucket-1/set-1"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "uri",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -476,11 +571,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "webinar/1"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "kind",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
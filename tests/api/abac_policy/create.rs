@@ -27,7 +27,10 @@ lazy_static! {
                         "value": "any"
                     }
                 ],
+                "effect": "allow",
+                "expires_at": null,
                 "namespace_id": "FOXFORD_NAMESPACE_ID",
+                "not_before": null,
                 "object": [
                     {
                         "key": "uri",
@@ -173,6 +176,71 @@ mod with_client {
     }
 }
 
+mod with_validity_window {
+    use super::*;
+
+    #[test]
+    fn can_create_record() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        let payload =
+            build_request_with_validity_window("2030-01-01T00:00:00Z", "2030-02-01T00:00:00Z");
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*FOXFORD_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["result"]["not_before"], json!("2030-01-01T00:00:00Z"));
+        assert_eq!(body["result"]["expires_at"], json!("2030-02-01T00:00:00Z"));
+
+        {
+            let conn = get_conn!(pool);
+            assert_eq!(find_record(&conn), Ok(1));
+        }
+    }
+
+    #[test]
+    fn cannot_create_record_with_inverted_window() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        let payload =
+            build_request_with_validity_window("2030-02-01T00:00:00Z", "2030-01-01T00:00:00Z");
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*FOXFORD_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::BAD_REQUEST);
+
+        {
+            let conn = get_conn!(pool);
+            assert_eq!(find_record(&conn), Ok(0));
+        }
+    }
+
+    fn build_request_with_validity_window(not_before: &str, expires_at: &str) -> serde_json::Value {
+        let mut payload = build_request();
+        payload["params"][0]["not_before"] = json!(not_before);
+        payload["params"][0]["expires_at"] = json!(expires_at);
+        payload
+    }
+}
+
 #[test]
 fn anonymous_cannot_create_record() {
     let shared::Server { mut srv, pool } = shared::build_server();
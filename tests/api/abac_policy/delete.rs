@@ -26,7 +26,10 @@ lazy_static! {
                         "value": "any"
                     }
                 ],
+                "effect": "allow",
+                "expires_at": null,
                 "namespace_id": "FOXFORD_NAMESPACE_ID",
+                "not_before": null,
                 "object": [
                     {
                         "key": "uri",
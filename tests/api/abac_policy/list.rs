@@ -75,7 +75,10 @@ mod with_admin {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "IAM_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "uri",
@@ -99,7 +102,10 @@ mod with_admin {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "IAM_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "uri",
@@ -123,7 +129,10 @@ mod with_admin {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "IAM_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "uri",
@@ -147,7 +156,10 @@ mod with_admin {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "NETOLOGY_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "type",
@@ -176,6 +188,29 @@ mod with_admin {
             .replace("IAM_NAMESPACE_ID", &IAM_NAMESPACE_ID.to_string());
         assert_eq!(body, shared::strip_json(&resp_json));
     }
+
+    #[test]
+    fn cannot_list_records_beyond_api_key_namespaces() {
+        use shared::db::create_api_key;
+
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        let api_key = {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+            let namespace_ids = Some(vec![*FOXFORD_NAMESPACE_ID]);
+            create_api_key(&conn, *IAM_ACCOUNT_ID, namespace_ids, None).1
+        };
+
+        let req = shared::build_api_key_request(
+            &srv,
+            serde_json::to_string(&build_request(&[*NETOLOGY_NAMESPACE_ID])).unwrap(),
+            &api_key,
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::FORBIDDEN);
+    }
 }
 
 mod with_client {
@@ -208,7 +243,10 @@ mod with_client {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "FOXFORD_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "type",
@@ -232,7 +270,10 @@ mod with_client {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "FOXFORD_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "type",
@@ -286,7 +327,10 @@ mod with_client {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "NETOLOGY_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "type",
@@ -380,7 +424,10 @@ mod with_client {
                             "value": "any"
                         }
                     ],
+                    "effect": "allow",
+                    "expires_at": null,
                     "namespace_id": "NETOLOGY_NAMESPACE_ID",
+                    "not_before": null,
                     "object": [
                         {
                             "key": "type",
@@ -446,7 +493,10 @@ mod with_client {
                                 "value": "any"
                             }
                         ],
+                        "effect": "allow",
+                        "expires_at": null,
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
+                        "not_before": null,
                         "object": [
                             {
                                 "key": "type",
@@ -503,7 +553,10 @@ mod with_client {
                                 "value": "any"
                             }
                         ],
+                        "effect": "allow",
+                        "expires_at": null,
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
+                        "not_before": null,
                         "object": [
                             {
                                 "key": "type",
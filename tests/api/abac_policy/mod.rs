@@ -2,3 +2,4 @@ mod create;
 mod delete;
 mod list;
 mod read;
+mod simulate;
@@ -0,0 +1,225 @@
+use actix_web::HttpMessage;
+use diesel::{self, prelude::*};
+use serde_json;
+use uuid::Uuid;
+
+use abac::prelude::*;
+use abac::schema::*;
+
+use iam::models::{Account, Namespace};
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{
+    self, FOXFORD_ACCOUNT_ID, FOXFORD_NAMESPACE_ID, IAM_NAMESPACE_ID, NETOLOGY_ACCOUNT_ID,
+};
+
+lazy_static! {
+    static ref ACCOUNT_ID: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> ((Account, Namespace), (Account, Namespace)) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    (
+        (iam_account, iam_namespace),
+        (foxford_account, foxford_namespace),
+    )
+}
+
+#[test]
+fn can_simulate_record_creation() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let payload = build_request("abac_policy.create");
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&payload).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    let resp_json = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "read": {
+                "after": true,
+                "before": false
+            }
+        },
+        "id": "qwerty"
+    }"#;
+    assert_eq!(body, shared::strip_json(resp_json));
+
+    {
+        let conn = get_conn!(pool);
+        assert_eq!(find_record(&conn), Ok(0));
+    }
+}
+
+#[test]
+fn can_simulate_record_deletion() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+
+        diesel::insert_into(abac_policy::table)
+            .values(build_record())
+            .execute(&conn)
+            .unwrap();
+    }
+
+    let payload = build_request("abac_policy.delete");
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&payload).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    let resp_json = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "read": {
+                "after": false,
+                "before": true
+            }
+        },
+        "id": "qwerty"
+    }"#;
+    assert_eq!(body, shared::strip_json(resp_json));
+
+    {
+        let conn = get_conn!(pool);
+        assert_eq!(find_record(&conn), Ok(1));
+    }
+}
+
+#[test]
+fn cannot_simulate_alien_changes() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+        let _ = create_account(&conn, AccountKind::Netology);
+    }
+
+    let payload = build_request("abac_policy.create");
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&payload).unwrap(),
+        Some(*NETOLOGY_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn anonymous_cannot_simulate_changes() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let payload = build_request("abac_policy.create");
+    let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request(method: &str) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "abac_policy.simulate",
+        "params": [{
+            "changes": [
+                {
+                    "method": method,
+                    "params": build_record(),
+                }
+            ],
+            "checks": [
+                {
+                    "id": "read",
+                    "namespace_ids": [*FOXFORD_NAMESPACE_ID],
+                    "subject": [
+                        {
+                            "namespace_id": *IAM_NAMESPACE_ID,
+                            "key": "uri",
+                            "value": format!("account/{}", *ACCOUNT_ID),
+                        }
+                    ],
+                    "object": [
+                        {
+                            "namespace_id": *FOXFORD_NAMESPACE_ID,
+                            "key": "uri",
+                            "value": "room/1",
+                        }
+                    ],
+                    "action": [
+                        {
+                            "namespace_id": *FOXFORD_NAMESPACE_ID,
+                            "key": "operation",
+                            "value": "read",
+                        }
+                    ],
+                }
+            ],
+        }],
+        "id": "qwerty"
+    })
+}
+
+fn build_record() -> NewAbacPolicy {
+    NewAbacPolicy {
+        namespace_id: *FOXFORD_NAMESPACE_ID,
+        subject: vec![AbacAttribute {
+            namespace_id: *IAM_NAMESPACE_ID,
+            key: "uri".to_owned(),
+            value: format!("account/{}", *ACCOUNT_ID),
+        }],
+        object: vec![AbacAttribute {
+            namespace_id: *FOXFORD_NAMESPACE_ID,
+            key: "uri".to_owned(),
+            value: "room/1".to_owned(),
+        }],
+        action: vec![AbacAttribute {
+            namespace_id: *FOXFORD_NAMESPACE_ID,
+            key: "operation".to_owned(),
+            value: "read".to_owned(),
+        }],
+    }
+}
+
+fn find_record(conn: &PgConnection) -> diesel::QueryResult<usize> {
+    let policy = build_record();
+    abac_policy::table
+        .find((
+            policy.subject,
+            policy.object,
+            policy.action,
+            policy.namespace_id,
+        ))
+        .execute(conn)
+}
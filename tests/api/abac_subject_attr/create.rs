@@ -22,11 +22,13 @@ lazy_static! {
         let template = r#"{
             "jsonrpc": "2.0",
             "result": {
+                "expires_at": null,
                 "inbound": {
                     "key": "uri",
                     "namespace_id": "IAM_NAMESPACE_ID",
                     "value": "account/USER_ACCOUNT_ID"
                 },
+                "not_before": null,
                 "outbound": {
                     "key": "role",
                     "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -67,11 +67,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "IAM_NAMESPACE_ID",
                         "value": "account/FOXFORD_USER_ID_1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "role",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -79,11 +81,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "IAM_NAMESPACE_ID",
                         "value": "account/FOXFORD_USER_ID_2"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "role",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -91,11 +95,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "IAM_NAMESPACE_ID",
                         "value": "account/FOXFORD_USER_ID_1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "customer",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -103,11 +109,13 @@ mod with_client {
                     }
                 },
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "role",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
                         "value": "user"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "role",
                         "namespace_id": "IAM_NAMESPACE_ID",
@@ -145,11 +153,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "IAM_NAMESPACE_ID",
                         "value": "account/FOXFORD_USER_ID_1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "role",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
@@ -227,11 +237,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "IAM_NAMESPACE_ID",
                         "value": "account/FOXFORD_USER_ID_1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "role",
                         "namespace_id": "NETOLOGY_NAMESPACE_ID",
@@ -280,11 +292,13 @@ mod with_client {
             "jsonrpc": "2.0",
             "result": [
                 {
+                    "expires_at": null,
                     "inbound": {
                         "key": "uri",
                         "namespace_id": "IAM_NAMESPACE_ID",
                         "value": "account/FOXFORD_USER_ID_1"
                     },
+                    "not_before": null,
                     "outbound": {
                         "key": "customer",
                         "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -334,11 +348,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "IAM_NAMESPACE_ID",
                             "value": "account/FOXFORD_USER_ID_1"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "role",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -380,11 +396,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "IAM_NAMESPACE_ID",
                             "value": "account/FOXFORD_USER_ID_2"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "role",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -392,11 +410,13 @@ mod with_client {
                         }
                     },
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "uri",
                             "namespace_id": "IAM_NAMESPACE_ID",
                             "value": "account/FOXFORD_USER_ID_1"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "customer",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
@@ -438,11 +458,13 @@ mod with_client {
                 "jsonrpc": "2.0",
                 "result": [
                     {
+                        "expires_at": null,
                         "inbound": {
                             "key": "role",
                             "namespace_id": "FOXFORD_NAMESPACE_ID",
                             "value": "user"
                         },
+                        "not_before": null,
                         "outbound": {
                             "key": "role",
                             "namespace_id": "IAM_NAMESPACE_ID",
@@ -208,6 +208,67 @@ mod with_client {
         assert_eq!(body, shared::strip_json(&resp_json));
     }
 
+    #[test]
+    fn can_tree_own_records_recursively() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+
+            diesel::insert_into(abac_subject::table)
+                .values(NewAbacSubject {
+                    inbound: AbacAttribute {
+                        namespace_id: *IAM_NAMESPACE_ID,
+                        key: "role".to_owned(),
+                        value: "member".to_owned(),
+                    },
+                    outbound: AbacAttribute {
+                        namespace_id: *IAM_NAMESPACE_ID,
+                        key: "role".to_owned(),
+                        value: "guest".to_owned(),
+                    },
+                })
+                .execute(&conn)
+                .unwrap();
+        }
+
+        let attr = AbacAttribute {
+            namespace_id: *FOXFORD_NAMESPACE_ID,
+            key: "role".to_owned(),
+            value: "user".to_owned(),
+        };
+        let mut payload = build_request(DirectionKind::Outbound, &attr);
+        payload["params"][0]["filter"]["recursive"] = json!(true);
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*FOXFORD_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_template = r#"{
+            "jsonrpc": "2.0",
+            "result": [
+                {
+                    "distance": 1,
+                    "key": "role",
+                    "namespace_id": "IAM_NAMESPACE_ID",
+                    "value": "member"
+                },
+                {
+                    "distance": 2,
+                    "key": "role",
+                    "namespace_id": "IAM_NAMESPACE_ID",
+                    "value": "guest"
+                }
+            ],
+            "id": "qwerty"
+        }"#;
+        let resp_json = resp_template.replace("IAM_NAMESPACE_ID", &IAM_NAMESPACE_ID.to_string());
+        assert_eq!(body, shared::strip_json(&resp_json));
+    }
+
     #[test]
     fn can_get_tree_with_pagination() {
         let shared::Server { mut srv, pool } = shared::build_server();
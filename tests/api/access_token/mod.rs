@@ -0,0 +1,2 @@
+mod revoke;
+mod revoke_all;
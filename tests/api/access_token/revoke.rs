@@ -0,0 +1,147 @@
+use actix_web::{client::ClientRequest, http, test::TestServer, HttpMessage};
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::authn::jwt::AccessToken;
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{self, FOXFORD_ACCOUNT_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID: Uuid = Uuid::new_v4();
+}
+
+fn before_each_1(conn: &PgConnection) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let _user_account = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID));
+}
+
+#[test]
+fn user_can_revoke_own_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let (jti, token) = generate_access_token(*USER_ACCOUNT_ID);
+    let (_, other_token) = generate_access_token(*USER_ACCOUNT_ID);
+
+    let req = build_request(&srv, &token, build_revoke_request(jti, *USER_ACCOUNT_ID));
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"]["id"], json!(jti));
+    assert_eq!(resp["result"]["data"]["account_id"], json!(*USER_ACCOUNT_ID));
+
+    let req = build_request(&srv, &token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+
+    let req = build_request(&srv, &other_token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"], json!("pong"));
+}
+
+#[test]
+fn client_cannot_revoke_user_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let (jti, _) = generate_access_token(*USER_ACCOUNT_ID);
+    let (_, token) = generate_access_token(*FOXFORD_ACCOUNT_ID);
+
+    let req = build_request(&srv, &token, build_revoke_request(jti, *USER_ACCOUNT_ID));
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn user_cannot_revoke_token_of_another_account() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let (jti, token) = generate_access_token(*FOXFORD_ACCOUNT_ID);
+    let (_, user_token) = generate_access_token(*USER_ACCOUNT_ID);
+
+    // The user is allowed to revoke tokens of its own account only.
+    let req = build_request(
+        &srv,
+        &user_token,
+        build_revoke_request(jti, *USER_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"]["id"], json!(jti));
+
+    let req = build_request(&srv, &token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"], json!("pong"));
+}
+
+fn generate_access_token(sub: Uuid) -> (Uuid, String) {
+    let token = AccessToken::new("foxford.ru".to_owned(), 300, sub);
+    let jti = token.jti().unwrap();
+    (jti, shared::sign_iam_access_token(token))
+}
+
+fn build_request(srv: &TestServer, token: &str, payload: Value) -> ClientRequest {
+    let mut builder = srv.post();
+    builder
+        .content_type("application/json")
+        .header(http::header::AUTHORIZATION, format!("Bearer {}", token))
+        .json(payload)
+        .unwrap()
+}
+
+fn build_revoke_request(jti: Uuid, account_id: Uuid) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "access_token.revoke",
+        "params": [{
+            "id": jti,
+            "account_id": account_id
+        }],
+        "id": "qwerty"
+    })
+}
+
+fn build_ping_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "ping",
+        "params": [],
+        "id": "qwerty"
+    })
+}
@@ -0,0 +1,196 @@
+use actix_web::{client::ClientRequest, http, test::TestServer, HttpMessage};
+use chrono::{Duration, TimeZone, Utc};
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::authn::jwt::AccessToken;
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{self, FOXFORD_ACCOUNT_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID: Uuid = Uuid::new_v4();
+}
+
+fn before_each_1(conn: &PgConnection) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let _user_account = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID));
+}
+
+#[test]
+fn user_can_revoke_own_tokens() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let token = generate_access_token(*USER_ACCOUNT_ID, 10);
+    let payload = build_revoke_all_request(*USER_ACCOUNT_ID, None);
+
+    let req = build_request(&srv, &token, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"]["account_id"], json!(*USER_ACCOUNT_ID));
+    assert!(resp["result"]["issued_before"].is_string());
+
+    let req = build_request(&srv, &token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn tokens_issued_after_remain_valid() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let old_token = generate_access_token(*USER_ACCOUNT_ID, 120);
+    let token = generate_access_token(*USER_ACCOUNT_ID, 10);
+    let issued_before = Utc::now() - Duration::seconds(60);
+    let payload = build_revoke_all_request(*USER_ACCOUNT_ID, Some(json!(issued_before)));
+
+    let req = build_request(&srv, &token, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert!(resp.status().is_success());
+
+    let req = build_request(&srv, &old_token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+
+    let req = build_request(&srv, &token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"], json!("pong"));
+}
+
+#[test]
+fn earlier_issued_before_is_ignored() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let old_token = generate_access_token(*USER_ACCOUNT_ID, 120);
+    let token = generate_access_token(*USER_ACCOUNT_ID, 10);
+    let issued_before = Utc::now() - Duration::seconds(60);
+    let payload = build_revoke_all_request(*USER_ACCOUNT_ID, Some(json!(issued_before)));
+
+    let req = build_request(&srv, &token, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert!(resp.status().is_success());
+
+    let earlier = issued_before - Duration::seconds(240);
+    let payload = build_revoke_all_request(*USER_ACCOUNT_ID, Some(json!(earlier)));
+
+    let req = build_request(&srv, &token, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    // The moment in effect is the later one, truncated to a whole second.
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    let truncated = Utc.timestamp(issued_before.timestamp(), 0);
+    assert_eq!(resp["result"]["issued_before"], json!(truncated));
+
+    let req = build_request(&srv, &old_token, build_ping_request());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn with_issued_before_in_future() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let token = generate_access_token(*USER_ACCOUNT_ID, 0);
+    let issued_before = Utc::now() + Duration::seconds(60);
+    let payload = build_revoke_all_request(*USER_ACCOUNT_ID, Some(json!(issued_before)));
+
+    let req = build_request(&srv, &token, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::BAD_REQUEST);
+}
+
+#[test]
+fn client_cannot_revoke_user_tokens() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let token = generate_access_token(*FOXFORD_ACCOUNT_ID, 0);
+    let payload = build_revoke_all_request(*USER_ACCOUNT_ID, None);
+
+    let req = build_request(&srv, &token, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+/// Tokens are issued `age` seconds ago, since `iat` has a precision of a second.
+fn generate_access_token(sub: Uuid, age: i64) -> String {
+    let mut token = AccessToken::new("foxford.ru".to_owned(), 300, sub);
+    token.iat = token.iat - Duration::seconds(age);
+    shared::sign_iam_access_token(token)
+}
+
+fn build_request(srv: &TestServer, token: &str, payload: Value) -> ClientRequest {
+    let mut builder = srv.post();
+    builder
+        .content_type("application/json")
+        .header(http::header::AUTHORIZATION, format!("Bearer {}", token))
+        .json(payload)
+        .unwrap()
+}
+
+fn build_revoke_all_request(account_id: Uuid, issued_before: Option<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "access_token.revoke_all",
+        "params": [{
+            "account_id": account_id,
+            "issued_before": issued_before
+        }],
+        "id": "qwerty"
+    })
+}
+
+fn build_ping_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "ping",
+        "params": [],
+        "id": "qwerty"
+    })
+}
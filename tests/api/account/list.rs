@@ -0,0 +1,161 @@
+use actix_web::HttpMessage;
+use chrono::{TimeZone, Utc};
+use diesel::{self, prelude::*};
+use serde_json;
+use uuid::Uuid;
+
+use iam::schema::account;
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{self, FOXFORD_ACCOUNT_ID, IAM_ACCOUNT_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
+    static ref USER_ACCOUNT_ID_2: Uuid = Uuid::new_v4();
+    static ref DISABLED_EXPECTED: String = {
+        let template = r#"{
+            "jsonrpc": "2.0",
+            "result": [
+                {
+                    "data": {
+                        "disabled_at": "2018-07-20T19:40:00Z"
+                    },
+                    "id": "USER_ACCOUNT_ID_1"
+                }
+            ],
+            "id": "qwerty"
+        }"#;
+
+        let json = template.replace("USER_ACCOUNT_ID_1", &USER_ACCOUNT_ID_1.to_string());
+        shared::strip_json(&json)
+    };
+    static ref DELETED_EXPECTED: String = {
+        let template = r#"{
+            "jsonrpc": "2.0",
+            "result": [
+                {
+                    "data": {
+                        "deleted_at": "2018-07-21T10:00:00Z",
+                        "disabled_at": null
+                    },
+                    "id": "USER_ACCOUNT_ID_2"
+                }
+            ],
+            "id": "qwerty"
+        }"#;
+
+        let json = template.replace("USER_ACCOUNT_ID_2", &USER_ACCOUNT_ID_2.to_string());
+        shared::strip_json(&json)
+    };
+}
+
+fn before_each_1(conn: &PgConnection) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _ = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let user_account_1 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+    diesel::update(&user_account_1)
+        .set(account::disabled_at.eq(Utc.ymd(2018, 7, 20).and_hms(19, 40, 0)))
+        .execute(conn)
+        .unwrap();
+
+    let user_account_2 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_2));
+    diesel::update(&user_account_2)
+        .set(account::deleted_at.eq(Utc.ymd(2018, 7, 21).and_hms(10, 0, 0)))
+        .execute(conn)
+        .unwrap();
+}
+
+#[test]
+fn admin_can_list_disabled_accounts() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(json!({ "disabled": true }))).unwrap(),
+        Some(*IAM_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *DISABLED_EXPECTED);
+}
+
+#[test]
+fn admin_can_list_deleted_accounts() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(json!({ "deleted": true }))).unwrap(),
+        Some(*IAM_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *DELETED_EXPECTED);
+}
+
+#[test]
+fn client_cannot_list_accounts() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(json!({}))).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn anonymous_cannot_list_accounts() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_anonymous_request(
+        &srv,
+        serde_json::to_string(&build_request(json!({}))).unwrap(),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request(filter: serde_json::Value) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "account.list",
+        "params": [{
+            "filter": filter
+        }],
+        "id": "qwerty"
+    })
+}
@@ -1,3 +1,5 @@
 mod disable;
 mod enable;
+mod list;
 mod read;
+mod rotate_refresh_key;
@@ -2,11 +2,11 @@ use diesel::{self, prelude::*};
 use serde_json;
 use uuid::Uuid;
 
-use iam::models::{Account, Namespace};
+use iam::models::{Account, Identity, Namespace};
 use iam::schema::account;
 
 use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
-use shared::{self, FOXFORD_ACCOUNT_ID, IAM_ACCOUNT_ID};
+use shared::{self, FOXFORD_ACCOUNT_ID, FOXFORD_NAMESPACE_ID, IAM_ACCOUNT_ID};
 
 lazy_static! {
     static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
@@ -463,6 +463,101 @@ mod without_existing_record {
     }
 }
 
+mod with_included_parts {
+    use super::*;
+    use actix_web::HttpMessage;
+    use chrono::NaiveDate;
+
+    use iam::schema::identity;
+
+    lazy_static! {
+        static ref FOXFORD_USER_ID: Uuid = Uuid::new_v4();
+        static ref MOD_EXPECTED: String = {
+            let template = r#"{
+                "jsonrpc": "2.0",
+                "result": {
+                    "data": {
+                        "constraints": {},
+                        "disabled_at": null,
+                        "identities": [
+                            {
+                                "data": {
+                                    "account_id": "USER_ACCOUNT_ID_1",
+                                    "created_at": "2018-06-02T08:40:00Z"
+                                },
+                                "id": {
+                                    "label": "oauth2",
+                                    "provider": "FOXFORD_NAMESPACE_ID",
+                                    "uid": "FOXFORD_USER_ID"
+                                }
+                            }
+                        ],
+                        "namespaces": []
+                    },
+                    "id": "USER_ACCOUNT_ID_1"
+                },
+                "id": "qwerty"
+            }"#;
+
+            let json = template
+                .replace("FOXFORD_USER_ID", &FOXFORD_USER_ID.to_string())
+                .replace("FOXFORD_NAMESPACE_ID", &FOXFORD_NAMESPACE_ID.to_string())
+                .replace("USER_ACCOUNT_ID_1", &USER_ACCOUNT_ID_1.to_string());
+            shared::strip_json(&json)
+        };
+    }
+
+    #[must_use]
+    fn before_each_2(conn: &PgConnection) -> Account {
+        let _ = before_each_1(conn);
+
+        let account = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+
+        let identity = diesel::insert_into(identity::table)
+            .values((
+                identity::provider.eq(*FOXFORD_NAMESPACE_ID),
+                identity::label.eq("oauth2"),
+                identity::uid.eq(FOXFORD_USER_ID.to_string()),
+                identity::account_id.eq(account.id),
+                identity::created_at.eq(NaiveDate::from_ymd(2018, 6, 2).and_hms(8, 40, 0)),
+            ))
+            .get_result::<Identity>(conn)
+            .unwrap();
+
+        shared::db::insert_identity_links(conn, &identity);
+
+        account
+    }
+
+    #[test]
+    fn admin_can_read_user_account() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_2(&conn);
+        }
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "account.read",
+            "params": [{
+                "id": *USER_ACCOUNT_ID_1,
+                "include": ["identities", "namespaces", "constraints"]
+            }],
+            "id": "qwerty"
+        });
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*IAM_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *MOD_EXPECTED);
+    }
+}
+
 fn build_request() -> serde_json::Value {
     json!({
         "jsonrpc": "2.0",
@@ -0,0 +1,153 @@
+use diesel::prelude::*;
+use jsonrpc;
+use serde_json;
+use uuid::Uuid;
+
+use iam::models::RefreshToken;
+use iam::schema::refresh_token;
+
+use shared::db::{
+    create_account, create_namespace, create_operations, create_refresh_token, AccountKind,
+    NamespaceKind,
+};
+use shared::{self, FOXFORD_ACCOUNT_ID, IAM_ACCOUNT_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> RefreshToken {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let user_account_1 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+    create_refresh_token(conn, user_account_1.id)
+}
+
+fn find_record(conn: &PgConnection, id: Uuid) -> RefreshToken {
+    refresh_token::table
+        .find(id)
+        .get_result::<RefreshToken>(conn)
+        .unwrap()
+}
+
+#[test]
+fn admin_can_rotate_refresh_key() {
+    use actix_web::HttpMessage;
+
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let token = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*IAM_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    if let Ok(_) = serde_json::from_slice::<jsonrpc::Success>(&body) {
+        let conn = get_conn!(pool);
+        let record = find_record(&conn, token.id);
+        assert_eq!(record.keys.len(), 2);
+        assert_ne!(record.keys[0], token.keys[0]);
+        assert_eq!(record.keys[1], token.keys[0]);
+    } else {
+        panic!("{:?}", body);
+    }
+}
+
+#[test]
+fn rotation_keeps_limited_number_of_keys() {
+    use actix_web::HttpMessage;
+
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let token = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let keep = {
+        let settings = get_settings!();
+        usize::from(settings.tokens.refresh_keys_kept)
+    };
+
+    for _ in 0..keep + 2 {
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&build_request()).unwrap(),
+            Some(*IAM_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let _ = srv.execute(resp.body()).unwrap();
+    }
+
+    let conn = get_conn!(pool);
+    let record = find_record(&conn, token.id);
+    assert_eq!(record.keys.len(), keep + 1);
+    assert!(!record.keys.contains(&token.keys[0]));
+}
+
+#[test]
+fn client_cannot_rotate_refresh_key() {
+    use actix_web::HttpMessage;
+
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn anonymous_cannot_rotate_refresh_key() {
+    use actix_web::HttpMessage;
+
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req =
+        shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request() -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "account.rotate_refresh_key",
+        "params": [{
+            "id": *USER_ACCOUNT_ID_1
+        }],
+        "id": "qwerty"
+    })
+}
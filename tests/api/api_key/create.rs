@@ -0,0 +1,148 @@
+use actix_web::HttpMessage;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::models::{ApiKey, Secret};
+use iam::schema::api_key;
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{self, FOXFORD_NAMESPACE_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID: Uuid = Uuid::new_v4();
+}
+
+fn before_each_1(conn: &PgConnection) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let _user_account = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID));
+}
+
+#[test]
+fn user_can_create_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(json!({ "label": "script" }))).unwrap(),
+        Some(*USER_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    let id = serde_json::from_value::<Uuid>(resp["result"]["id"].clone()).unwrap();
+    let data = &resp["result"]["data"];
+    assert_eq!(data["account_id"], json!(*USER_ACCOUNT_ID));
+    assert_eq!(data["namespace_ids"], Value::Null);
+    assert_eq!(data["expires_at"], Value::Null);
+
+    let value = data["key"].as_str().unwrap();
+    let (key_id, secret) = ApiKey::parse(value).unwrap();
+    assert_eq!(key_id, id);
+
+    let conn = get_conn!(pool);
+    let key = api_key::table.find(id).get_result::<ApiKey>(&conn).unwrap();
+    assert_eq!(key.account_id, *USER_ACCOUNT_ID);
+    assert!(key.verify(secret));
+    assert_eq!(key.secret_hash, Secret::hash(secret));
+    assert_ne!(key.secret_hash, secret.as_bytes());
+}
+
+#[test]
+fn user_can_create_restricted_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let expires_at = Utc::now() + Duration::days(30);
+    let params = json!({
+        "label": "script",
+        "namespace_ids": [*FOXFORD_NAMESPACE_ID],
+        "expires_at": expires_at,
+    });
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(params)).unwrap(),
+        Some(*USER_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    let id = serde_json::from_value::<Uuid>(resp["result"]["id"].clone()).unwrap();
+
+    let conn = get_conn!(pool);
+    let key = api_key::table.find(id).get_result::<ApiKey>(&conn).unwrap();
+    assert_eq!(key.namespace_ids, Some(vec![*FOXFORD_NAMESPACE_ID]));
+    assert!(key.expires_at.is_some());
+}
+
+#[test]
+fn user_cannot_create_expired_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let params = json!({
+        "label": "script",
+        "expires_at": Utc::now() - Duration::days(1),
+    });
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(params)).unwrap(),
+        Some(*USER_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::BAD_REQUEST);
+}
+
+#[test]
+fn anonymous_cannot_create_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_anonymous_request(
+        &srv,
+        serde_json::to_string(&build_request(json!({ "label": "script" }))).unwrap(),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request(params: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "api_key.create",
+        "params": [params],
+        "id": "qwerty"
+    })
+}
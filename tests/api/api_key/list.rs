@@ -0,0 +1,207 @@
+use actix_web::HttpMessage;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::models::ApiKey;
+
+use shared::db::{
+    create_account, create_api_key, create_namespace, create_operations, AccountKind, NamespaceKind,
+};
+use shared::{self, FOXFORD_NAMESPACE_ID, IAM_NAMESPACE_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
+    static ref USER_ACCOUNT_ID_2: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> Vec<(ApiKey, String)> {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let user_account_1 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+    let user_account_2 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_2));
+    let _ = create_api_key(conn, user_account_2.id, None, None);
+
+    vec![
+        create_api_key(conn, user_account_1.id, None, None),
+        create_api_key(conn, user_account_1.id, None, None),
+    ]
+}
+
+fn extract_ids(body: &[u8]) -> Vec<String> {
+    let resp: Value = serde_json::from_slice(body).unwrap();
+    let items = resp["result"].as_array().expect("Result is not an array");
+    items
+        .iter()
+        .map(|item| {
+            assert_eq!(item["data"]["account_id"], json!(*USER_ACCOUNT_ID_1));
+            assert!(item["data"].get("key").is_none());
+            item["id"].as_str().unwrap().to_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn user_can_list_own_api_keys() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let keys = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*USER_ACCOUNT_ID_1),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let mut expected = keys
+        .iter()
+        .map(|(key, _)| key.id.to_string())
+        .collect::<Vec<_>>();
+    let mut ids = extract_ids(&body);
+    expected.sort();
+    ids.sort();
+    assert_eq!(ids, expected);
+}
+
+#[test]
+fn user_can_list_own_api_keys_with_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let keys = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_api_key_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        &keys[0].1,
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(extract_ids(&body).len(), 2);
+}
+
+#[test]
+fn with_invalid_api_key_secret() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let keys = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let api_key = format!("{}.foo", keys[0].0.id);
+    let req = shared::build_api_key_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        &api_key,
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn with_expired_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let api_key = {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+        let expires_at = Some(Utc::now() - Duration::seconds(1));
+        create_api_key(&conn, *USER_ACCOUNT_ID_1, None, expires_at).1
+    };
+
+    let req = shared::build_api_key_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        &api_key,
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn with_api_key_restricted_to_iam_namespace() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let api_key = {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+        let namespace_ids = Some(vec![*IAM_NAMESPACE_ID]);
+        create_api_key(&conn, *USER_ACCOUNT_ID_1, namespace_ids, None).1
+    };
+
+    let req = shared::build_api_key_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        &api_key,
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(extract_ids(&body).len(), 3);
+}
+
+#[test]
+fn with_api_key_restricted_to_other_namespace() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let api_key = {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+        let namespace_ids = Some(vec![*FOXFORD_NAMESPACE_ID]);
+        create_api_key(&conn, *USER_ACCOUNT_ID_1, namespace_ids, None).1
+    };
+
+    let req = shared::build_api_key_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        &api_key,
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn anonymous_cannot_list_api_keys() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req =
+        shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "api_key.list",
+        "params": [{}],
+        "id": "qwerty"
+    })
+}
@@ -0,0 +1,109 @@
+use actix_web::HttpMessage;
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::models::ApiKey;
+
+use shared;
+use shared::db::{
+    create_account, create_api_key, create_namespace, create_operations, AccountKind, NamespaceKind,
+};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
+    static ref USER_ACCOUNT_ID_2: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> (ApiKey, String) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let user_account_1 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+    let _user_account_2 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_2));
+
+    create_api_key(conn, user_account_1.id, None, None)
+}
+
+#[test]
+fn user_can_revoke_own_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (key, value) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(key.id)).unwrap(),
+        Some(*USER_ACCOUNT_ID_1),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"]["id"], json!(key.id));
+
+    // The key is no longer accepted.
+    let req = shared::build_api_key_request(
+        &srv,
+        serde_json::to_string(&build_request(key.id)).unwrap(),
+        &value,
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn user_cannot_revoke_alien_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (key, _) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(key.id)).unwrap(),
+        Some(*USER_ACCOUNT_ID_2),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::NOT_FOUND);
+}
+
+#[test]
+fn anonymous_cannot_revoke_api_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (key, _) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_anonymous_request(
+        &srv,
+        serde_json::to_string(&build_request(key.id)).unwrap(),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request(id: Uuid) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "api_key.revoke",
+        "params": [{ "id": id }],
+        "id": "qwerty"
+    })
+}
@@ -0,0 +1,235 @@
+use actix_web::{client::ClientRequest, test::TestServer, HttpMessage};
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::authn::{self, jwt};
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{self, NETOLOGY_ACCOUNT_ID};
+
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID: Uuid = Uuid::new_v4();
+    static ref SERVICE_ACCOUNT_ID: Uuid = Uuid::new_v4();
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ErrorResponse {
+    error: String,
+}
+
+fn before_each_1(conn: &PgConnection) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let netology_account = create_account(conn, AccountKind::Netology);
+    let _netology_namespace = create_namespace(conn, NamespaceKind::Netology(netology_account.id));
+
+    let _user_account = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID));
+    let _service_account = create_account(conn, AccountKind::Other(*SERVICE_ACCOUNT_ID));
+}
+
+#[test]
+fn with_access_tokens() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let subject_token = shared::generate_iam_access_token(*USER_ACCOUNT_ID);
+    let actor_token = shared::generate_iam_access_token(*SERVICE_ACCOUNT_ID);
+    let payload = build_payload(&subject_token, &actor_token, "netology.ru", None);
+
+    let resp = exchange(&mut srv, payload);
+    assert_eq!(resp["issued_token_type"], json!(ACCESS_TOKEN_TYPE));
+    assert_eq!(resp["token_type"], json!("Bearer"));
+
+    let token = decode(&resp);
+    assert_eq!(token.aud, "netology.ru");
+    assert_eq!(token.sub, *USER_ACCOUNT_ID);
+    assert_eq!(
+        token.act,
+        Some(jwt::Actor {
+            sub: *SERVICE_ACCOUNT_ID,
+            act: None,
+        })
+    );
+}
+
+#[test]
+fn with_delegated_subject_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let subject_token = {
+        let mut token = jwt::AccessToken::new("foxford.ru".to_owned(), 300, *USER_ACCOUNT_ID);
+        token.act = Some(jwt::Actor {
+            sub: *NETOLOGY_ACCOUNT_ID,
+            act: None,
+        });
+        shared::sign_iam_access_token(token)
+    };
+    let actor_token = shared::generate_iam_access_token(*SERVICE_ACCOUNT_ID);
+    let payload = build_payload(&subject_token, &actor_token, "netology.ru", None);
+
+    let token = decode(&exchange(&mut srv, payload));
+    assert_eq!(
+        token.act,
+        Some(jwt::Actor {
+            sub: *SERVICE_ACCOUNT_ID,
+            act: Some(Box::new(jwt::Actor {
+                sub: *NETOLOGY_ACCOUNT_ID,
+                act: None,
+            })),
+        })
+    );
+}
+
+#[test]
+fn issued_token_never_outlives_subject_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let subject = jwt::AccessToken::new("foxford.ru".to_owned(), 60, *USER_ACCOUNT_ID);
+    let subject_exp = subject.exp;
+    let subject_token = shared::sign_iam_access_token(subject);
+    let actor_token = shared::generate_iam_access_token(*SERVICE_ACCOUNT_ID);
+    let payload = build_payload(&subject_token, &actor_token, "netology.ru", Some(3600));
+
+    let resp = exchange(&mut srv, payload);
+    assert!(resp["expires_in"].as_u64().unwrap() <= 60);
+    assert_eq!(decode(&resp).exp, subject_exp);
+}
+
+#[test]
+fn with_unknown_audience() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let subject_token = shared::generate_iam_access_token(*USER_ACCOUNT_ID);
+    let actor_token = shared::generate_iam_access_token(*SERVICE_ACCOUNT_ID);
+    let payload = build_payload(&subject_token, &actor_token, "example.org", None);
+
+    assert_eq!(exchange_error(&mut srv, payload), "invalid_target");
+}
+
+#[test]
+fn with_client_token_as_subject_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let subject_token = shared::generate_client_access_token(*USER_ACCOUNT_ID);
+    let actor_token = shared::generate_iam_access_token(*SERVICE_ACCOUNT_ID);
+    let payload = build_payload(&subject_token, &actor_token, "netology.ru", None);
+
+    assert_eq!(exchange_error(&mut srv, payload), "invalid_request");
+}
+
+#[test]
+fn without_actor_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let subject_token = shared::generate_iam_access_token(*USER_ACCOUNT_ID);
+    let payload = json!({
+        "grant_type": GRANT_TYPE,
+        "subject_token": subject_token,
+        "subject_token_type": ACCESS_TOKEN_TYPE,
+        "audience": "netology.ru",
+    });
+
+    assert_eq!(exchange_error(&mut srv, payload), "invalid_request");
+}
+
+fn build_payload(
+    subject_token: &str,
+    actor_token: &str,
+    audience: &str,
+    expires_in: Option<u16>,
+) -> Value {
+    let mut payload = json!({
+        "grant_type": GRANT_TYPE,
+        "subject_token": subject_token,
+        "subject_token_type": ACCESS_TOKEN_TYPE,
+        "actor_token": actor_token,
+        "actor_token_type": ACCESS_TOKEN_TYPE,
+        "audience": audience,
+    });
+    if let Some(expires_in) = expires_in {
+        payload["expires_in"] = json!(expires_in);
+    }
+    payload
+}
+
+fn exchange(srv: &mut TestServer, payload: Value) -> Value {
+    let req = build_request(srv, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = srv.execute(resp.body()).unwrap();
+    serde_json::from_slice::<Value>(&body).unwrap()
+}
+
+fn exchange_error(srv: &mut TestServer, payload: Value) -> String {
+    let req = build_request(srv, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let body = srv.execute(resp.body()).unwrap();
+    serde_json::from_slice::<ErrorResponse>(&body).unwrap().error
+}
+
+fn decode(resp: &Value) -> jwt::AccessToken {
+    let raw_token = jwt::RawToken {
+        kind: jwt::RawTokenKind::Iam,
+        value: resp["access_token"].as_str().unwrap(),
+    };
+    jwt::AccessToken::decode(&raw_token).unwrap()
+}
+
+/// Any authentication key is accepted, the new token is issued by IAM itself.
+fn build_request(srv: &TestServer, payload: Value) -> ClientRequest {
+    use actix_web::http::Method;
+
+    let auth_key = authn::AuthKey {
+        provider: "foxford.ru".to_owned(),
+        label: "oauth2".to_owned(),
+    };
+    let url = format!("/auth/{}/token", auth_key);
+    let mut builder = srv.client(Method::POST, &url);
+    builder
+        .content_type("application/json")
+        .json(payload)
+        .unwrap()
+}
@@ -0,0 +1,219 @@
+use actix_web::{client::ClientRequest, http, test::TestServer, HttpMessage};
+use chrono::{Duration, Utc};
+use diesel::{self, prelude::*};
+use serde_json;
+use uuid::Uuid;
+
+use iam::actors::db;
+use iam::authn::introspect::Response;
+use iam::authn::jwt::AccessToken;
+use iam::models::{identity::PrimaryKey, RefreshToken};
+use iam::schema::{account, refresh_token, revoked_access_token};
+
+use shared::db::{
+    create_account, create_namespace, create_operations, create_refresh_token, AccountKind,
+    NamespaceKind,
+};
+use shared::{self, FOXFORD_NAMESPACE_ID};
+
+lazy_static! {
+    static ref FOXFORD_USER_ID: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> RefreshToken {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let pk = PrimaryKey {
+        provider: *FOXFORD_NAMESPACE_ID,
+        label: "oauth2".to_owned(),
+        uid: FOXFORD_USER_ID.to_string(),
+    };
+    let (_, account) = db::identity::insert::insert_identity_with_account(&conn, pk).unwrap();
+
+    create_refresh_token(conn, account.id)
+}
+
+#[test]
+fn without_authorization() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let token = shared::generate_iam_access_token(refresh_token.account_id);
+    let req = {
+        use actix_web::http::Method;
+
+        let mut builder = srv.client(Method::POST, "/introspect");
+        builder
+            .content_type("application/json")
+            .json(json!({ "token": token }))
+            .unwrap()
+    };
+
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+#[test]
+fn with_access_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let token = shared::generate_iam_access_token(refresh_token.account_id);
+    let resp = introspect(&mut srv, &token);
+
+    assert!(resp.active);
+    assert_eq!(resp.sub, Some(refresh_token.account_id));
+    assert_eq!(resp.aud, Some("foxford.ru".to_owned()));
+    assert!(resp.exp.is_some());
+    assert_eq!(resp.disabled, Some(false));
+    assert_eq!(resp.deleted, Some(false));
+}
+
+#[test]
+fn with_access_token_of_disabled_account() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        let refresh_token = before_each_1(&conn);
+
+        diesel::update(account::table.find(refresh_token.account_id))
+            .set(account::disabled_at.eq(diesel::dsl::now))
+            .execute(&conn)
+            .unwrap();
+
+        refresh_token
+    };
+
+    let token = shared::generate_iam_access_token(refresh_token.account_id);
+    let resp = introspect(&mut srv, &token);
+
+    assert!(!resp.active);
+    assert_eq!(resp.sub, Some(refresh_token.account_id));
+    assert_eq!(resp.disabled, Some(true));
+    assert_eq!(resp.deleted, Some(false));
+}
+
+#[test]
+fn with_revoked_access_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let token = {
+        let conn = get_conn!(pool);
+        let refresh_token = before_each_1(&conn);
+
+        let token = AccessToken::new("foxford.ru".to_owned(), 300, refresh_token.account_id);
+        diesel::insert_into(revoked_access_token::table)
+            .values((
+                revoked_access_token::jti.eq(token.jti().unwrap()),
+                revoked_access_token::account_id.eq(refresh_token.account_id),
+                revoked_access_token::expires_at.eq(Utc::now() + Duration::seconds(300)),
+            ))
+            .execute(&conn)
+            .unwrap();
+
+        shared::sign_iam_access_token(token)
+    };
+
+    let resp = introspect(&mut srv, &token);
+    assert!(!resp.active);
+    assert_eq!(resp.sub, None);
+}
+
+#[test]
+fn with_refresh_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let token = shared::generate_refresh_token(&refresh_token);
+    let resp = introspect(&mut srv, &token);
+
+    assert!(resp.active);
+    assert_eq!(resp.sub, Some(refresh_token.account_id));
+    assert_eq!(resp.aud, Some(refresh_token.aud.clone()));
+    assert_eq!(resp.exp, None);
+}
+
+#[test]
+fn with_rotated_refresh_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        let refresh_token = before_each_1(&conn);
+
+        diesel::update(refresh_token::table.find(refresh_token.id))
+            .set(refresh_token::jti.eq(Uuid::new_v4()))
+            .execute(&conn)
+            .unwrap();
+
+        refresh_token
+    };
+
+    let token = shared::generate_refresh_token(&refresh_token);
+    let resp = introspect(&mut srv, &token);
+
+    assert!(!resp.active);
+    assert_eq!(resp.sub, None);
+}
+
+#[test]
+fn with_invalid_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req = build_request(&srv, "qwerty");
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, r#"{"active":false}"#);
+}
+
+fn introspect(srv: &mut TestServer, token: &str) -> Response {
+    let req = build_request(srv, token);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = srv.execute(resp.body()).unwrap();
+    serde_json::from_slice::<Response>(&body).unwrap()
+}
+
+fn build_request(srv: &TestServer, token: &str) -> ClientRequest {
+    use actix_web::http::Method;
+
+    let auth_header = format!("Bearer {}", shared::generate_iam_access_token(Uuid::new_v4()));
+    let mut builder = srv.client(Method::POST, "/introspect");
+
+    builder
+        .content_type("application/json")
+        .header(http::header::AUTHORIZATION, auth_header)
+        .json(json!({ "token": token }))
+        .unwrap()
+}
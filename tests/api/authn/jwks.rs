@@ -0,0 +1,43 @@
+use actix_web::{http, HttpMessage};
+use serde_json;
+
+use iam::authn::jwks::Response;
+
+use shared;
+
+#[test]
+fn with_configured_keys() {
+    let shared::Server { mut srv, pool: _ } = shared::build_server();
+
+    let req = srv
+        .client(http::Method::GET, "/.well-known/jwks.json")
+        .finish()
+        .unwrap();
+
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get(http::header::CACHE_CONTROL).unwrap(),
+        "public, max-age=600"
+    );
+
+    let body = srv.execute(resp.body()).unwrap();
+    let resp = serde_json::from_slice::<Response>(&body).unwrap();
+
+    let kids = resp
+        .keys
+        .iter()
+        .map(|key| key.kid.as_ref().unwrap().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(kids, vec!["iam.1", "iam.2"]);
+
+    for key in resp.keys {
+        assert_eq!(key.kty, "EC");
+        assert_eq!(key.crv.unwrap(), "P-256");
+        assert_eq!(key.alg.unwrap(), "ES256");
+        assert_eq!(key.use_.unwrap(), "sig");
+        // 32 bytes of a coordinate encoded without padding.
+        assert_eq!(key.x.unwrap().len(), 43);
+        assert_eq!(key.y.unwrap().len(), 43);
+    }
+}
@@ -1,3 +1,7 @@
+mod exchange;
+mod introspect;
+mod jwks;
 mod refresh;
 mod retrieve;
 mod revoke;
+mod service_account;
@@ -1,4 +1,5 @@
 use actix_web::{client::ClientRequest, http, test::TestServer, HttpMessage};
+use chrono::{Duration, Utc};
 use diesel::{self, prelude::*};
 use serde::ser::Serialize;
 use serde_json;
@@ -9,7 +10,10 @@ use iam::authn;
 use iam::models::{identity::PrimaryKey, RefreshToken};
 use iam::schema::{account, refresh_token};
 
-use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::db::{
+    create_account, create_namespace, create_operations, create_refresh_token, AccountKind,
+    NamespaceKind,
+};
 use shared::{self, FOXFORD_NAMESPACE_ID};
 
 lazy_static! {
@@ -34,10 +38,9 @@ fn before_each_1(conn: &PgConnection) -> RefreshToken {
         label: "oauth2".to_owned(),
         uid: FOXFORD_USER_ID.to_string(),
     };
-    let (_, _, refresh_token) =
-        db::identity::insert::insert_identity_with_account(&conn, pk).unwrap();
+    let (_, account) = db::identity::insert::insert_identity_with_account(&conn, pk).unwrap();
 
-    refresh_token
+    create_refresh_token(conn, account.id)
 }
 
 #[test]
@@ -200,19 +203,99 @@ fn with_disabled_account() {
 fn without_existing_account() {
     let shared::Server { mut srv, pool } = shared::build_server();
 
-    {
+    let refresh_token = {
         let conn = get_conn!(pool);
-        let _ = before_each_1(&conn);
-    }
+        before_each_1(&conn)
+    };
 
+    // The token is valid, but was issued for another account.
+    let token = shared::generate_refresh_token(&refresh_token);
     let account_id = Uuid::new_v4();
     let payload = json!({});
-    let req = build_request(&srv, &account_id.to_string(), "qwerty", payload);
+    let req = build_request(&srv, &account_id.to_string(), &token, payload);
+
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+#[test]
+fn with_expired_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        let refresh_token = before_each_1(&conn);
+
+        // The session hasn't been used for a year.
+        let long_ago = Utc::now() - Duration::days(365);
+        diesel::update(&refresh_token)
+            .set((
+                refresh_token::created_at.eq(long_ago),
+                refresh_token::last_used_at.eq(long_ago),
+            ))
+            .execute(&conn)
+            .unwrap();
+
+        refresh_token
+    };
+
+    let token = shared::generate_refresh_token(&refresh_token);
+    let req = build_request(&srv, "me", &token, json!({}));
 
     let resp = srv.execute(req.send()).unwrap();
     assert_eq!(resp.status(), 404);
 }
 
+#[test]
+fn with_scoped_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let refresh_token = {
+        let conn = get_conn!(pool);
+        let refresh_token = before_each_1(&conn);
+
+        diesel::update(&refresh_token)
+            .set(refresh_token::scope.eq("namespace:read account:write"))
+            .execute(&conn)
+            .unwrap();
+
+        refresh_token
+    };
+
+    // The scope granted to the session is inherited.
+    let token = shared::generate_refresh_token(&refresh_token);
+    let req = build_request(&srv, "me", &token, json!({}));
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = srv.execute(resp.body()).unwrap();
+    let (access_token, token) = decode_response(&body);
+    assert_eq!(
+        access_token.scopes(),
+        Some(vec![
+            "namespace:read".to_owned(),
+            "account:write".to_owned()
+        ])
+    );
+
+    // It may be narrowed.
+    let req = build_request(&srv, "me", &token, json!({ "scope": "account:write" }));
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = srv.execute(resp.body()).unwrap();
+    let (access_token, token) = decode_response(&body);
+    assert_eq!(
+        access_token.scopes(),
+        Some(vec!["account:write".to_owned()])
+    );
+
+    // But never widened.
+    let req = build_request(&srv, "me", &token, json!({ "scope": "identity:write" }));
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
 mod with_me {
     use super::*;
 
@@ -246,6 +329,124 @@ mod with_me {
         }
     }
 
+    #[test]
+    fn with_rotated_token() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        let refresh_token = {
+            let conn = get_conn!(pool);
+            before_each_1(&conn)
+        };
+
+        let token = shared::generate_refresh_token(&refresh_token);
+        let req = build_request(&srv, "me", &token, json!({}));
+
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = srv.execute(resp.body()).unwrap();
+        let new_token = match serde_json::from_slice::<authn::refresh::Response>(&body) {
+            Ok(resp) => resp.refresh_token.to_owned(),
+            Err(_) => panic!("{:?}", body),
+        };
+
+        let req = build_request(&srv, "me", &new_token, json!({}));
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[test]
+    fn with_reused_token() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        let refresh_token = {
+            let conn = get_conn!(pool);
+            before_each_1(&conn)
+        };
+
+        let token = shared::generate_refresh_token(&refresh_token);
+        let req = build_request(&srv, "me", &token, json!({}));
+
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = srv.execute(resp.body()).unwrap();
+        let new_token = match serde_json::from_slice::<authn::refresh::Response>(&body) {
+            Ok(resp) => resp.refresh_token.to_owned(),
+            Err(_) => panic!("{:?}", body),
+        };
+
+        let req = build_request(&srv, "me", &token, json!({}));
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 401);
+
+        // The whole family is revoked, including the token issued on rotation.
+        let req = build_request(&srv, "me", &new_token, json!({}));
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[test]
+    fn with_token_without_jti() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        let refresh_token = {
+            let conn = get_conn!(pool);
+            before_each_1(&conn)
+        };
+
+        // Issued before rotation, it's the first generation of the session.
+        let mut payload = authn::jwt::RefreshToken::new(
+            refresh_token.aud.clone(),
+            refresh_token.account_id,
+            refresh_token.id,
+            refresh_token.jti,
+        );
+        payload.jti = None;
+        let token = authn::jwt::RefreshToken::encode(&payload, &refresh_token.keys[0]).unwrap();
+
+        let req = build_request(&srv, "me", &token, json!({}));
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let req = build_request(&srv, "me", &token, json!({}));
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[test]
+    fn with_token_without_sid() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        let refresh_token = {
+            let conn = get_conn!(pool);
+            before_each_1(&conn)
+        };
+
+        // Issued before sessions, it belongs to the earliest session of the account.
+        let mut payload = authn::jwt::RefreshToken::new(
+            refresh_token.aud.clone(),
+            refresh_token.account_id,
+            refresh_token.id,
+            refresh_token.jti,
+        );
+        payload.sid = None;
+        payload.jti = None;
+        let token = authn::jwt::RefreshToken::encode(&payload, &refresh_token.keys[0]).unwrap();
+
+        let req = build_request(&srv, "me", &token, json!({}));
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Tokens issued on rotation carry the session.
+        let body = srv.execute(resp.body()).unwrap();
+        let new_token = match serde_json::from_slice::<authn::refresh::Response>(&body) {
+            Ok(resp) => authn::jwt::RefreshToken::decode(&resp.refresh_token, &refresh_token.keys),
+            Err(_) => panic!("{:?}", body),
+        };
+        assert_eq!(new_token.unwrap().sid, Some(refresh_token.id));
+    }
+
     #[test]
     fn with_invalid_signature() {
         let shared::Server { mut srv, pool } = shared::build_server();
@@ -266,6 +467,42 @@ mod with_me {
     }
 }
 
+#[test]
+fn when_token_signed_with_previous_key() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (refresh_token, new_key) = {
+        let conn = get_conn!(pool);
+        let token = before_each_1(&conn);
+
+        let new_key = vec![1; 64];
+        let keys = vec![new_key.clone(), token.keys[0].clone()];
+        diesel::update(refresh_token::table.find(token.id))
+            .set(refresh_token::keys.eq(keys))
+            .execute(&conn)
+            .unwrap();
+
+        (token, new_key)
+    };
+
+    let token = shared::generate_refresh_token(&refresh_token);
+    let payload = json!({});
+    let req = build_request(&srv, "me", &token, payload);
+
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // New tokens are signed with the newest key only.
+    let body = srv.execute(resp.body()).unwrap();
+    if let Ok(resp) = serde_json::from_slice::<authn::refresh::Response>(&body) {
+        let old_keys = &refresh_token.keys;
+        assert!(authn::jwt::RefreshToken::decode(&resp.refresh_token, &[new_key]).is_ok());
+        assert!(authn::jwt::RefreshToken::decode(&resp.refresh_token, old_keys).is_err());
+    } else {
+        panic!("{:?}", body);
+    }
+}
+
 #[test]
 fn when_token_without_key() {
     let shared::Server { mut srv, pool } = shared::build_server();
@@ -274,7 +511,7 @@ fn when_token_without_key() {
         let conn = get_conn!(pool);
         let token = before_each_1(&conn);
 
-        diesel::update(refresh_token::table.find(token.account_id))
+        diesel::update(refresh_token::table.find(token.id))
             .set(refresh_token::keys.eq(Vec::<Vec<u8>>::new()))
             .execute(&conn)
             .unwrap();
@@ -307,3 +544,18 @@ fn build_request<T: Serialize>(
         .json(payload)
         .unwrap()
 }
+
+/// Returns the access token along with the refresh token.
+fn decode_response(body: &[u8]) -> (authn::jwt::AccessToken, String) {
+    match serde_json::from_slice::<authn::refresh::Response>(body) {
+        Ok(resp) => {
+            let raw_token = authn::jwt::RawToken {
+                kind: authn::jwt::RawTokenKind::Iam,
+                value: &resp.access_token,
+            };
+            let access_token = authn::jwt::AccessToken::decode(&raw_token).unwrap();
+            (access_token, resp.refresh_token.to_owned())
+        }
+        Err(_) => panic!("{:?}", body),
+    }
+}
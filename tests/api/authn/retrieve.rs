@@ -20,6 +20,7 @@ lazy_static! {
 #[serde(deny_unknown_fields)]
 struct ErrorResponse {
     error: String,
+    error_description: Option<String>,
 }
 
 #[must_use]
@@ -134,6 +135,38 @@ fn with_invalid_expires_in() {
     }
 }
 
+#[test]
+fn with_invalid_scope() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let auth_key = authn::AuthKey {
+        provider: "foxford.ru".to_owned(),
+        label: "oauth2".to_owned(),
+    };
+    let client_token = shared::generate_client_access_token(*FOXFORD_USER_ID);
+
+    let payload = json!({
+        "grant_type": "client_credentials",
+        "client_token": client_token,
+        "scope": "namespace:read account"
+    });
+    let req = build_request(&srv, &auth_key, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let body = srv.execute(resp.body()).unwrap();
+    if let Ok(resp) = serde_json::from_slice::<ErrorResponse>(&body) {
+        assert_eq!(resp.error, "invalid_scope");
+    } else {
+        panic!("{:?}", body);
+    }
+}
+
 #[test]
 fn with_invalid_client_token_payload() {
     let shared::Server { mut srv, pool } = shared::build_server();
@@ -214,6 +247,130 @@ fn with_expired_client_token() {
     let body = srv.execute(resp.body()).unwrap();
     if let Ok(resp) = serde_json::from_slice::<ErrorResponse>(&body) {
         assert_eq!(resp.error, "invalid_client");
+        assert_eq!(resp.error_description.unwrap(), "Expired client token");
+    } else {
+        panic!("{:?}", body);
+    }
+}
+
+#[test]
+fn with_premature_client_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let auth_key = authn::AuthKey {
+        provider: "foxford.ru".to_owned(),
+        label: "oauth2".to_owned(),
+    };
+    let client_token = {
+        use chrono::Utc;
+
+        // Beyond the leeway of the provider.
+        let now = Utc::now().timestamp();
+        let token = json!({
+            "aud": "iam.netology-group.services".to_owned(),
+            "iss": "foxford.ru".to_owned(),
+            "exp": now + 400,
+            "iat": now,
+            "nbf": now + 100,
+            "sub": *FOXFORD_USER_ID,
+        });
+        shared::sign_client_access_token(token)
+    };
+
+    let payload = json!({
+        "grant_type": "client_credentials",
+        "client_token": client_token,
+    });
+    let req = build_request(&srv, &auth_key, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let body = srv.execute(resp.body()).unwrap();
+    if let Ok(resp) = serde_json::from_slice::<ErrorResponse>(&body) {
+        assert_eq!(resp.error, "invalid_client");
+        assert_eq!(resp.error_description.unwrap(), "Client token isn't valid yet");
+    } else {
+        panic!("{:?}", body);
+    }
+}
+
+#[test]
+fn with_invalid_client_token_issuer() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let auth_key = authn::AuthKey {
+        provider: "foxford.ru".to_owned(),
+        label: "oauth2".to_owned(),
+    };
+    let client_token = {
+        let mut token = authn::jwt::AccessToken::new(
+            "iam.netology-group.services".to_owned(),
+            300,
+            *FOXFORD_USER_ID,
+        );
+        token.iss = "netology.ru".to_owned();
+        shared::sign_client_access_token(token)
+    };
+
+    let payload = json!({
+        "grant_type": "client_credentials",
+        "client_token": client_token,
+    });
+    let req = build_request(&srv, &auth_key, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let body = srv.execute(resp.body()).unwrap();
+    if let Ok(resp) = serde_json::from_slice::<ErrorResponse>(&body) {
+        assert_eq!(resp.error, "invalid_client");
+        assert_eq!(resp.error_description.unwrap(), "Invalid issuer of client token");
+    } else {
+        panic!("{:?}", body);
+    }
+}
+
+#[test]
+fn with_invalid_client_token_audience() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let auth_key = authn::AuthKey {
+        provider: "foxford.ru".to_owned(),
+        label: "oauth2".to_owned(),
+    };
+    let client_token = {
+        let mut token =
+            authn::jwt::AccessToken::new("netology.ru".to_owned(), 300, *FOXFORD_USER_ID);
+        token.iss = "foxford.ru".to_owned();
+        shared::sign_client_access_token(token)
+    };
+
+    let payload = json!({
+        "grant_type": "client_credentials",
+        "client_token": client_token,
+    });
+    let req = build_request(&srv, &auth_key, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let body = srv.execute(resp.body()).unwrap();
+    if let Ok(resp) = serde_json::from_slice::<ErrorResponse>(&body) {
+        assert_eq!(resp.error, "invalid_client");
+        assert_eq!(resp.error_description.unwrap(), "Invalid audience of client token");
     } else {
         panic!("{:?}", body);
     }
@@ -223,7 +380,7 @@ mod with_existing_identity {
     use super::*;
 
     #[must_use]
-    fn before_each_2(conn: &PgConnection) -> (Identity, Account, RefreshToken) {
+    fn before_each_2(conn: &PgConnection) -> (Identity, Account) {
         let _ = before_each_1(conn);
 
         let pk = PrimaryKey {
@@ -238,10 +395,10 @@ mod with_existing_identity {
     fn with_enabled_account() {
         let shared::Server { mut srv, pool } = shared::build_server();
 
-        let (account, created_refresh_token) = {
+        let account = {
             let conn = get_conn!(pool);
-            let (_, account, token) = before_each_2(&conn);
-            (account, token)
+            let (_, account) = before_each_2(&conn);
+            account
         };
 
         let auth_key = authn::AuthKey {
@@ -268,12 +425,63 @@ mod with_existing_identity {
 
             assert_eq!(account.id, access_token.sub);
 
+            // A new session is started for every authentication.
+            let created_refresh_token = {
+                let conn = get_conn!(pool);
+                refresh_token::table
+                    .filter(refresh_token::account_id.eq(account.id))
+                    .get_result::<RefreshToken>(&conn)
+                    .unwrap()
+            };
+
             let refresh_token = authn::jwt::RefreshToken::decode(
                 &resp.refresh_token,
-                &created_refresh_token.keys[0],
-            );
+                &created_refresh_token.keys,
+            ).unwrap();
+
+            assert_eq!(refresh_token.sid, created_refresh_token.id);
+            assert_eq!(refresh_token.aud, created_refresh_token.aud);
+        } else {
+            panic!("{:?}", body);
+        }
+    }
+
+    #[test]
+    fn with_scope() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_2(&conn);
+        }
 
-            assert!(refresh_token.is_ok());
+        let auth_key = authn::AuthKey {
+            provider: "foxford.ru".to_owned(),
+            label: "oauth2".to_owned(),
+        };
+        let client_token = shared::generate_client_access_token(*FOXFORD_USER_ID);
+
+        let payload = json!({
+            "grant_type": "client_credentials",
+            "client_token": client_token,
+            "scope": "namespace:read account:write"
+        });
+        let req = build_request(&srv, &auth_key, payload);
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = srv.execute(resp.body()).unwrap();
+        if let Ok(resp) = serde_json::from_slice::<authn::retrieve::Response>(&body) {
+            let raw_token = authn::jwt::RawToken {
+                kind: authn::jwt::RawTokenKind::Iam,
+                value: &resp.access_token,
+            };
+            let access_token = authn::jwt::AccessToken::decode(&raw_token).unwrap();
+
+            assert_eq!(
+                access_token.scopes(),
+                Some(vec!["namespace:read".to_owned(), "account:write".to_owned()])
+            );
         } else {
             panic!("{:?}", body);
         }
@@ -285,7 +493,7 @@ mod with_existing_identity {
 
         {
             let conn = get_conn!(pool);
-            let (_, account, _) = before_each_2(&conn);
+            let (_, account) = before_each_2(&conn);
 
             diesel::update(account::table.find(account.id))
                 .set(account::disabled_at.eq(diesel::dsl::now))
@@ -360,13 +568,13 @@ mod without_existing_identity {
                 assert_eq!(identity.account_id, account_id);
 
                 let created_refresh_token = refresh_token::table
-                    .find(account_id)
+                    .filter(refresh_token::account_id.eq(account_id))
                     .get_result::<RefreshToken>(&conn)
                     .unwrap();
 
                 let refresh_token = authn::jwt::RefreshToken::decode(
                     &resp.refresh_token,
-                    &created_refresh_token.keys[0],
+                    &created_refresh_token.keys,
                 ).unwrap();
 
                 assert_eq!(refresh_token.sub, account_id);
@@ -377,6 +585,173 @@ mod without_existing_identity {
     }
 }
 
+mod with_provider_jwks {
+    use super::*;
+
+    use frank_jwt;
+    use iam::authn::jwks;
+
+    fn before_each_2(srv: &mut TestServer) -> authn::AuthKey {
+        let auth_key = authn::AuthKey {
+            provider: "foxford.ru".to_owned(),
+            label: "jwks".to_owned(),
+        };
+
+        // IAM publishes its own keys, so it stands in for the provider.
+        let url = srv.url("/.well-known/jwks.json");
+        let keys = srv.execute(jwks::fetch(&url)).unwrap();
+        jwks::store_provider_keys(auth_key.clone(), keys);
+
+        auth_key
+    }
+
+    fn sign_client_access_token(kid: &str) -> String {
+        let aud = "iam.netology-group.services".to_owned();
+        let mut token = authn::jwt::AccessToken::new(aud, 300, *FOXFORD_USER_ID);
+        token.iss = "foxford.ru".to_owned();
+
+        let settings = get_settings!();
+        let key = settings.tokens.active_key().unwrap();
+        frank_jwt::encode(
+            json!({ "kid": kid }),
+            key.private_key.as_ref().unwrap(),
+            &serde_json::to_value(token).unwrap(),
+            frank_jwt::Algorithm::ES256,
+        ).unwrap()
+    }
+
+    #[test]
+    fn with_known_kid() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        let auth_key = before_each_2(&mut srv);
+        let client_token = sign_client_access_token("iam.1");
+
+        let payload = json!({
+            "grant_type": "client_credentials",
+            "client_token": client_token,
+        });
+        let req = build_request(&srv, &auth_key, payload);
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[test]
+    fn with_unknown_kid() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        let auth_key = before_each_2(&mut srv);
+        let client_token = sign_client_access_token("iam.3");
+
+        let payload = json!({
+            "grant_type": "client_credentials",
+            "client_token": client_token,
+        });
+        let req = build_request(&srv, &auth_key, payload);
+        let resp = srv.execute(req.send()).unwrap();
+        assert_eq!(resp.status(), 400);
+
+        let body = srv.execute(resp.body()).unwrap();
+        if let Ok(resp) = serde_json::from_slice::<ErrorResponse>(&body) {
+            assert_eq!(resp.error, "invalid_client");
+        } else {
+            panic!("{:?}", body);
+        }
+    }
+}
+
+mod with_provider_algorithm {
+    use super::*;
+
+    use iam::authn::jwt::Algorithm;
+
+    fn sign_client_access_token(algorithm: Algorithm, keys: &str) -> String {
+        use std::{fs::File, io::Read};
+
+        let read = |path: String| {
+            let mut file = File::open(path).expect("Missing key");
+            let mut key = String::new();
+            file.read_to_string(&mut key).unwrap();
+            key
+        };
+        let private_key = read(format!("tests/keys/{}/private_key.pem", keys));
+        let public_key = read(format!("tests/keys/{}/public_key.pem", keys));
+
+        let aud = "iam.netology-group.services".to_owned();
+        let mut token = authn::jwt::AccessToken::new(aud, 300, *FOXFORD_USER_ID);
+        token.iss = "foxford.ru".to_owned();
+
+        let payload = serde_json::to_value(token).unwrap();
+        algorithm
+            .sign(json!({}), &payload, &private_key, &public_key)
+            .unwrap()
+    }
+
+    fn retrieve(srv: &mut TestServer, label: &str, client_token: String) -> u16 {
+        let auth_key = authn::AuthKey {
+            provider: "foxford.ru".to_owned(),
+            label: label.to_owned(),
+        };
+        let payload = json!({
+            "grant_type": "client_credentials",
+            "client_token": client_token,
+        });
+        let req = build_request(srv, &auth_key, payload);
+        let resp = srv.execute(req.send()).unwrap();
+        resp.status().as_u16()
+    }
+
+    #[test]
+    fn with_rs256() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        let client_token = sign_client_access_token(Algorithm::RS256, "foxford-rsa");
+        assert_eq!(retrieve(&mut srv, "rs256", client_token), 200);
+    }
+
+    #[test]
+    fn with_eddsa() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        let client_token = sign_client_access_token(Algorithm::EdDSA, "foxford-ed25519");
+        assert_eq!(retrieve(&mut srv, "eddsa", client_token), 200);
+    }
+
+    #[test]
+    fn with_algorithm_of_another_provider() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = get_conn!(pool);
+            let _ = before_each_1(&conn);
+        }
+
+        // The provider only accepts RS256.
+        let client_token = shared::generate_client_access_token(*FOXFORD_USER_ID);
+        assert_eq!(retrieve(&mut srv, "rs256", client_token), 400);
+    }
+}
+
 fn build_request<T: Serialize>(
     srv: &TestServer,
     auth_key: &authn::AuthKey,
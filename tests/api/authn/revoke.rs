@@ -8,7 +8,10 @@ use iam::authn;
 use iam::models::{identity::PrimaryKey, RefreshToken};
 use iam::schema::{account, refresh_token};
 
-use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::db::{
+    create_account, create_namespace, create_operations, create_refresh_token, AccountKind,
+    NamespaceKind,
+};
 use shared::{self, FOXFORD_NAMESPACE_ID};
 
 lazy_static! {
@@ -33,10 +36,9 @@ fn before_each_1(conn: &PgConnection) -> RefreshToken {
         label: "oauth2".to_owned(),
         uid: FOXFORD_USER_ID.to_string(),
     };
-    let (_, _, refresh_token) =
-        db::identity::insert::insert_identity_with_account(&conn, pk).unwrap();
+    let (_, account) = db::identity::insert::insert_identity_with_account(&conn, pk).unwrap();
 
-    refresh_token
+    create_refresh_token(conn, account.id)
 }
 
 #[test]
@@ -96,24 +98,84 @@ fn with_enabled_account() {
 
     let body = srv.execute(resp.body()).unwrap();
     if let Ok(resp) = serde_json::from_slice::<authn::revoke::Response>(&body) {
-        let old_key = &old_refresh_token.keys[0];
-        assert!(authn::jwt::RefreshToken::decode(&resp.refresh_token, old_key).is_err());
+        let old_keys = &old_refresh_token.keys;
+        assert!(authn::jwt::RefreshToken::decode(&resp.refresh_token, old_keys).is_err());
 
         let new_refresh_token = {
             let conn = get_conn!(pool);
             refresh_token::table
-                .find(old_refresh_token.account_id)
+                .find(old_refresh_token.id)
                 .get_result::<RefreshToken>(&conn)
                 .unwrap()
         };
-        let new_key = &new_refresh_token.keys[0];
-        let refresh_token = authn::jwt::RefreshToken::decode(&resp.refresh_token, new_key).unwrap();
+        let new_keys = &new_refresh_token.keys;
+        let refresh_token =
+            authn::jwt::RefreshToken::decode(&resp.refresh_token, new_keys).unwrap();
         assert_eq!(refresh_token.sub, old_refresh_token.account_id);
     } else {
         panic!("{:?}", body);
     }
 }
 
+#[test]
+fn with_reused_token() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let old_refresh_token = {
+        let conn = get_conn!(pool);
+        let token = before_each_1(&conn);
+
+        // Simulates the token having been rotated already.
+        diesel::update(refresh_token::table.find(token.id))
+            .set(refresh_token::jti.eq(Uuid::new_v4()))
+            .execute(&conn)
+            .unwrap();
+
+        token
+    };
+
+    let token = shared::generate_refresh_token(&old_refresh_token);
+    let req = build_request(&srv, &old_refresh_token.account_id.to_string(), &token);
+
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 401);
+
+    let new_refresh_token = {
+        let conn = get_conn!(pool);
+        refresh_token::table
+            .find(old_refresh_token.id)
+            .get_result::<RefreshToken>(&conn)
+            .unwrap()
+    };
+    assert_ne!(new_refresh_token.keys, old_refresh_token.keys);
+}
+
+#[test]
+fn with_another_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (refresh_token, other_refresh_token) = {
+        let conn = get_conn!(pool);
+        let token = before_each_1(&conn);
+        let other_token = create_refresh_token(&conn, token.account_id);
+        (token, other_token)
+    };
+
+    let token = shared::generate_refresh_token(&refresh_token);
+    let req = build_request(&srv, "me", &token);
+
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Only the session the token was issued for gets revoked.
+    let conn = get_conn!(pool);
+    let unchanged_refresh_token = refresh_token::table
+        .find(other_refresh_token.id)
+        .get_result::<RefreshToken>(&conn)
+        .unwrap();
+    assert_eq!(unchanged_refresh_token.keys, other_refresh_token.keys);
+}
+
 #[test]
 fn with_disabled_account() {
     let shared::Server { mut srv, pool } = shared::build_server();
@@ -141,13 +203,15 @@ fn with_disabled_account() {
 fn without_existing_account() {
     let shared::Server { mut srv, pool } = shared::build_server();
 
-    {
+    let refresh_token = {
         let conn = get_conn!(pool);
-        let _ = before_each_1(&conn);
-    }
+        before_each_1(&conn)
+    };
 
+    // The token is valid, but was issued for another account.
+    let token = shared::generate_refresh_token(&refresh_token);
     let account_id = Uuid::new_v4();
-    let req = build_request(&srv, &account_id.to_string(), "qwerty");
+    let req = build_request(&srv, &account_id.to_string(), &token);
 
     let resp = srv.execute(req.send()).unwrap();
     assert_eq!(resp.status(), 404);
@@ -173,19 +237,19 @@ mod with_me {
 
         let body = srv.execute(resp.body()).unwrap();
         if let Ok(resp) = serde_json::from_slice::<authn::revoke::Response>(&body) {
-            let old_key = &old_refresh_token.keys[0];
-            assert!(authn::jwt::RefreshToken::decode(&resp.refresh_token, old_key).is_err());
+            let old_keys = &old_refresh_token.keys;
+            assert!(authn::jwt::RefreshToken::decode(&resp.refresh_token, old_keys).is_err());
 
             let new_refresh_token = {
                 let conn = get_conn!(pool);
                 refresh_token::table
-                    .find(old_refresh_token.account_id)
+                    .find(old_refresh_token.id)
                     .get_result::<RefreshToken>(&conn)
                     .unwrap()
             };
-            let new_key = &new_refresh_token.keys[0];
+            let new_keys = &new_refresh_token.keys;
             let refresh_token =
-                authn::jwt::RefreshToken::decode(&resp.refresh_token, new_key).unwrap();
+                authn::jwt::RefreshToken::decode(&resp.refresh_token, new_keys).unwrap();
             assert_eq!(refresh_token.sub, old_refresh_token.account_id);
         } else {
             panic!("{:?}", body);
@@ -219,7 +283,7 @@ fn when_token_without_key() {
         let conn = get_conn!(pool);
         let token = before_each_1(&conn);
 
-        diesel::update(refresh_token::table.find(token.account_id))
+        diesel::update(refresh_token::table.find(token.id))
             .set(refresh_token::keys.eq(Vec::<Vec<u8>>::new()))
             .execute(&conn)
             .unwrap();
@@ -0,0 +1,158 @@
+use actix_web::{client::ClientRequest, test::TestServer, HttpMessage};
+use diesel::{self, prelude::*};
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::actors::db;
+use iam::authn::jwt;
+use iam::models::{Account, ClientCredential, Secret};
+use iam::schema::account;
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ErrorResponse {
+    error: String,
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> (ClientCredential, Account, String) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let netology_account = create_account(conn, AccountKind::Netology);
+    let _netology_namespace = create_namespace(conn, NamespaceKind::Netology(netology_account.id));
+
+    let secret = Secret::generate().unwrap();
+    let msg = db::client_credential::insert::InsertWithAccount {
+        secret_hash: secret.hash,
+    };
+    let (credential, account) =
+        db::client_credential::insert::insert_credential_with_account(conn, msg).unwrap();
+
+    (credential, account, secret.value)
+}
+
+#[test]
+fn with_valid_credentials() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (credential, account, secret) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let payload = build_payload(credential.id, &secret, "netology.ru");
+    let req = build_request(&srv, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = srv.execute(resp.body()).unwrap();
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["token_type"], json!("Bearer"));
+    assert!(resp.get("refresh_token").is_none());
+
+    let raw_token = jwt::RawToken {
+        kind: jwt::RawTokenKind::Iam,
+        value: resp["access_token"].as_str().unwrap(),
+    };
+    let token = jwt::AccessToken::decode(&raw_token).unwrap();
+    assert_eq!(token.sub, account.id);
+    assert_eq!(token.aud, "netology.ru");
+}
+
+#[test]
+fn with_invalid_secret() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (credential, _, _) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let payload = build_payload(credential.id, "foo", "netology.ru");
+    assert_eq!(retrieve_error(&mut srv, payload), "invalid_client");
+}
+
+#[test]
+fn with_unknown_client_id() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (_, _, secret) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let payload = build_payload(Uuid::new_v4(), &secret, "netology.ru");
+    assert_eq!(retrieve_error(&mut srv, payload), "invalid_client");
+}
+
+#[test]
+fn with_unknown_audience() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (credential, _, secret) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let payload = build_payload(credential.id, &secret, "example.org");
+    assert_eq!(retrieve_error(&mut srv, payload), "invalid_target");
+}
+
+#[test]
+fn with_disabled_account() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (credential, _, secret) = {
+        let conn = get_conn!(pool);
+        let (credential, account, secret) = before_each_1(&conn);
+
+        diesel::update(account::table.find(account.id))
+            .set(account::disabled_at.eq(diesel::dsl::now))
+            .execute(&conn)
+            .unwrap();
+
+        (credential, account, secret)
+    };
+
+    let payload = build_payload(credential.id, &secret, "netology.ru");
+    let req = build_request(&srv, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 403);
+}
+
+fn build_payload(client_id: Uuid, client_secret: &str, audience: &str) -> Value {
+    json!({
+        "grant_type": "client_credentials",
+        "client_id": client_id,
+        "client_secret": client_secret,
+        "audience": audience,
+    })
+}
+
+fn retrieve_error(srv: &mut TestServer, payload: Value) -> String {
+    let req = build_request(srv, payload);
+    let resp = srv.execute(req.send()).unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let body = srv.execute(resp.body()).unwrap();
+    serde_json::from_slice::<ErrorResponse>(&body).unwrap().error
+}
+
+fn build_request(srv: &TestServer, payload: Value) -> ClientRequest {
+    use actix_web::http::Method;
+
+    let mut builder = srv.client(Method::POST, "/service_accounts/token");
+    builder
+        .content_type("application/json")
+        .json(payload)
+        .unwrap()
+}
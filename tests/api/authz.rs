@@ -2,13 +2,16 @@ use actix_web::HttpMessage;
 use diesel;
 use diesel::prelude::*;
 use serde_json;
+use uuid::Uuid;
 
 use abac::prelude::*;
 use abac::schema::*;
-use iam::models::{Account, Namespace};
+use iam::models::abac_policy::Effect;
+use iam::models::{Account, Namespace, NewAbacPolicy as NewPolicy};
+use iam::schema::abac_policy as policy;
 
 use shared::db::{create_account, create_namespace, AccountKind, NamespaceKind};
-use shared::{self, IAM_NAMESPACE_ID};
+use shared::{self, FOXFORD_NAMESPACE_ID, IAM_NAMESPACE_ID};
 
 #[must_use]
 fn before_each(conn: &PgConnection) -> (Account, Namespace) {
@@ -178,3 +181,888 @@ fn build_request() -> serde_json::Value {
         "id": "qwerty",
     })
 }
+
+fn insert_policy(conn: &PgConnection, namespace_id: Uuid, effect: Effect) {
+    diesel::insert_into(policy::table)
+        .values(NewPolicy {
+            subject: vec![AbacAttribute {
+                namespace_id: *IAM_NAMESPACE_ID,
+                key: "role".to_owned(),
+                value: "client".to_owned(),
+            }],
+            object: vec![AbacAttribute {
+                namespace_id: *IAM_NAMESPACE_ID,
+                key: "type".to_owned(),
+                value: "room".to_owned(),
+            }],
+            action: vec![AbacAttribute {
+                namespace_id: *IAM_NAMESPACE_ID,
+                key: "operation".to_owned(),
+                value: "read".to_owned(),
+            }],
+            namespace_id,
+            effect,
+            not_before: None,
+            expires_at: None,
+        })
+        .execute(conn)
+        .unwrap();
+}
+
+mod batch {
+    use super::*;
+
+    #[test]
+    fn with_mixed_permissions() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+
+            diesel::insert_into(abac_policy::table)
+                .values(NewAbacPolicy {
+                    subject: vec![AbacAttribute {
+                        namespace_id: namespace.id,
+                        key: "role".to_owned(),
+                        value: "client".to_owned(),
+                    }],
+                    object: vec![AbacAttribute {
+                        namespace_id: namespace.id,
+                        key: "type".to_owned(),
+                        value: "room".to_owned(),
+                    }],
+                    action: vec![AbacAttribute {
+                        namespace_id: namespace.id,
+                        key: "operation".to_owned(),
+                        value: "read".to_owned(),
+                    }],
+                    namespace_id: namespace.id,
+                })
+                .execute(&conn)
+                .unwrap();
+        }
+
+        let payload = build_batch_request(vec![
+            build_check("read", "read"),
+            build_check("create", "create"),
+        ]);
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": {
+                "create": false,
+                "read": true
+            },
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    #[test]
+    fn with_empty_checks() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+        }
+
+        let payload = build_batch_request(vec![]);
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": {},
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    #[test]
+    fn with_duplicate_ids() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+        }
+
+        let payload = build_batch_request(vec![
+            build_check("read", "read"),
+            build_check("read", "create"),
+        ]);
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::BAD_REQUEST);
+    }
+
+    fn build_batch_request(checks: Vec<serde_json::Value>) -> serde_json::Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "authorize.batch",
+            "params": [{
+                "checks": checks,
+            }],
+            "id": "qwerty",
+        })
+    }
+
+    fn build_check(id: &str, operation: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "namespace_ids": [*IAM_NAMESPACE_ID],
+            "subject": [
+                {
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "uri",
+                    "value": "account/25a0c367-756a-42e1-ac5a-e7a2b6b64420"
+                }
+            ],
+            "object": [
+                {
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "uri",
+                    "value": "room/1"
+                }
+            ],
+            "action": [
+                {
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "operation",
+                    "value": operation
+                }
+            ],
+        })
+    }
+}
+
+mod explain {
+    use super::*;
+
+    #[test]
+    fn with_permission() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+            allow_listing_policies(&conn);
+            insert_policy(&conn, namespace.id, "any");
+        }
+
+        let payload = build_explain_request();
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        let result = &resp["result"];
+
+        assert_eq!(result["granted"], json!(true));
+        assert_eq!(result["policies"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            result["policies"][0]["action"],
+            json!([{
+                "namespace_id": *IAM_NAMESPACE_ID,
+                "key": "operation",
+                "value": "any"
+            }])
+        );
+        assert_eq!(
+            result["subject"]["links"],
+            json!([{
+                "inbound": {
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "uri",
+                    "value": "account/25a0c367-756a-42e1-ac5a-e7a2b6b64420"
+                },
+                "outbound": {
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "role",
+                    "value": "client"
+                },
+                "depth": 1
+            }])
+        );
+        assert_eq!(result["candidates"], json!([]));
+    }
+
+    #[test]
+    fn without_permission() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+            allow_listing_policies(&conn);
+            insert_policy(&conn, namespace.id, "delete");
+        }
+
+        let payload = build_explain_request();
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        let result = &resp["result"];
+
+        assert_eq!(result["granted"], json!(false));
+        assert_eq!(result["policies"], json!([]));
+        assert_eq!(
+            result["candidates"][0]["missing"],
+            json!({
+                "subject": [],
+                "object": [],
+                "action": [{
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "operation",
+                    "value": "delete"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn with_permission_of_iam_namespace() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+            allow_listing_policies(&conn);
+            insert_policy(&conn, namespace.id, "any");
+        }
+
+        // The IAM namespace is implied, so are its policies reported.
+        let mut payload = build_explain_request();
+        payload["params"][0]["namespace_ids"] = json!([]);
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        let result = &resp["result"];
+
+        assert_eq!(result["granted"], json!(true));
+        assert_eq!(result["policies"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn anonymous_cannot_explain() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+        }
+
+        let payload = build_explain_request();
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::FORBIDDEN);
+    }
+
+    pub fn allow_listing_policies(conn: &PgConnection) {
+        diesel::insert_into(abac_action::table)
+            .values(NewAbacAction {
+                inbound: AbacAttribute {
+                    namespace_id: *IAM_NAMESPACE_ID,
+                    key: "operation".to_owned(),
+                    value: "list".to_owned(),
+                },
+                outbound: AbacAttribute {
+                    namespace_id: *IAM_NAMESPACE_ID,
+                    key: "operation".to_owned(),
+                    value: "any".to_owned(),
+                },
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn insert_policy(conn: &PgConnection, namespace_id: Uuid, operation: &str) {
+        diesel::insert_into(abac_policy::table)
+            .values(NewAbacPolicy {
+                subject: vec![AbacAttribute {
+                    namespace_id,
+                    key: "role".to_owned(),
+                    value: "client".to_owned(),
+                }],
+                object: vec![AbacAttribute {
+                    namespace_id,
+                    key: "type".to_owned(),
+                    value: "room".to_owned(),
+                }],
+                action: vec![AbacAttribute {
+                    namespace_id,
+                    key: "operation".to_owned(),
+                    value: operation.to_owned(),
+                }],
+                namespace_id,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn build_explain_request() -> serde_json::Value {
+        let mut payload = build_request();
+        payload["method"] = json!("authorize.explain");
+        payload
+    }
+}
+
+mod deny {
+    use super::*;
+
+    use shared::FOXFORD_NAMESPACE_ID;
+
+    #[test]
+    fn deny_overrides_allow_across_namespaces() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+            let foxford_namespace = create_foxford_namespace(&conn);
+
+            insert_policy(&conn, namespace.id, Effect::Allow);
+            insert_policy(&conn, foxford_namespace.id, Effect::Deny);
+        }
+
+        let payload = build_request_within(vec![*IAM_NAMESPACE_ID, *FOXFORD_NAMESPACE_ID]);
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": false,
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    #[test]
+    fn deny_is_ignored_outside_of_requested_namespaces() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+            let foxford_namespace = create_foxford_namespace(&conn);
+
+            insert_policy(&conn, namespace.id, Effect::Allow);
+            insert_policy(&conn, foxford_namespace.id, Effect::Deny);
+        }
+
+        let payload = build_request_within(vec![*IAM_NAMESPACE_ID]);
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": true,
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    #[test]
+    fn deny_without_allow() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+
+            insert_policy(&conn, namespace.id, Effect::Deny);
+        }
+
+        let payload = build_request_within(vec![*IAM_NAMESPACE_ID]);
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": false,
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    fn create_foxford_namespace(conn: &PgConnection) -> Namespace {
+        let foxford_account = create_account(conn, AccountKind::Foxford);
+        create_namespace(conn, NamespaceKind::Foxford(foxford_account.id))
+    }
+
+    fn build_request_within(namespace_ids: Vec<Uuid>) -> serde_json::Value {
+        let mut payload = build_request();
+        payload["params"][0]["namespace_ids"] = json!(namespace_ids);
+        payload
+    }
+}
+
+mod validity {
+    use super::*;
+
+    use chrono::{DateTime, Duration, Utc};
+
+    use iam::models::NewAbacSubject as NewSubject;
+    use iam::schema::abac_subject as subject;
+
+    #[test]
+    fn with_active_policy() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+
+            let now = Utc::now();
+            insert_policy(
+                &conn,
+                namespace.id,
+                Some(now - Duration::hours(1)),
+                Some(now + Duration::hours(1)),
+            );
+        }
+
+        let req =
+            shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, build_response(true));
+    }
+
+    #[test]
+    fn with_expired_policy() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+
+            insert_policy(&conn, namespace.id, None, Some(Utc::now() - Duration::hours(1)));
+        }
+
+        let req =
+            shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, build_response(false));
+    }
+
+    #[test]
+    fn with_pending_policy() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+
+            insert_policy(&conn, namespace.id, Some(Utc::now() + Duration::hours(1)), None);
+        }
+
+        let req =
+            shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, build_response(false));
+    }
+
+    #[test]
+    fn with_expired_subject_link() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let (_account, namespace) = before_each(&conn);
+
+            insert_policy(&conn, namespace.id, None, None);
+
+            // Only the expired link leads to the role the policy is granted to.
+            diesel::delete(subject::table).execute(&conn).unwrap();
+            diesel::insert_into(subject::table)
+                .values(NewSubject {
+                    inbound: AbacAttribute {
+                        namespace_id: namespace.id,
+                        key: "uri".to_owned(),
+                        value: "account/25a0c367-756a-42e1-ac5a-e7a2b6b64420".to_owned(),
+                    },
+                    outbound: AbacAttribute {
+                        namespace_id: namespace.id,
+                        key: "role".to_owned(),
+                        value: "client".to_owned(),
+                    },
+                    not_before: None,
+                    expires_at: Some(Utc::now() - Duration::hours(1)),
+                })
+                .execute(&conn)
+                .unwrap();
+        }
+
+        let req =
+            shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, build_response(false));
+    }
+
+    fn insert_policy(
+        conn: &PgConnection,
+        namespace_id: Uuid,
+        not_before: Option<DateTime<Utc>>,
+        expires_at: Option<DateTime<Utc>>,
+    ) {
+        diesel::insert_into(policy::table)
+            .values(NewPolicy {
+                subject: vec![AbacAttribute {
+                    namespace_id,
+                    key: "role".to_owned(),
+                    value: "client".to_owned(),
+                }],
+                object: vec![AbacAttribute {
+                    namespace_id,
+                    key: "type".to_owned(),
+                    value: "room".to_owned(),
+                }],
+                action: vec![AbacAttribute {
+                    namespace_id,
+                    key: "operation".to_owned(),
+                    value: "any".to_owned(),
+                }],
+                namespace_id,
+                effect: Effect::Allow,
+                not_before,
+                expires_at,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn build_response(granted: bool) -> String {
+        let template = r#"{
+            "jsonrpc": "2.0",
+            "result": GRANTED,
+            "id": "qwerty"
+        }"#;
+        shared::strip_json(&template.replace("GRANTED", &granted.to_string()))
+    }
+}
+
+mod permissions {
+    use super::*;
+
+    lazy_static! {
+        static ref CLIENT_ACCOUNT_ID: Uuid =
+            Uuid::parse_str("25a0c367-756a-42e1-ac5a-e7a2b6b64420").unwrap();
+    }
+
+    #[test]
+    fn can_list_own_permissions() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+        }
+
+        let payload = build_permissions_request();
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*CLIENT_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_template = r#"{
+            "jsonrpc": "2.0",
+            "result": [
+                {
+                    "action": [
+                        {
+                            "key": "operation",
+                            "namespace_id": "IAM_NAMESPACE_ID",
+                            "value": "read"
+                        }
+                    ],
+                    "namespace_id": "IAM_NAMESPACE_ID",
+                    "object": [
+                        {
+                            "key": "type",
+                            "namespace_id": "IAM_NAMESPACE_ID",
+                            "value": "room"
+                        }
+                    ]
+                }
+            ],
+            "id": "qwerty"
+        }"#;
+        let resp_json =
+            resp_template.replace("IAM_NAMESPACE_ID", &IAM_NAMESPACE_ID.to_string());
+        assert_eq!(body, shared::strip_json(&resp_json));
+    }
+
+    #[test]
+    fn denied_permissions_are_not_listed() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Deny);
+        }
+
+        let payload = build_permissions_request();
+        let req = shared::build_auth_request(
+            &srv,
+            serde_json::to_string(&payload).unwrap(),
+            Some(*CLIENT_ACCOUNT_ID),
+        );
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": [],
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    #[test]
+    fn cannot_list_own_permissions_beyond_api_key_namespaces() {
+        use shared::db::create_api_key;
+
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        let api_key = {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+            let _ = create_account(&conn, AccountKind::Other(*CLIENT_ACCOUNT_ID));
+            let namespace_ids = Some(vec![*FOXFORD_NAMESPACE_ID]);
+            create_api_key(&conn, *CLIENT_ACCOUNT_ID, namespace_ids, None).1
+        };
+
+        let payload = build_permissions_request();
+        let req =
+            shared::build_api_key_request(&srv, serde_json::to_string(&payload).unwrap(), &api_key);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::FORBIDDEN);
+    }
+
+    #[test]
+    fn cannot_list_permissions_of_another_account_without_rights() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+        }
+
+        let payload = build_permissions_request();
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::FORBIDDEN);
+    }
+
+    #[test]
+    fn anonymous_cannot_list_permissions() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+        }
+
+        let payload = build_permissions_request();
+        let req = shared::build_anonymous_request(&srv, serde_json::to_string(&payload).unwrap());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::FORBIDDEN);
+    }
+
+    fn build_permissions_request() -> serde_json::Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "authorize.permissions",
+            "params": [{
+                "filter": {
+                    "account_id": *CLIENT_ACCOUNT_ID,
+                    "namespace_ids": [*IAM_NAMESPACE_ID],
+                },
+            }],
+            "id": "qwerty",
+        })
+    }
+}
+
+mod subjects {
+    use super::*;
+
+    #[test]
+    fn can_list_subjects_with_access() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            explain::allow_listing_policies(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+        }
+
+        let payload = build_subjects_request();
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_template = r#"{
+            "jsonrpc": "2.0",
+            "result": [
+                {
+                    "key": "uri",
+                    "namespace_id": "IAM_NAMESPACE_ID",
+                    "value": "account/25a0c367-756a-42e1-ac5a-e7a2b6b64420"
+                }
+            ],
+            "id": "qwerty"
+        }"#;
+        let resp_json =
+            resp_template.replace("IAM_NAMESPACE_ID", &IAM_NAMESPACE_ID.to_string());
+        assert_eq!(body, shared::strip_json(&resp_json));
+    }
+
+    #[test]
+    fn denied_subjects_are_not_listed() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            explain::allow_listing_policies(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Deny);
+        }
+
+        let payload = build_subjects_request();
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp_json = r#"{
+            "jsonrpc": "2.0",
+            "result": [],
+            "id": "qwerty"
+        }"#;
+        assert_eq!(body, shared::strip_json(resp_json));
+    }
+
+    #[test]
+    fn cannot_list_subjects_without_rights() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+            insert_policy(&conn, *IAM_NAMESPACE_ID, Effect::Allow);
+        }
+
+        let payload = build_subjects_request();
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&payload).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, *shared::api::FORBIDDEN);
+    }
+
+    fn build_subjects_request() -> serde_json::Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "authorize.subjects",
+            "params": [{
+                "filter": {
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "object": {
+                        "namespace_id": *IAM_NAMESPACE_ID,
+                        "key": "uri",
+                        "value": "room/1"
+                    },
+                    "action": {
+                        "namespace_id": *IAM_NAMESPACE_ID,
+                        "key": "operation",
+                        "value": "read"
+                    },
+                },
+            }],
+            "id": "qwerty",
+        })
+    }
+}
+
+mod cache {
+    use super::*;
+
+    #[test]
+    fn creating_policy_invalidates_cached_decisions() {
+        let shared::Server { mut srv, pool } = shared::build_server();
+
+        {
+            let conn = pool.get().expect("Failed to get connection from pool");
+            let _ = before_each(&conn);
+        }
+
+        let payload = serde_json::to_string(&build_request()).unwrap();
+
+        let req = shared::build_anonymous_request(&srv, payload.clone());
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, build_response(false));
+
+        let policy = json!({
+            "jsonrpc": "2.0",
+            "method": "abac_policy.create",
+            "params": [{
+                "namespace_id": *IAM_NAMESPACE_ID,
+                "subject": [{
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "role",
+                    "value": "client"
+                }],
+                "object": [{
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "type",
+                    "value": "room"
+                }],
+                "action": [{
+                    "namespace_id": *IAM_NAMESPACE_ID,
+                    "key": "operation",
+                    "value": "read"
+                }]
+            }],
+            "id": "qwerty"
+        });
+        let req = shared::build_auth_request(&srv, serde_json::to_string(&policy).unwrap(), None);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        let resp = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert!(resp.get("result").is_some());
+
+        let req = shared::build_anonymous_request(&srv, payload);
+        let resp = srv.execute(req.send()).unwrap();
+        let body = srv.execute(resp.body()).unwrap();
+        assert_eq!(body, build_response(true));
+    }
+
+    fn build_response(granted: bool) -> String {
+        let template = r#"{
+            "jsonrpc": "2.0",
+            "result": GRANTED,
+            "id": "qwerty"
+        }"#;
+        shared::strip_json(&template.replace("GRANTED", &granted.to_string()))
+    }
+}
@@ -26,10 +26,14 @@ mod abac_action_attr;
 mod abac_object_attr;
 mod abac_policy;
 mod abac_subject_attr;
+mod access_token;
 mod account;
+mod api_key;
 mod authn;
 mod authz;
 mod identity;
 mod namespace;
 mod ping;
 mod rpc;
+mod service_account;
+mod session;
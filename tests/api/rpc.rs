@@ -1,11 +1,40 @@
 use actix_web::{client::ClientRequest, http, test::TestServer, HttpMessage};
 use chrono::{NaiveDateTime, Utc};
+use frank_jwt;
+use serde_json;
 use uuid::Uuid;
 
+use iam::authn;
+
 use shared;
 
 lazy_static! {
     static ref ACCOUNT_ID: Uuid = Uuid::new_v4();
+    static ref METHOD_NOT_FOUND: String = {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "error": {
+                "code": -32601,
+                "message": "Method not found"
+            },
+            "id": "qwerty"
+        }"#;
+        shared::strip_json(json)
+    };
+    static ref INSUFFICIENT_SCOPE: String = {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "error": {
+                "code": 403,
+                "message": "Insufficient scope",
+                "data": {
+                    "scope": "namespace:write"
+                }
+            },
+            "id": "qwerty"
+        }"#;
+        shared::strip_json(json)
+    };
 }
 
 #[test]
@@ -63,6 +92,116 @@ fn with_expired_access_token() {
     assert_eq!(body, *shared::api::UNAUTHORIZED);
 }
 
+#[test]
+fn with_access_token_signed_with_inactive_key() {
+    let shared::Server { mut srv, pool: _ } = shared::build_server();
+
+    let access_token = sign_access_token("iam.2", "tests/keys/iam-2/private_key.pem");
+    let req = build_request(&srv, &format!("Bearer {}", access_token));
+
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *METHOD_NOT_FOUND);
+}
+
+#[test]
+fn with_access_token_signed_with_unknown_key() {
+    let shared::Server { mut srv, pool: _ } = shared::build_server();
+
+    let access_token = sign_access_token("iam.3", "tests/keys/iam-2/private_key.pem");
+    let req = build_request(&srv, &format!("Bearer {}", access_token));
+
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn with_access_token_without_kid() {
+    let shared::Server { mut srv, pool: _ } = shared::build_server();
+
+    // Tokens without `kid` are verified with the active key.
+    let access_token = sign_access_token_without_kid("tests/keys/iam/private_key.pem");
+    let req = build_request(&srv, &format!("Bearer {}", access_token));
+
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *METHOD_NOT_FOUND);
+}
+
+#[test]
+fn with_access_token_signed_with_public_key_as_secret() {
+    let shared::Server { mut srv, pool: _ } = shared::build_server();
+
+    // The algorithm of the token header must not be trusted.
+    let access_token = {
+        use std::{fs::File, io::Read};
+
+        let mut file = File::open("tests/keys/iam/public_key.pem").expect("Missing public key");
+        let mut key = String::new();
+        file.read_to_string(&mut key).unwrap();
+
+        let token = authn::jwt::AccessToken::new("foxford.ru".to_owned(), 300, *ACCOUNT_ID);
+        frank_jwt::encode(
+            json!({ "kid": "iam.1" }),
+            &key,
+            &serde_json::to_value(token).unwrap(),
+            frank_jwt::Algorithm::HS256,
+        ).unwrap()
+    };
+    let req = build_request(&srv, &format!("Bearer {}", access_token));
+
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::UNAUTHORIZED);
+}
+
+#[test]
+fn with_access_token_lacking_method_scope() {
+    let shared::Server { mut srv, pool: _ } = shared::build_server();
+
+    let access_token = {
+        let mut token = authn::jwt::AccessToken::new("foxford.ru".to_owned(), 300, *ACCOUNT_ID);
+        token.scope = Some("namespace:read abac_policy:write".to_owned());
+        shared::sign_iam_access_token(token)
+    };
+    let payload = json!({
+        "jsonrpc": "2.0",
+        "method": "namespace.delete",
+        "params": [{ "id": Uuid::new_v4() }],
+        "id": "qwerty"
+    });
+    let req = build_request_with_payload(&srv, &format!("Bearer {}", access_token), payload);
+
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *INSUFFICIENT_SCOPE);
+}
+
+fn sign_access_token(kid: &str, keyfile: &str) -> String {
+    sign_access_token_with_header(json!({ "kid": kid }), keyfile)
+}
+
+fn sign_access_token_without_kid(keyfile: &str) -> String {
+    sign_access_token_with_header(json!({}), keyfile)
+}
+
+fn sign_access_token_with_header(header: serde_json::Value, keyfile: &str) -> String {
+    use std::{fs::File, io::Read};
+
+    let mut file = File::open(keyfile).expect("Missing private key");
+    let mut key = String::new();
+    file.read_to_string(&mut key).unwrap();
+
+    let token = authn::jwt::AccessToken::new("foxford.ru".to_owned(), 300, *ACCOUNT_ID);
+    frank_jwt::encode(
+        header,
+        &key,
+        &serde_json::to_value(token).unwrap(),
+        frank_jwt::Algorithm::ES256,
+    ).unwrap()
+}
+
 fn build_request(srv: &TestServer, auth_header: &str) -> ClientRequest {
     let payload = json!({
         "jsonrpc": "2.0",
@@ -70,7 +209,14 @@ fn build_request(srv: &TestServer, auth_header: &str) -> ClientRequest {
         "params": [],
         "id": "qwerty"
     });
+    build_request_with_payload(srv, auth_header, payload)
+}
 
+fn build_request_with_payload(
+    srv: &TestServer,
+    auth_header: &str,
+    payload: serde_json::Value,
+) -> ClientRequest {
     let mut builder = srv.post();
     builder
         .content_type("application/json")
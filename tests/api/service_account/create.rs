@@ -0,0 +1,83 @@
+use actix_web::HttpMessage;
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::models::ClientCredential;
+use iam::schema::client_credential;
+
+use shared::db::{create_account, create_namespace, create_operations, AccountKind, NamespaceKind};
+use shared::{self, FOXFORD_ACCOUNT_ID, IAM_ACCOUNT_ID};
+
+fn before_each_1(conn: &PgConnection) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+}
+
+#[test]
+fn admin_can_create_service_account() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*IAM_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    let account_id = serde_json::from_value::<Uuid>(resp["result"]["id"].clone()).unwrap();
+    let client_id = serde_json::from_value::<Uuid>(resp["result"]["data"]["client_id"].clone());
+    let client_secret = resp["result"]["data"]["client_secret"].as_str().unwrap();
+
+    let conn = get_conn!(pool);
+    let credential = client_credential::table
+        .find(client_id.unwrap())
+        .get_result::<ClientCredential>(&conn)
+        .unwrap();
+    assert_eq!(credential.account_id, account_id);
+    assert!(credential.verify(client_secret));
+    assert_ne!(credential.secret_hash, client_secret.as_bytes());
+}
+
+#[test]
+fn client_cannot_create_service_account() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "service_account.create",
+        "params": [{}],
+        "id": "qwerty"
+    })
+}
@@ -0,0 +1,137 @@
+use actix_web::HttpMessage;
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::models::RefreshToken;
+use iam::schema::refresh_token;
+
+use shared::db::{
+    create_account, create_namespace, create_operations, create_refresh_token, AccountKind,
+    NamespaceKind,
+};
+use shared::{self, FOXFORD_ACCOUNT_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
+    static ref USER_ACCOUNT_ID_2: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> (RefreshToken, RefreshToken) {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let user_account_1 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+    let _user_account_2 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_2));
+
+    (
+        create_refresh_token(conn, user_account_1.id),
+        create_refresh_token(conn, user_account_1.id),
+    )
+}
+
+#[test]
+fn user_can_delete_own_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (token, other_token) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(token.id)).unwrap(),
+        Some(*USER_ACCOUNT_ID_1),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let resp: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resp["result"]["id"], json!(token.id));
+
+    let conn = get_conn!(pool);
+    let ids = refresh_token::table
+        .filter(refresh_token::account_id.eq(*USER_ACCOUNT_ID_1))
+        .select(refresh_token::id)
+        .load::<Uuid>(&conn)
+        .unwrap();
+    assert_eq!(ids, vec![other_token.id]);
+}
+
+#[test]
+fn client_cannot_delete_user_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (token, _) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(token.id)).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn user_cannot_delete_alien_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let (token, _) = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(token.id)).unwrap(),
+        Some(*USER_ACCOUNT_ID_2),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn without_existing_session() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request(Uuid::new_v4())).unwrap(),
+        Some(*USER_ACCOUNT_ID_1),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::NOT_FOUND);
+}
+
+fn build_request(id: Uuid) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "session.delete",
+        "params": [{
+            "id": id
+        }],
+        "id": "qwerty"
+    })
+}
@@ -0,0 +1,167 @@
+use actix_web::HttpMessage;
+use diesel::prelude::*;
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use iam::models::RefreshToken;
+
+use shared::db::{
+    create_account, create_namespace, create_operations, create_refresh_token, AccountKind,
+    NamespaceKind,
+};
+use shared::{self, FOXFORD_ACCOUNT_ID, IAM_ACCOUNT_ID};
+
+lazy_static! {
+    static ref USER_ACCOUNT_ID_1: Uuid = Uuid::new_v4();
+    static ref USER_ACCOUNT_ID_2: Uuid = Uuid::new_v4();
+}
+
+#[must_use]
+fn before_each_1(conn: &PgConnection) -> Vec<RefreshToken> {
+    conn.begin_test_transaction()
+        .expect("Failed to begin transaction");
+
+    let iam_account = create_account(conn, AccountKind::Iam);
+    let iam_namespace = create_namespace(conn, NamespaceKind::Iam(iam_account.id));
+
+    create_operations(conn, iam_namespace.id);
+
+    let foxford_account = create_account(conn, AccountKind::Foxford);
+    let _foxford_namespace = create_namespace(conn, NamespaceKind::Foxford(foxford_account.id));
+
+    let user_account_1 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_1));
+    let user_account_2 = create_account(conn, AccountKind::Other(*USER_ACCOUNT_ID_2));
+    let _ = create_refresh_token(conn, user_account_2.id);
+
+    vec![
+        create_refresh_token(conn, user_account_1.id),
+        create_refresh_token(conn, user_account_1.id),
+    ]
+}
+
+fn extract_ids(body: &[u8]) -> Vec<String> {
+    let resp: Value = serde_json::from_slice(body).unwrap();
+    let items = resp["result"].as_array().expect("Result is not an array");
+    items
+        .iter()
+        .map(|item| {
+            assert_eq!(item["data"]["account_id"], json!(*USER_ACCOUNT_ID_1));
+            assert_eq!(item["data"]["aud"], json!("foxford.ru"));
+            assert_eq!(item["data"]["last_used_at"], Value::Null);
+            item["id"].as_str().unwrap().to_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn user_can_list_own_sessions() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    let tokens = {
+        let conn = get_conn!(pool);
+        before_each_1(&conn)
+    };
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*USER_ACCOUNT_ID_1),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+
+    let mut expected = tokens
+        .iter()
+        .map(|token| token.id.to_string())
+        .collect::<Vec<_>>();
+    let mut ids = extract_ids(&body);
+    expected.sort();
+    ids.sort();
+    assert_eq!(ids, expected);
+}
+
+#[test]
+fn admin_can_list_user_sessions() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*IAM_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(extract_ids(&body).len(), 2);
+}
+
+#[test]
+fn client_cannot_list_user_sessions() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*FOXFORD_ACCOUNT_ID),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn user_cannot_list_alien_sessions() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req = shared::build_auth_request(
+        &srv,
+        serde_json::to_string(&build_request()).unwrap(),
+        Some(*USER_ACCOUNT_ID_2),
+    );
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+#[test]
+fn anonymous_cannot_list_sessions() {
+    let shared::Server { mut srv, pool } = shared::build_server();
+
+    {
+        let conn = get_conn!(pool);
+        let _ = before_each_1(&conn);
+    }
+
+    let req =
+        shared::build_anonymous_request(&srv, serde_json::to_string(&build_request()).unwrap());
+    let resp = srv.execute(req.send()).unwrap();
+    let body = srv.execute(resp.body()).unwrap();
+    assert_eq!(body, *shared::api::FORBIDDEN);
+}
+
+fn build_request() -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "session.list",
+        "params": [{
+            "filter": {
+                "account_id": *USER_ACCOUNT_ID_1
+            }
+        }],
+        "id": "qwerty"
+    })
+}
@@ -28,6 +28,17 @@ pub mod request {
         build_rpc_request(srv, json, None)
     }
 
+    pub fn build_api_key_request(
+        srv: &TestServer,
+        json: String,
+        api_key: &str,
+    ) -> actix_web::client::ClientRequest {
+        let mut builder = srv.post();
+        builder.content_type("application/json");
+        builder.header(http::header::AUTHORIZATION, format!("ApiKey {}", api_key));
+        builder.body(json).unwrap()
+    }
+
     fn build_rpc_request(
         srv: &TestServer,
         json: String,
@@ -109,16 +120,21 @@ pub fn generate_client_access_token(sub: Uuid) -> String {
 }
 
 pub fn generate_refresh_token(refresh_token: &iam::models::RefreshToken) -> String {
-    let token =
-        iam::authn::jwt::RefreshToken::new("foxford.ru".to_owned(), refresh_token.account_id);
+    let token = iam::authn::jwt::RefreshToken::new(
+        refresh_token.aud.clone(),
+        refresh_token.account_id,
+        refresh_token.id,
+        refresh_token.jti,
+    );
     iam::authn::jwt::RefreshToken::encode(&token, &refresh_token.keys[0]).unwrap()
 }
 
 pub fn sign_iam_access_token<T: Serialize>(token: T) -> String {
     let settings = get_settings!();
+    let key = settings.tokens.active_key().unwrap();
     frank_jwt::encode(
-        json!({}),
-        &settings.tokens.key,
+        json!({ "kid": key.kid }),
+        key.private_key.as_ref().unwrap(),
         &serde_json::to_value(token).unwrap(),
         frank_jwt::Algorithm::ES256,
     ).unwrap()
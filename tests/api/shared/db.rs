@@ -1,12 +1,14 @@
 use abac::prelude::*;
 use abac::schema::*;
-use chrono::{NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use diesel;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use iam::actors::db;
-use iam::models::*;
+use iam::models::{
+    Account, ApiKey, Identity, Namespace, NewApiKey, NewRefreshToken, RefreshToken, Secret,
+};
 
 use shared::{
     FOXFORD_ACCOUNT_ID, FOXFORD_NAMESPACE_ID, IAM_ACCOUNT_ID, IAM_NAMESPACE_ID,
@@ -173,6 +175,31 @@ pub fn create_operations(conn: &PgConnection, namespace_id: Uuid) {
         .unwrap();
 }
 
+pub fn create_refresh_token(conn: &PgConnection, account_id: Uuid) -> RefreshToken {
+    let changeset = NewRefreshToken::try_new(account_id, "foxford.ru".to_owned()).unwrap();
+    db::refresh_token::insert::insert_token(conn, changeset).unwrap()
+}
+
+/// Returns the key along with its value to be presented.
+pub fn create_api_key(
+    conn: &PgConnection,
+    account_id: Uuid,
+    namespace_ids: Option<Vec<Uuid>>,
+    expires_at: Option<DateTime<Utc>>,
+) -> (ApiKey, String) {
+    let secret = Secret::generate().unwrap();
+    let changeset = NewApiKey {
+        account_id,
+        label: "script".to_owned(),
+        secret_hash: secret.hash,
+        namespace_ids,
+        expires_at,
+    };
+    let key = db::api_key::insert::insert_key(conn, &changeset).unwrap();
+    let value = format!("{}.{}", key.id, secret.value);
+    (key, value)
+}
+
 pub fn insert_identity_links(conn: &PgConnection, identity: &Identity) {
     use iam::abac_attribute::UriKind;
     use iam::models::identity::PrimaryKey;
@@ -1,9 +1,12 @@
 use actix_web::{http, test::TestServer};
 use diesel;
 use iam;
+use iam::actors::db::DecisionCache;
 use uuid::Uuid;
 
-pub use shared::api::request::{build_anonymous_request, build_auth_request};
+use std::sync::Arc;
+
+pub use shared::api::request::{build_anonymous_request, build_api_key_request, build_auth_request};
 pub use shared::api::{
     generate_client_access_token, generate_iam_access_token, generate_refresh_token,
     sign_client_access_token, sign_iam_access_token,
@@ -48,34 +51,46 @@ pub fn build_server() -> Server {
         .expect("Failed to build pool");
 
     let pool1 = pool.clone();
-    let srv =
-        TestServer::build_with_state(move || iam::build_app_state(pool1.clone())).start(|app| {
-            app.resource("/", |r| {
-                r.method(http::Method::POST).with_async(iam::rpc::index)
-            }).resource("/auth/{auth_key}/token", |r| {
-                    use actix_web::{pred, HttpResponse};
-
-                    r.route()
-                        .filter(pred::Not(
-                            pred::Any(pred::Header(
-                                "Content-Type",
-                                "application/x-www-form-urlencoded",
-                            )).or(pred::Header("Content-Type", "application/json")),
-                        ))
-                        .f(|_| HttpResponse::NotAcceptable());
-
-                    r.method(http::Method::POST)
-                        .with_async(iam::authn::retrieve::call)
-                })
-                .resource("/accounts/{key}/refresh", |r| {
-                    r.method(http::Method::POST)
-                        .with_async(iam::authn::refresh::call)
-                })
-                .resource("/accounts/{key}/revoke", |r| {
-                    r.method(http::Method::POST)
-                        .with_async(iam::authn::revoke::call)
-                });
-        });
+    let cache = Arc::new(DecisionCache::from_settings());
+    let state_factory = move || iam::build_app_state(pool1.clone(), cache.clone());
+    let srv = TestServer::build_with_state(state_factory).start(|app| {
+        app.resource("/", |r| {
+            r.method(http::Method::POST).with_async(iam::rpc::index)
+        }).resource("/auth/{auth_key}/token", |r| {
+                use actix_web::{pred, HttpResponse};
+
+                r.route()
+                    .filter(pred::Not(
+                        pred::Any(pred::Header(
+                            "Content-Type",
+                            "application/x-www-form-urlencoded",
+                        )).or(pred::Header("Content-Type", "application/json")),
+                    ))
+                    .f(|_| HttpResponse::NotAcceptable());
+
+                r.method(http::Method::POST)
+                    .with_async(iam::authn::retrieve::call)
+            })
+            .resource("/accounts/{key}/refresh", |r| {
+                r.method(http::Method::POST)
+                    .with_async(iam::authn::refresh::call)
+            })
+            .resource("/accounts/{key}/revoke", |r| {
+                r.method(http::Method::POST)
+                    .with_async(iam::authn::revoke::call)
+            })
+            .resource("/service_accounts/token", |r| {
+                r.method(http::Method::POST)
+                    .with_async(iam::authn::service_account::call)
+            })
+            .resource("/introspect", |r| {
+                r.method(http::Method::POST)
+                    .with_async(iam::authn::introspect::call)
+            })
+            .resource("/.well-known/jwks.json", |r| {
+                r.method(http::Method::GET).f(iam::authn::jwks::call)
+            });
+    });
 
     Server { srv, pool }
 }